@@ -1,8 +1,14 @@
+use std::convert::TryFrom;
+
 pub mod select_server;
 
 /// аргументы, необходимые для создания транзакции в online mode
 #[derive(Debug, Default, clap::Clap)]
 pub struct CliNetworkArgs {
+    /// The network (testnet, mainnet, betanet, localnet or a network of the configuration file)
+    /// instead of a server subcommand
+    #[clap(long)]
+    network: Option<crate::common::ConnectionConfig>,
     #[clap(subcommand)]
     selected_server: Option<self::select_server::CliSelectServer>,
 }
@@ -12,25 +18,28 @@ pub struct NetworkArgs {
     selected_server: self::select_server::SelectServer,
 }
 
-impl From<CliNetworkArgs> for NetworkArgs {
-    fn from(item: CliNetworkArgs) -> Self {
-        let selected_server = match item.selected_server {
-            Some(cli_selected_server) => {
-                self::select_server::SelectServer::from(cli_selected_server)
-            }
-            None => self::select_server::SelectServer::choose_server(),
-        };
-        Self { selected_server }
+impl TryFrom<CliNetworkArgs> for NetworkArgs {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliNetworkArgs) -> color_eyre::eyre::Result<Self> {
+        let selected_server =
+            self::select_server::SelectServer::from_cli(item.network, item.selected_server)?;
+        Ok(Self { selected_server })
     }
 }
 
 impl NetworkArgs {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.selected_server.to_cli_args()
+    }
+
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         self.selected_server
-            .process(prepopulated_unsigned_transaction)
+            .process(prepopulated_unsigned_transaction, output_format)
             .await
     }
 }
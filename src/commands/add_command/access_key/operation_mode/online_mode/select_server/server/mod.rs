@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::{TryFrom, TryInto};
 
 /// предустановленный RPC-сервер
 #[derive(Debug, Default, clap::Clap)]
@@ -16,6 +17,16 @@ pub struct CliCustomServer {
     send_from: Option<CliSendFrom>,
 }
 
+/// A network of the configuration file or a built-in one, selected by its name
+#[derive(Debug, Default, clap::Clap)]
+pub struct CliNamedServer {
+    /// The name of the network (~/.config/near-cli/config.toml)
+    #[clap(long)]
+    pub network: Option<crate::common::ConnectionConfig>,
+    #[clap(flatten)]
+    pub server: CliServer,
+}
+
 #[derive(Debug)]
 pub struct Server {
     pub network_connection_config: Option<crate::common::ConnectionConfig>,
@@ -23,49 +34,70 @@ pub struct Server {
 }
 
 impl CliServer {
-    pub fn into_server(self, network_connection_config: crate::common::ConnectionConfig) -> Server {
+    pub fn into_server(
+        self,
+        network_connection_config: crate::common::ConnectionConfig,
+    ) -> color_eyre::eyre::Result<Server> {
         let send_from = match self.send_from {
-            Some(cli_send_from) => SendFrom::from(cli_send_from),
-            None => SendFrom::choose_send_from(),
+            Some(cli_send_from) => SendFrom::try_from(cli_send_from)?,
+            None => SendFrom::choose_send_from()?,
         };
-        Server {
+        Ok(Server {
             network_connection_config: Some(network_connection_config),
             send_from,
-        }
+        })
     }
 }
 
 impl CliCustomServer {
-    pub fn into_server(self) -> Server {
+    pub fn into_server(self) -> color_eyre::eyre::Result<Server> {
         let url: crate::common::AvailableRpcServerUrl = match self.url {
             Some(url) => url,
-            None => Input::new()
-                .with_prompt("What is the RPC endpoint?")
-                .interact_text()
-                .unwrap(),
+            None => {
+                crate::common::ensure_interactive("`--url <URL>` of `custom`")?;
+                Input::new()
+                    .with_prompt("What is the RPC endpoint?")
+                    .interact_text()
+                    .unwrap()
+            }
         };
+        let connection_config = crate::common::ConnectionConfig::Custom { url: url.inner };
         let send_from = match self.send_from {
-            Some(cli_send_from) => SendFrom::from(cli_send_from),
-            None => SendFrom::choose_send_from(),
+            Some(cli_send_from) => SendFrom::try_from(cli_send_from)?,
+            None => SendFrom::choose_send_from()?,
         };
-        Server {
-            network_connection_config: Some(crate::common::ConnectionConfig::Custom {
-                url: url.inner,
-            }),
+        Ok(Server {
+            network_connection_config: Some(connection_config),
             send_from,
-        }
+        })
+    }
+}
+
+impl CliNamedServer {
+    pub fn into_server(self) -> color_eyre::eyre::Result<Server> {
+        let connection_config = match self.network {
+            Some(connection_config) => connection_config,
+            None => crate::config::choose_network()?,
+        };
+        self.server.into_server(connection_config)
     }
 }
 
 impl Server {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.send_from.to_cli_args()
+    }
+
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         self.send_from
             .process(
                 prepopulated_unsigned_transaction,
                 self.network_connection_config,
+                output_format,
             )
             .await
     }
@@ -82,28 +114,43 @@ pub enum SendFrom {
     Account(super::super::super::super::sender::Sender),
 }
 
-impl From<CliSendFrom> for SendFrom {
-    fn from(item: CliSendFrom) -> Self {
-        match item {
-            CliSendFrom::Account(cli_sender) => Self::Account(cli_sender.into()),
-        }
+impl TryFrom<CliSendFrom> for SendFrom {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSendFrom) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
+            CliSendFrom::Account(cli_sender) => Self::Account(cli_sender.try_into()?),
+        })
     }
 }
 
 impl SendFrom {
-    pub fn choose_send_from() -> Self {
-        Self::from(CliSendFrom::Account(Default::default()))
+    pub fn choose_send_from() -> color_eyre::eyre::Result<Self> {
+        Self::try_from(CliSendFrom::Account(Default::default()))
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (command, mut args) = match self {
+            Self::Account(sender) => ("account", sender.to_cli_args()),
+        };
+        args.insert(0, command.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             SendFrom::Account(sender) => {
                 sender
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
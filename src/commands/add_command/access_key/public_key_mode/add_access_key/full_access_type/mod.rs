@@ -1,3 +1,5 @@
+use std::convert::{TryFrom, TryInto};
+
 /// данные для определения ключа с полным доступом
 #[derive(Debug, Default, clap::Clap)]
 pub struct CliFullAccessType {
@@ -13,23 +15,30 @@ pub struct FullAccessType {
         crate::commands::construct_transaction_command::sign_transaction::SignTransaction,
 }
 
-impl From<CliFullAccessType> for FullAccessType {
-    fn from(item: CliFullAccessType) -> Self {
+impl TryFrom<CliFullAccessType> for FullAccessType {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliFullAccessType) -> color_eyre::eyre::Result<Self> {
         let sign_option = match item.sign_option {
-            Some(cli_sign_transaction) => cli_sign_transaction.into(),
-            None => crate::commands::construct_transaction_command::sign_transaction::SignTransaction::choose_sign_option(),
+            Some(cli_sign_transaction) => cli_sign_transaction.try_into()?,
+            None => crate::commands::construct_transaction_command::sign_transaction::SignTransaction::choose_sign_option()?,
         };
-        Self { sign_option }
+        Ok(Self { sign_option })
     }
 }
 
 impl FullAccessType {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.sign_option.to_cli_args()
+    }
+
     pub async fn process(
         self,
         nonce: near_primitives::types::Nonce,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
         public_key: near_crypto::PublicKey,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let access_key: near_primitives::account::AccessKey = near_primitives::account::AccessKey {
             nonce,
@@ -48,7 +57,11 @@ impl FullAccessType {
             ..prepopulated_unsigned_transaction
         };
         self.sign_option
-            .process(unsigned_transaction, network_connection_config)
+            .process(
+                unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
@@ -1,11 +1,13 @@
 use dialoguer::{console::Term, theme::ColorfulTheme, Input, Select};
+use std::convert::{TryFrom, TryInto};
 use std::vec;
 
 /// данные для определения ключа с function call
 #[derive(Debug, Default, clap::Clap)]
 pub struct CliFunctionCallType {
+    /// An amount of NEAR or `unlimited`
     #[clap(long)]
-    allowance: Option<crate::common::NearBalance>,
+    allowance: Option<crate::common::Allowance>,
     #[clap(long)]
     receiver_id: Option<near_primitives::types::AccountId>,
     #[clap(long)]
@@ -25,15 +27,17 @@ pub struct FunctionCallType {
         crate::commands::construct_transaction_command::sign_transaction::SignTransaction,
 }
 
-impl From<CliFunctionCallType> for FunctionCallType {
-    fn from(item: CliFunctionCallType) -> Self {
+impl TryFrom<CliFunctionCallType> for FunctionCallType {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliFunctionCallType) -> color_eyre::eyre::Result<Self> {
         let allowance: Option<near_primitives::types::Balance> = match item.allowance {
-            Some(cli_allowance) => Some(cli_allowance.to_yoctonear()),
-            None => FunctionCallType::input_allowance(),
+            Some(cli_allowance) => cli_allowance.to_yoctonear(),
+            None => FunctionCallType::input_allowance()?,
         };
         let receiver_id: near_primitives::types::AccountId = match item.receiver_id {
             Some(cli_receiver_id) => near_primitives::types::AccountId::from(cli_receiver_id),
-            None => FunctionCallType::input_receiver_id(),
+            None => FunctionCallType::input_receiver_id()?,
         };
         let method_names: Vec<String> = match item.method_names {
             Some(cli_method_names) => {
@@ -46,23 +50,24 @@ impl From<CliFunctionCallType> for FunctionCallType {
                         .collect::<Vec<String>>()
                 }
             }
-            None => FunctionCallType::input_method_names(),
+            None => FunctionCallType::input_method_names()?,
         };
         let sign_option = match item.sign_option {
-            Some(cli_sign_transaction) => cli_sign_transaction.into(),
-            None => crate::commands::construct_transaction_command::sign_transaction::SignTransaction::choose_sign_option(),
+            Some(cli_sign_transaction) => cli_sign_transaction.try_into()?,
+            None => crate::commands::construct_transaction_command::sign_transaction::SignTransaction::choose_sign_option()?,
         };
-        Self {
+        Ok(Self {
             allowance,
             receiver_id,
             method_names,
             sign_option,
-        }
+        })
     }
 }
 
 impl FunctionCallType {
-    pub fn input_method_names() -> Vec<String> {
+    pub fn input_method_names() -> color_eyre::eyre::Result<Vec<String>> {
+        crate::common::ensure_interactive("`--method-names <METHOD_NAMES>` of `grant-function-call-access` (an empty string allows any method)")?;
         println!();
         let choose_input = vec![
             "Yes, I want to input a list of method names that can be used",
@@ -74,7 +79,7 @@ impl FunctionCallType {
             .default(0)
             .interact_on_opt(&Term::stderr())
             .unwrap();
-        match select_choose_input {
+        Ok(match select_choose_input {
             Some(0) => {
                 let mut input_method_names: String = Input::new()
                     .with_prompt("Enter a list of method names that can be used. The access key only allows transactions with the function call of one of the given method names. Empty list means any method name can be used.")
@@ -94,10 +99,13 @@ impl FunctionCallType {
             }
             Some(1) => vec![],
             _ => unreachable!("Error"),
-        }
+        })
     }
 
-    pub fn input_allowance() -> Option<near_primitives::types::Balance> {
+    pub fn input_allowance() -> color_eyre::eyre::Result<Option<near_primitives::types::Balance>> {
+        crate::common::ensure_interactive(
+            "`--allowance <ALLOWANCE>` of `grant-function-call-access` (`unlimited` for no limit)",
+        )?;
         println!();
         let choose_input = vec![
             "Yes, I want to input allowance for receiver ID",
@@ -109,7 +117,7 @@ impl FunctionCallType {
             .default(0)
             .interact_on_opt(&Term::stderr())
             .unwrap();
-        match select_choose_input {
+        Ok(match select_choose_input {
             Some(0) => {
                 let allowance_near_balance: crate::common::NearBalance = Input::new()
                     .with_prompt("Enter an allowance which is a balance limit to use by this access key to pay for function call gas and transaction fees.")
@@ -119,15 +127,31 @@ impl FunctionCallType {
             }
             Some(1) => None,
             _ => unreachable!("Error"),
-        }
+        })
     }
 
-    pub fn input_receiver_id() -> near_primitives::types::AccountId {
+    pub fn input_receiver_id() -> color_eyre::eyre::Result<near_primitives::types::AccountId> {
+        crate::common::ensure_interactive(
+            "`--receiver-id <RECEIVER_ID>` of `grant-function-call-access`",
+        )?;
         println!();
-        Input::new()
+        Ok(Input::new()
             .with_prompt("Enter a receiver to use by this access key to pay for function call gas and transaction fees.")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--allowance".to_owned(),
+            crate::common::Allowance::from_yoctonear(self.allowance).to_cli_arg(),
+            "--receiver-id".to_owned(),
+            self.receiver_id.clone(),
+            "--method-names".to_owned(),
+            self.method_names.join(","),
+        ];
+        args.extend(self.sign_option.to_cli_args());
+        args
     }
 
     pub async fn process(
@@ -136,6 +160,7 @@ impl FunctionCallType {
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
         public_key: near_crypto::PublicKey,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let access_key: near_primitives::account::AccessKey = near_primitives::account::AccessKey {
             nonce,
@@ -160,7 +185,11 @@ impl FunctionCallType {
             ..prepopulated_unsigned_transaction
         };
         self.sign_option
-            .process(unsigned_transaction, network_connection_config)
+            .process(
+                unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
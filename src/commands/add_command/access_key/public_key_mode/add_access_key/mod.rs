@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Input, Select};
+use std::convert::TryFrom;
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 mod full_access_type;
@@ -20,36 +21,46 @@ pub struct AddAccessKeyAction {
     pub permission: AccessKeyPermission,
 }
 
-impl From<CliAddAccessKeyAction> for AddAccessKeyAction {
-    fn from(item: CliAddAccessKeyAction) -> Self {
+impl TryFrom<CliAddAccessKeyAction> for AddAccessKeyAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliAddAccessKeyAction) -> color_eyre::eyre::Result<Self> {
         let public_key: near_crypto::PublicKey = match item.public_key {
             Some(cli_public_key) => cli_public_key,
-            None => AddAccessKeyAction::input_public_key(),
+            None => AddAccessKeyAction::input_public_key()?,
         };
         let permission: AccessKeyPermission = match item.permission {
-            Some(cli_permission) => AccessKeyPermission::from(cli_permission),
-            None => AccessKeyPermission::choose_permission(),
+            Some(cli_permission) => AccessKeyPermission::try_from(cli_permission)?,
+            None => AccessKeyPermission::choose_permission()?,
         };
-        Self {
+        Ok(Self {
             public_key,
             nonce: 0,
             permission,
-        }
+        })
     }
 }
 
 impl AddAccessKeyAction {
-    pub fn input_public_key() -> near_crypto::PublicKey {
-        Input::new()
+    pub fn input_public_key() -> color_eyre::eyre::Result<near_crypto::PublicKey> {
+        crate::common::ensure_interactive("`<PUBLIC_KEY>` of `public-key`")?;
+        Ok(Input::new()
             .with_prompt("Enter a public key for this access key")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![self.public_key.to_string()];
+        args.extend(self.permission.to_cli_args());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self.permission {
             AccessKeyPermission::GrantFullAccess(full_access_type) => {
@@ -59,6 +70,7 @@ impl AddAccessKeyAction {
                         prepopulated_unsigned_transaction,
                         network_connection_config,
                         self.public_key,
+                        output_format,
                     )
                     .await
             }
@@ -69,6 +81,7 @@ impl AddAccessKeyAction {
                         prepopulated_unsigned_transaction,
                         network_connection_config,
                         self.public_key,
+                        output_format,
                     )
                     .await
             }
@@ -93,26 +106,29 @@ pub enum AccessKeyPermission {
     GrantFullAccess(self::full_access_type::FullAccessType),
 }
 
-impl From<CliAccessKeyPermission> for AccessKeyPermission {
-    fn from(item: CliAccessKeyPermission) -> Self {
-        match item {
+impl TryFrom<CliAccessKeyPermission> for AccessKeyPermission {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliAccessKeyPermission) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliAccessKeyPermission::GrantFunctionCallAccess(cli_function_call_type) => {
                 let function_call_type =
-                    self::function_call_type::FunctionCallType::from(cli_function_call_type);
+                    self::function_call_type::FunctionCallType::try_from(cli_function_call_type)?;
                 AccessKeyPermission::GrantFunctionCallAccess(function_call_type)
             }
             CliAccessKeyPermission::GrantFullAccess(cli_full_access_type) => {
                 let full_access_type =
-                    self::full_access_type::FullAccessType::from(cli_full_access_type);
+                    self::full_access_type::FullAccessType::try_from(cli_full_access_type)?;
                 AccessKeyPermission::GrantFullAccess(full_access_type)
             }
-        }
+        })
     }
 }
 
 impl AccessKeyPermission {
-    pub fn choose_permission() -> Self {
+    pub fn choose_permission() -> color_eyre::eyre::Result<Self> {
         let variants = AccessKeyPermissionDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let permissions = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -123,13 +139,27 @@ impl AccessKeyPermission {
             .default(0)
             .interact()
             .unwrap();
-        match variants[select_permission] {
-            AccessKeyPermissionDiscriminants::GrantFunctionCallAccess => Self::from(
+        Ok(match variants[select_permission] {
+            AccessKeyPermissionDiscriminants::GrantFunctionCallAccess => Self::try_from(
                 CliAccessKeyPermission::GrantFunctionCallAccess(Default::default()),
-            ),
+            )?,
             AccessKeyPermissionDiscriminants::GrantFullAccess => {
-                Self::from(CliAccessKeyPermission::GrantFullAccess(Default::default()))
+                Self::try_from(CliAccessKeyPermission::GrantFullAccess(Default::default()))?
             }
-        }
+        })
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (permission, mut args) = match self {
+            Self::GrantFunctionCallAccess(function_call_type) => (
+                "grant-function-call-access",
+                function_call_type.to_cli_args(),
+            ),
+            Self::GrantFullAccess(full_access_type) => {
+                ("grant-full-access", full_access_type.to_cli_args())
+            }
+        };
+        args.insert(0, permission.to_owned());
+        args
     }
 }
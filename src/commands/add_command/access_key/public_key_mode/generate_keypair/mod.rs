@@ -1,3 +1,4 @@
+use std::convert::TryFrom;
 use std::io::Write;
 use std::str::FromStr;
 
@@ -33,23 +34,30 @@ pub struct GenerateKeypair {
     pub permission: super::add_access_key::AccessKeyPermission,
 }
 
-impl From<CliGenerateKeypair> for GenerateKeypair {
-    fn from(item: CliGenerateKeypair) -> Self {
+impl TryFrom<CliGenerateKeypair> for GenerateKeypair {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliGenerateKeypair) -> color_eyre::eyre::Result<Self> {
         let permission: super::add_access_key::AccessKeyPermission = match item.permission {
             Some(cli_permission) => {
-                super::add_access_key::AccessKeyPermission::from(cli_permission)
+                super::add_access_key::AccessKeyPermission::try_from(cli_permission)?
             }
-            None => super::add_access_key::AccessKeyPermission::choose_permission(),
+            None => super::add_access_key::AccessKeyPermission::choose_permission()?,
         };
-        Self { permission }
+        Ok(Self { permission })
     }
 }
 
 impl GenerateKeypair {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.permission.to_cli_args()
+    }
+
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let new_master_seed_phrase_words_count: usize = 12;
         let seed_phrase_hd_path = slip10::BIP32Path::from_str("m/44'/397'/0'").unwrap();
@@ -135,6 +143,7 @@ impl GenerateKeypair {
                         prepopulated_unsigned_transaction,
                         network_connection_config,
                         public_key,
+                        output_format,
                     )
                     .await
             }
@@ -147,6 +156,7 @@ impl GenerateKeypair {
                         prepopulated_unsigned_transaction,
                         network_connection_config,
                         public_key,
+                        output_format,
                     )
                     .await
             }
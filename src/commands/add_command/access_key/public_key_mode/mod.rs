@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::{TryFrom, TryInto};
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 mod add_access_key;
@@ -21,22 +22,25 @@ pub enum PublicKeyMode {
     GenerateKeypair(self::generate_keypair::GenerateKeypair),
 }
 
-impl From<CliPublicKeyMode> for PublicKeyMode {
-    fn from(item: CliPublicKeyMode) -> Self {
-        match item {
+impl TryFrom<CliPublicKeyMode> for PublicKeyMode {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliPublicKeyMode) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliPublicKeyMode::PublicKey(cli_add_access_key_action) => {
-                PublicKeyMode::PublicKey(cli_add_access_key_action.into())
+                PublicKeyMode::PublicKey(cli_add_access_key_action.try_into()?)
             }
             CliPublicKeyMode::GenerateKeypair(cli_generate_keypair) => {
-                PublicKeyMode::GenerateKeypair(cli_generate_keypair.into())
+                PublicKeyMode::GenerateKeypair(cli_generate_keypair.try_into()?)
             }
-        }
+        })
     }
 }
 
 impl PublicKeyMode {
-    pub fn choose_public_key_mode() -> Self {
+    pub fn choose_public_key_mode() -> color_eyre::eyre::Result<Self> {
         let variants = PublicKeyModeDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let modes = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -47,30 +51,52 @@ impl PublicKeyMode {
             .default(0)
             .interact()
             .unwrap();
-        match variants[select_mode] {
+        Ok(match variants[select_mode] {
             PublicKeyModeDiscriminants::PublicKey => {
-                Self::from(CliPublicKeyMode::PublicKey(Default::default()))
+                Self::try_from(CliPublicKeyMode::PublicKey(Default::default()))?
             }
             PublicKeyModeDiscriminants::GenerateKeypair => {
-                Self::from(CliPublicKeyMode::GenerateKeypair(Default::default()))
+                Self::try_from(CliPublicKeyMode::GenerateKeypair(Default::default()))?
             }
-        }
+        })
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (mode, mut args) = match self {
+            Self::PublicKey(add_access_key_action) => {
+                ("public-key", add_access_key_action.to_cli_args())
+            }
+            Self::GenerateKeypair(generate_keypair) => {
+                ("generate-keypair", generate_keypair.to_cli_args())
+            }
+        };
+        args.insert(0, mode.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             PublicKeyMode::PublicKey(add_access_key_action) => {
                 add_access_key_action
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
             PublicKeyMode::GenerateKeypair(cli_generate_keypair) => {
                 cli_generate_keypair
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 /// данные об отправителе транзакции
 #[derive(Debug, Default, clap::Clap)]
@@ -14,38 +15,48 @@ pub struct Sender {
     pub public_key_mode: super::public_key_mode::PublicKeyMode,
 }
 
-impl From<CliSender> for Sender {
-    fn from(item: CliSender) -> Self {
+impl TryFrom<CliSender> for Sender {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSender) -> color_eyre::eyre::Result<Self> {
         let sender_account_id: String = match item.sender_account_id {
             Some(cli_sender_account_id) => cli_sender_account_id,
-            None => Sender::input_sender_account_id(),
+            None => Sender::input_sender_account_id()?,
         };
         let public_key_mode = match item.public_key_mode {
             Some(cli_public_key_mode) => {
-                super::public_key_mode::PublicKeyMode::from(cli_public_key_mode)
+                super::public_key_mode::PublicKeyMode::try_from(cli_public_key_mode)?
             }
-            None => super::public_key_mode::PublicKeyMode::choose_public_key_mode(),
+            None => super::public_key_mode::PublicKeyMode::choose_public_key_mode()?,
         };
-        Self {
+        Ok(Self {
             sender_account_id,
             public_key_mode,
-        }
+        })
     }
 }
 
 impl Sender {
-    pub fn input_sender_account_id() -> String {
+    pub fn input_sender_account_id() -> color_eyre::eyre::Result<String> {
+        crate::common::ensure_interactive("`<SENDER_ACCOUNT_ID>` of `account`")?;
         println!();
-        Input::new()
+        Ok(Input::new()
             .with_prompt("What is the account ID of the sender?")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![self.sender_account_id.clone()];
+        args.extend(self.public_key_mode.to_cli_args());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let unsigned_transaction = near_primitives::transaction::Transaction {
             signer_id: self.sender_account_id.clone(),
@@ -53,7 +64,11 @@ impl Sender {
             ..prepopulated_unsigned_transaction
         };
         self.public_key_mode
-            .process(unsigned_transaction, network_connection_config)
+            .process(
+                unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::{TryFrom, TryInto};
 
 /// вызов CallFunction
 #[derive(Debug, Default, clap::Clap)]
@@ -25,50 +26,54 @@ pub struct CallFunctionAction {
         crate::commands::construct_transaction_command::sign_transaction::SignTransaction,
 }
 
-impl From<CliCallFunctionAction> for CallFunctionAction {
-    fn from(item: CliCallFunctionAction) -> Self {
+impl TryFrom<CliCallFunctionAction> for CallFunctionAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliCallFunctionAction) -> color_eyre::eyre::Result<Self> {
         let method_name: String = match item.method_name {
             Some(cli_method_name) => cli_method_name,
-            None => CallFunctionAction::input_method_name(),
+            None => CallFunctionAction::input_method_name()?,
         };
         let args: Vec<u8> = match item.args {
             Some(cli_args) => cli_args.into_bytes(),
-            None => CallFunctionAction::input_args(),
+            None => CallFunctionAction::input_args()?,
         };
         let gas: near_primitives::types::Gas = match item.gas {
             Some(cli_gas) => match cli_gas {
                 crate::common::NearGas { inner: num } => num,
             },
-            None => CallFunctionAction::input_gas(),
+            None => CallFunctionAction::input_gas()?,
         };
         let deposit: near_primitives::types::Balance = match item.deposit {
             Some(cli_deposit) => cli_deposit.to_yoctonear(),
-            None => CallFunctionAction::input_deposit(),
+            None => CallFunctionAction::input_deposit()?,
         };
         let sign_option = match item.sign_option {
-            Some(cli_sign_transaction) => cli_sign_transaction.into(),
-            None => crate::commands::construct_transaction_command::sign_transaction::SignTransaction::choose_sign_option(),
+            Some(cli_sign_transaction) => cli_sign_transaction.try_into()?,
+            None => crate::commands::construct_transaction_command::sign_transaction::SignTransaction::choose_sign_option()?,
         };
-        Self {
+        Ok(Self {
             method_name,
             args,
             gas,
             deposit,
             sign_option,
-        }
+        })
     }
 }
 
 impl CallFunctionAction {
-    fn input_method_name() -> String {
+    fn input_method_name() -> color_eyre::eyre::Result<String> {
+        crate::common::ensure_interactive("`<METHOD_NAME>` of `initialize`")?;
         println!();
-        Input::new()
+        Ok(Input::new()
             .with_prompt("Enter a method name")
             .interact_text()
-            .unwrap()
+            .unwrap())
     }
 
-    fn input_gas() -> near_primitives::types::Gas {
+    fn input_gas() -> color_eyre::eyre::Result<near_primitives::types::Gas> {
+        crate::common::ensure_interactive("`--prepaid-gas <PREPAID_GAS>` of `initialize`")?;
         println!();
         let gas: u64 = loop {
             let input_gas: crate::common::NearGas = Input::new()
@@ -85,19 +90,23 @@ impl CallFunctionAction {
                 println!("You need to enter a value of no more than 200 TERAGAS")
             }
         };
-        gas
+        Ok(gas)
     }
 
-    fn input_args() -> Vec<u8> {
+    fn input_args() -> color_eyre::eyre::Result<Vec<u8>> {
+        crate::common::ensure_interactive("`<ARGS>` of `initialize`")?;
         println!();
         let input: String = Input::new()
             .with_prompt("Enter args for function")
             .interact_text()
             .unwrap();
-        input.into_bytes()
+        Ok(input.into_bytes())
     }
 
-    fn input_deposit() -> near_primitives::types::Balance {
+    fn input_deposit() -> color_eyre::eyre::Result<near_primitives::types::Balance> {
+        crate::common::ensure_interactive(
+            "`--attached-deposit <ATTACHED_DEPOSIT>` of `initialize`",
+        )?;
         println!();
         let deposit: crate::common::NearBalance = Input::new()
             .with_prompt(
@@ -106,13 +115,27 @@ impl CallFunctionAction {
             .with_initial_text("0 NEAR")
             .interact_text()
             .unwrap();
-        deposit.to_yoctonear()
+        Ok(deposit.to_yoctonear())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![
+            self.method_name.clone(),
+            String::from_utf8_lossy(&self.args).into_owned(),
+            "--attached-deposit".to_owned(),
+            crate::common::NearBalance::from_yoctonear(self.deposit).to_cli_arg(),
+            "--prepaid-gas".to_owned(),
+            crate::common::NearGas { inner: self.gas }.to_cli_arg(),
+        ];
+        args.extend(self.sign_option.to_cli_args());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let action = near_primitives::transaction::Action::FunctionCall(
             near_primitives::transaction::FunctionCallAction {
@@ -129,7 +152,11 @@ impl CallFunctionAction {
             ..prepopulated_unsigned_transaction
         };
         self.sign_option
-            .process(unsigned_transaction, network_connection_config)
+            .process(
+                unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
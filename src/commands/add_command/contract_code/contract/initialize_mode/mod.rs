@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::{TryFrom, TryInto};
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 mod call_function_type;
@@ -20,23 +21,26 @@ pub enum NextAction {
     NoInitialize(NoInitialize),
 }
 
-impl From<CliNextAction> for NextAction {
-    fn from(item: CliNextAction) -> Self {
-        match item {
+impl TryFrom<CliNextAction> for NextAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliNextAction) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliNextAction::Initialize(cli_call_function_action) => {
-                NextAction::Initialize(cli_call_function_action.into())
+                NextAction::Initialize(cli_call_function_action.try_into()?)
             }
             CliNextAction::NoInitialize(cli_no_initialize) => {
-                NextAction::NoInitialize(cli_no_initialize.into())
+                NextAction::NoInitialize(cli_no_initialize.try_into()?)
             }
-        }
+        })
     }
 }
 
 impl NextAction {
-    pub fn choose_next_action() -> Self {
+    pub fn choose_next_action() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = NextActionDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let actions = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -53,23 +57,43 @@ impl NextAction {
                 CliNextAction::NoInitialize(Default::default())
             }
         };
-        Self::from(cli_action)
+        Self::try_from(cli_action)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (next_action, mut args) = match self {
+            Self::Initialize(call_function_action) => {
+                ("initialize", call_function_action.to_cli_args())
+            }
+            Self::NoInitialize(no_initialize) => ("no-initialize", no_initialize.to_cli_args()),
+        };
+        args.insert(0, next_action.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             NextAction::Initialize(call_function_action) => {
                 call_function_action
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
             NextAction::NoInitialize(no_initialize) => {
                 no_initialize
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
@@ -91,24 +115,35 @@ pub struct NoInitialize {
         crate::commands::construct_transaction_command::sign_transaction::SignTransaction,
 }
 
-impl From<CliNoInitialize> for NoInitialize {
-    fn from(item: CliNoInitialize) -> Self {
+impl TryFrom<CliNoInitialize> for NoInitialize {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliNoInitialize) -> color_eyre::eyre::Result<Self> {
         let sign_option = match item.sign_option {
-            Some(cli_sign_transaction) => cli_sign_transaction.into(),
-            None => crate::commands::construct_transaction_command::sign_transaction::SignTransaction::choose_sign_option(),
+            Some(cli_sign_transaction) => cli_sign_transaction.try_into()?,
+            None => crate::commands::construct_transaction_command::sign_transaction::SignTransaction::choose_sign_option()?,
         };
-        Self { sign_option }
+        Ok(Self { sign_option })
     }
 }
 
 impl NoInitialize {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.sign_option.to_cli_args()
+    }
+
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         self.sign_option
-            .process(prepopulated_unsigned_transaction, network_connection_config)
+            .process(
+                prepopulated_unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Input, Select};
+use std::convert::{TryFrom, TryInto};
 use std::io::Read;
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
@@ -17,20 +18,23 @@ pub enum Contract {
     ContractFile(ContractFile),
 }
 
-impl From<CliContract> for Contract {
-    fn from(item: CliContract) -> Self {
-        match item {
+impl TryFrom<CliContract> for Contract {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliContract) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliContract::ContractFile(cli_contract_file) => {
-                Contract::ContractFile(cli_contract_file.into())
+                Contract::ContractFile(cli_contract_file.try_into()?)
             }
-        }
+        })
     }
 }
 
 impl Contract {
-    pub fn choose_contract() -> Self {
+    pub fn choose_contract() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = ContractDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let contracts = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -44,18 +48,31 @@ impl Contract {
         let cli_contract = match variants[selected_contract] {
             ContractDiscriminants::ContractFile => CliContract::ContractFile(Default::default()),
         };
-        Self::from(cli_contract)
+        Self::try_from(cli_contract)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (contract, mut args) = match self {
+            Self::ContractFile(contract_file) => ("contract-file", contract_file.to_cli_args()),
+        };
+        args.insert(0, contract.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             Contract::ContractFile(contract_file) => {
                 contract_file
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
@@ -76,25 +93,28 @@ pub struct ContractFile {
     next_action: self::initialize_mode::NextAction,
 }
 
-impl From<CliContractFile> for ContractFile {
-    fn from(item: CliContractFile) -> Self {
+impl TryFrom<CliContractFile> for ContractFile {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliContractFile) -> color_eyre::eyre::Result<Self> {
         let file_path = match item.file_path {
             Some(cli_file_path) => cli_file_path,
-            None => ContractFile::input_file_path(),
+            None => ContractFile::input_file_path()?,
         };
         let next_action = match item.next_action {
-            Some(cli_next_action) => self::initialize_mode::NextAction::from(cli_next_action),
-            None => self::initialize_mode::NextAction::choose_next_action(),
+            Some(cli_next_action) => self::initialize_mode::NextAction::try_from(cli_next_action)?,
+            None => self::initialize_mode::NextAction::choose_next_action()?,
         };
-        ContractFile {
+        Ok(ContractFile {
             file_path,
             next_action,
-        }
+        })
     }
 }
 
 impl ContractFile {
-    fn input_file_path() -> std::path::PathBuf {
+    fn input_file_path() -> color_eyre::eyre::Result<std::path::PathBuf> {
+        crate::common::ensure_interactive("`<FILE_PATH>` of `contract-file`")?;
         println!();
         let input_file_path: String = Input::new()
             .with_prompt("What is a file location of the contract?")
@@ -103,13 +123,20 @@ impl ContractFile {
         let mut path = std::path::PathBuf::new();
         path.push(input_file_path);
         println!("path: {:?}", &path);
-        path
+        Ok(path)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![self.file_path.display().to_string()];
+        args.extend(self.next_action.to_cli_args());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let mut code = Vec::new();
         std::fs::File::open(&self.file_path.clone())
@@ -126,7 +153,11 @@ impl ContractFile {
             ..prepopulated_unsigned_transaction
         };
         self.next_action
-            .process(unsigned_transaction, network_connection_config)
+            .process(
+                unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
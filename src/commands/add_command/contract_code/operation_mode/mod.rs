@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::{TryFrom, TryInto};
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 mod offline_mode;
@@ -16,22 +17,31 @@ pub struct OperationMode {
     pub mode: Mode,
 }
 
-impl From<CliOperationMode> for OperationMode {
-    fn from(item: CliOperationMode) -> Self {
+impl TryFrom<CliOperationMode> for OperationMode {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliOperationMode) -> color_eyre::eyre::Result<Self> {
         let mode = match item.mode {
-            Some(cli_mode) => Mode::from(cli_mode),
-            None => Mode::choose_mode(),
+            Some(cli_mode) => Mode::try_from(cli_mode)?,
+            None => Mode::choose_mode()?,
         };
-        Self { mode }
+        Ok(Self { mode })
     }
 }
 
 impl OperationMode {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.mode.to_cli_args()
+    }
+
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
-        self.mode.process(prepopulated_unsigned_transaction).await
+        self.mode
+            .process(prepopulated_unsigned_transaction, output_format)
+            .await
     }
 }
 
@@ -54,19 +64,22 @@ pub enum Mode {
     Offline(self::offline_mode::OfflineArgs),
 }
 
-impl From<CliMode> for Mode {
-    fn from(item: CliMode) -> Self {
-        match item {
-            CliMode::Network(cli_network_args) => Self::Network(cli_network_args.into()),
-            CliMode::Offline(cli_offline_args) => Self::Offline(cli_offline_args.into()),
-        }
+impl TryFrom<CliMode> for Mode {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliMode) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
+            CliMode::Network(cli_network_args) => Self::Network(cli_network_args.try_into()?),
+            CliMode::Offline(cli_offline_args) => Self::Offline(cli_offline_args.try_into()?),
+        })
     }
 }
 
 impl Mode {
-    pub fn choose_mode() -> Self {
+    pub fn choose_mode() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = ModeDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let modes = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -84,22 +97,32 @@ impl Mode {
             ModeDiscriminants::Network => CliMode::Network(Default::default()),
             ModeDiscriminants::Offline => CliMode::Offline(Default::default()),
         };
-        Self::from(cli_mode)
+        Self::try_from(cli_mode)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (mode, mut args) = match self {
+            Self::Network(network_args) => ("network", network_args.to_cli_args()),
+            Self::Offline(offline_args) => ("offline", offline_args.to_cli_args()),
+        };
+        args.insert(0, mode.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             Self::Network(network_args) => {
                 network_args
-                    .process(prepopulated_unsigned_transaction)
+                    .process(prepopulated_unsigned_transaction, output_format)
                     .await
             }
             Self::Offline(offline_args) => {
                 offline_args
-                    .process(prepopulated_unsigned_transaction)
+                    .process(prepopulated_unsigned_transaction, output_format)
                     .await
             }
         }
@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 /// данные об аккаунте контракта
 #[derive(Debug, Default, clap::Clap)]
@@ -14,36 +15,46 @@ pub struct Sender {
     pub contract: super::contract::Contract,
 }
 
-impl From<CliSender> for Sender {
-    fn from(item: CliSender) -> Self {
+impl TryFrom<CliSender> for Sender {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSender) -> color_eyre::eyre::Result<Self> {
         let sender_account_id: String = match item.sender_account_id {
             Some(cli_sender_account_id) => cli_sender_account_id,
-            None => Sender::input_sender_account_id(),
+            None => Sender::input_sender_account_id()?,
         };
         let contract = match item.contract {
-            Some(cli_contract) => super::contract::Contract::from(cli_contract),
-            None => super::contract::Contract::choose_contract(),
+            Some(cli_contract) => super::contract::Contract::try_from(cli_contract)?,
+            None => super::contract::Contract::choose_contract()?,
         };
-        Self {
+        Ok(Self {
             sender_account_id,
             contract,
-        }
+        })
     }
 }
 
 impl Sender {
-    pub fn input_sender_account_id() -> String {
+    pub fn input_sender_account_id() -> color_eyre::eyre::Result<String> {
+        crate::common::ensure_interactive("`<SENDER_ACCOUNT_ID>` of `account`")?;
         println!();
-        Input::new()
+        Ok(Input::new()
             .with_prompt("What is the account ID of the contract?")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![self.sender_account_id.clone()];
+        args.extend(self.contract.to_cli_args());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let unsigned_transaction = near_primitives::transaction::Transaction {
             signer_id: self.sender_account_id.clone(),
@@ -51,7 +62,11 @@ impl Sender {
             ..prepopulated_unsigned_transaction
         };
         self.contract
-            .process(unsigned_transaction, network_connection_config)
+            .process(
+                unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
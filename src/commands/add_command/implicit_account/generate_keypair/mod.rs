@@ -1,4 +1,3 @@
-use std::io::Write;
 use std::str::FromStr;
 
 fn bip32path_to_string(bip32path: &slip10::BIP32Path) -> String {
@@ -23,9 +22,20 @@ fn bip32path_to_string(bip32path: &slip10::BIP32Path) -> String {
 /// Generate a key pair of secret and public keys (use it anywhere you need
 /// Ed25519 keys)
 #[derive(Debug, Default, clap::Clap)]
-pub struct CliGenerateKeypair {}
+pub struct CliGenerateKeypair {
+    /// Where the keys are kept: file (default) or secret-service
+    #[clap(long)]
+    key_store: Option<crate::key_store::KeyStoreKind>,
+}
 
 impl CliGenerateKeypair {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        match self.key_store {
+            Some(key_store) => vec!["--key-store".to_owned(), <&str>::from(key_store).to_owned()],
+            None => vec![],
+        }
+    }
+
     pub async fn process(self) -> crate::CliResult {
         let new_master_seed_phrase_words_count: usize = 12;
         let seed_phrase_hd_path = slip10::BIP32Path::from_str("m/44'/397'/0'").unwrap();
@@ -68,38 +78,28 @@ impl CliGenerateKeypair {
             bs58::encode(&secret_keypair.to_bytes()).into_string()
         );
 
-        let buf = format!(
-            "{}",
-            serde_json::json!({
-            "master_seed_phrase": master_seed_phrase,
-            "seed_phrase_hd_path": bip32path_to_string(&seed_phrase_hd_path),
-            "account_id": implicit_account_id,
-            "public_key": public_key_str,
-            "private_key": secret_keypair_str,
-            })
-        );
-        let home_dir = dirs::home_dir().expect("Impossible to get your home dir!");
-        let file_name: std::path::PathBuf = format!("{}.json", &implicit_account_id).into();
-        let mut path = std::path::PathBuf::from(&home_dir);
-        path.push(crate::consts::DIR_NAME_KEY_CHAIN);
-        std::fs::create_dir_all(&path)?;
-        path.push(file_name);
-        if path.exists() {
+        let stored_key = crate::key_store::StoredKey {
+            account_id: implicit_account_id,
+            public_key: near_crypto::PublicKey::from_str(&public_key_str)?,
+            private_key: Some(near_crypto::SecretKey::from_str(&secret_keypair_str)?),
+            master_seed_phrase: Some(master_seed_phrase),
+            seed_phrase_hd_path: Some(bip32path_to_string(&seed_phrase_hd_path)),
+            encrypted_secrets: None,
+        };
+        let key_store = self.key_store.unwrap_or_default().open()?;
+        let network = crate::key_store::KeyChainNetwork::default_key_chain();
+        if let Some((location, _)) = key_store
+            .find(&network, &stored_key.account_id)?
+            .into_iter()
+            .next()
+        {
             return Err(color_eyre::Report::msg(format!(
-                "The file: {} already exists!",
-                &path.display()
+                "The key of {} already exists: {}",
+                &stored_key.account_id, location
             )));
         };
-        std::fs::File::create(&path)
-            .map_err(|err| color_eyre::Report::msg(format!("Failed to create file: {:?}", err)))?
-            .write(buf.as_bytes())
-            .map_err(|err| {
-                color_eyre::Report::msg(format!("Failed to write to file: {:?}", err))
-            })?;
-        println!(
-            "The data for the access key is saved in a file {}",
-            &path.display()
-        );
+        let location = key_store.save(&network, &stored_key)?;
+        println!("The data for the access key is saved in {}", location);
         Ok(())
     }
 }
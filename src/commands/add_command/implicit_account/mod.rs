@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::TryFrom;
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 mod generate_keypair;
@@ -15,17 +16,23 @@ pub struct ImplicitAccount {
     pub public_key_mode: PublicKeyMode,
 }
 
-impl From<CliImplicitAccount> for ImplicitAccount {
-    fn from(item: CliImplicitAccount) -> Self {
+impl TryFrom<CliImplicitAccount> for ImplicitAccount {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliImplicitAccount) -> color_eyre::eyre::Result<Self> {
         let public_key_mode = match item.public_key_mode {
-            Some(cli_public_key_mode) => PublicKeyMode::from(cli_public_key_mode),
-            None => PublicKeyMode::choose_public_key_mode(),
+            Some(cli_public_key_mode) => PublicKeyMode::try_from(cli_public_key_mode)?,
+            None => PublicKeyMode::choose_public_key_mode()?,
         };
-        Self { public_key_mode }
+        Ok(Self { public_key_mode })
     }
 }
 
 impl ImplicitAccount {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.public_key_mode.to_cli_args()
+    }
+
     pub async fn process(self) -> crate::CliResult {
         self.public_key_mode.process().await
     }
@@ -44,19 +51,22 @@ pub enum PublicKeyMode {
     GenerateKeypair(self::generate_keypair::CliGenerateKeypair),
 }
 
-impl From<CliPublicKeyMode> for PublicKeyMode {
-    fn from(item: CliPublicKeyMode) -> Self {
-        match item {
+impl TryFrom<CliPublicKeyMode> for PublicKeyMode {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliPublicKeyMode) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliPublicKeyMode::GenerateKeypair(cli_generate_keypair) => {
                 PublicKeyMode::GenerateKeypair(cli_generate_keypair)
             }
-        }
+        })
     }
 }
 
 impl PublicKeyMode {
-    pub fn choose_public_key_mode() -> Self {
+    pub fn choose_public_key_mode() -> color_eyre::eyre::Result<Self> {
         let variants = PublicKeyModeDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let modes = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -67,11 +77,21 @@ impl PublicKeyMode {
             .default(0)
             .interact()
             .unwrap();
-        match variants[select_mode] {
+        Ok(match variants[select_mode] {
             PublicKeyModeDiscriminants::GenerateKeypair => {
-                Self::from(CliPublicKeyMode::GenerateKeypair(Default::default()))
+                Self::try_from(CliPublicKeyMode::GenerateKeypair(Default::default()))?
             }
-        }
+        })
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (mode, mut args) = match self {
+            Self::GenerateKeypair(cli_generate_keypair) => {
+                ("generate-keypair", cli_generate_keypair.to_cli_args())
+            }
+        };
+        args.insert(0, mode.to_owned());
+        args
     }
 
     pub async fn process(self) -> crate::CliResult {
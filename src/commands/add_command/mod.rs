@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::{TryFrom, TryInto};
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 mod access_key;
@@ -19,22 +20,31 @@ pub struct AddAction {
     pub action: Action,
 }
 
-impl From<CliAddAction> for AddAction {
-    fn from(item: CliAddAction) -> Self {
+impl TryFrom<CliAddAction> for AddAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliAddAction) -> color_eyre::eyre::Result<Self> {
         let action = match item.action {
-            Some(cli_action) => cli_action.into(),
-            None => Action::choose_action(),
+            Some(cli_action) => cli_action.try_into()?,
+            None => Action::choose_action()?,
         };
-        Self { action }
+        Ok(Self { action })
     }
 }
 
 impl AddAction {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.action.to_cli_args()
+    }
+
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
-        self.action.process(prepopulated_unsigned_transaction).await
+        self.action
+            .process(prepopulated_unsigned_transaction, output_format)
+            .await
     }
 }
 
@@ -67,32 +77,35 @@ pub enum Action {
     SubAccount(self::sub_account::operation_mode::OperationMode),
 }
 
-impl From<CliAction> for Action {
-    fn from(item: CliAction) -> Self {
-        match item {
+impl TryFrom<CliAction> for Action {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliAction) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliAction::AccessKey(cli_operation_mode) => {
-                Action::AccessKey(cli_operation_mode.into())
+                Action::AccessKey(cli_operation_mode.try_into()?)
             }
             CliAction::ContractCode(cli_operation_mode) => {
-                Action::ContractCode(cli_operation_mode.into())
+                Action::ContractCode(cli_operation_mode.try_into()?)
             }
             CliAction::ImplicitAccount(cli_generate_keypair) => {
-                Action::ImplicitAccount(cli_generate_keypair.into())
+                Action::ImplicitAccount(cli_generate_keypair.try_into()?)
             }
             CliAction::StakeProposal(cli_operation_mode) => {
-                Action::StakeProposal(cli_operation_mode.into())
+                Action::StakeProposal(cli_operation_mode.try_into()?)
             }
             CliAction::SubAccount(cli_operation_mode) => {
-                Action::SubAccount(cli_operation_mode.into())
+                Action::SubAccount(cli_operation_mode.try_into()?)
             }
-        }
+        })
     }
 }
 
 impl Action {
-    fn choose_action() -> Self {
+    fn choose_action() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = ActionDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let actions = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -110,33 +123,48 @@ impl Action {
             ActionDiscriminants::StakeProposal => CliAction::StakeProposal(Default::default()),
             ActionDiscriminants::SubAccount => CliAction::SubAccount(Default::default()),
         };
-        Self::from(cli_action)
+        Self::try_from(cli_action)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (action, mut args) = match self {
+            Self::AccessKey(operation_mode) => ("access-key", operation_mode.to_cli_args()),
+            Self::ContractCode(operation_mode) => ("contract-code", operation_mode.to_cli_args()),
+            Self::ImplicitAccount(implicit_account) => {
+                ("implicit-account", implicit_account.to_cli_args())
+            }
+            Self::StakeProposal(operation_mode) => ("stake-proposal", operation_mode.to_cli_args()),
+            Self::SubAccount(operation_mode) => ("sub-account", operation_mode.to_cli_args()),
+        };
+        args.insert(0, action.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             Action::AccessKey(operation_mode) => {
                 operation_mode
-                    .process(prepopulated_unsigned_transaction)
+                    .process(prepopulated_unsigned_transaction, output_format)
                     .await
             }
             Action::ContractCode(operation_mode) => {
                 operation_mode
-                    .process(prepopulated_unsigned_transaction)
+                    .process(prepopulated_unsigned_transaction, output_format)
                     .await
             }
             Action::ImplicitAccount(generate_keypair) => generate_keypair.process().await,
             Action::StakeProposal(operation_mode) => {
                 operation_mode
-                    .process(prepopulated_unsigned_transaction)
+                    .process(prepopulated_unsigned_transaction, output_format)
                     .await
             }
             Action::SubAccount(operation_mode) => {
                 operation_mode
-                    .process(prepopulated_unsigned_transaction)
+                    .process(prepopulated_unsigned_transaction, output_format)
                     .await
             }
         }
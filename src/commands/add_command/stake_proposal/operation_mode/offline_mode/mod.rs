@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 /// аргументы, необходимые для создания трансфера в offline mode
 #[derive(Debug, Default, clap::Clap)]
@@ -18,52 +19,68 @@ pub struct OfflineArgs {
     send_from: super::online_mode::select_server::server::SendFrom,
 }
 
-impl From<CliOfflineArgs> for OfflineArgs {
-    fn from(item: CliOfflineArgs) -> Self {
+impl TryFrom<CliOfflineArgs> for OfflineArgs {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliOfflineArgs) -> color_eyre::eyre::Result<Self> {
         let nonce: u64 = match item.nonce {
             Some(cli_nonce) => cli_nonce,
-            None => OfflineArgs::input_nonce(),
+            None => OfflineArgs::input_nonce()?,
         };
         let block_hash = match item.block_hash {
             Some(cli_block_hash) => cli_block_hash.inner,
-            None => OfflineArgs::input_block_hash(),
+            None => OfflineArgs::input_block_hash()?,
         };
         let send_from = match item.send_from {
             Some(cli_send_from) => {
-                super::online_mode::select_server::server::SendFrom::from(cli_send_from)
+                super::online_mode::select_server::server::SendFrom::try_from(cli_send_from)?
             }
-            None => super::online_mode::select_server::server::SendFrom::choose_send_from(),
+            None => super::online_mode::select_server::server::SendFrom::choose_send_from()?,
         };
-        Self {
+        Ok(Self {
             nonce,
             block_hash,
             send_from,
-        }
+        })
     }
 }
 
 impl OfflineArgs {
-    fn input_nonce() -> u64 {
-        Input::new()
+    fn input_nonce() -> color_eyre::eyre::Result<u64> {
+        crate::common::ensure_interactive("`--nonce <NONCE>` of `offline`")?;
+        Ok(Input::new()
             .with_prompt(
                 "Enter transaction nonce (query the access key information with \
                 `near-cli utils view-access-key frol4.testnet ed25519:...` incremented by 1)",
             )
             .interact_text()
-            .unwrap()
+            .unwrap())
     }
 
-    fn input_block_hash() -> near_primitives::hash::CryptoHash {
+    fn input_block_hash() -> color_eyre::eyre::Result<near_primitives::hash::CryptoHash> {
+        crate::common::ensure_interactive("`--block-hash <BLOCK_HASH>` of `offline`")?;
         let input_block_hash: crate::common::BlockHashAsBase58 = Input::new()
             .with_prompt("Enter recent block hash")
             .interact_text()
             .unwrap();
-        input_block_hash.inner
+        Ok(input_block_hash.inner)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--nonce".to_owned(),
+            self.nonce.to_string(),
+            "--block-hash".to_owned(),
+            self.block_hash.to_string(),
+        ];
+        args.extend(self.send_from.to_cli_args());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let nonce = self.nonce.clone();
         let block_hash = self.block_hash.clone();
@@ -74,7 +91,7 @@ impl OfflineArgs {
         };
         let selected_server_url = None;
         self.send_from
-            .process(unsigned_transaction, selected_server_url)
+            .process(unsigned_transaction, selected_server_url, output_format)
             .await
     }
 }
@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::{TryFrom, TryInto};
 
 #[derive(Debug, clap::Clap)]
 pub enum CliTransactionsSigning {
@@ -11,28 +12,42 @@ pub enum TransactionsSigning {
     TransactionsSigningPublicKey(TransactionsSigningAction),
 }
 
-impl From<CliTransactionsSigning> for TransactionsSigning {
-    fn from(item: CliTransactionsSigning) -> Self {
-        match item {
+impl TryFrom<CliTransactionsSigning> for TransactionsSigning {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliTransactionsSigning) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliTransactionsSigning::TransactionsSigningPublicKey(
                 cli_transactions_signing_action,
-            ) => Self::TransactionsSigningPublicKey(cli_transactions_signing_action.into()),
-        }
+            ) => Self::TransactionsSigningPublicKey(cli_transactions_signing_action.try_into()?),
+        })
     }
 }
 
 impl TransactionsSigning {
-    pub fn choose_sign_transactions() -> Self {
-        Self::from(CliTransactionsSigning::TransactionsSigningPublicKey(
+    pub fn choose_sign_transactions() -> color_eyre::eyre::Result<Self> {
+        Self::try_from(CliTransactionsSigning::TransactionsSigningPublicKey(
             Default::default(),
         ))
     }
 
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (command, mut args) = match self {
+            Self::TransactionsSigningPublicKey(transactions_signing_action) => (
+                "transactions-signing-public-key",
+                transactions_signing_action.to_cli_args(),
+            ),
+        };
+        args.insert(0, command.to_owned());
+        args
+    }
+
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
         stake: u128,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             TransactionsSigning::TransactionsSigningPublicKey(transactions_sign_action) => {
@@ -41,6 +56,7 @@ impl TransactionsSigning {
                         prepopulated_unsigned_transaction,
                         network_connection_config,
                         stake,
+                        output_format,
                     )
                     .await
             }
@@ -65,30 +81,41 @@ pub struct TransactionsSigningAction {
         crate::commands::construct_transaction_command::sign_transaction::SignTransaction,
 }
 
-impl From<CliTransactionsSigningAction> for TransactionsSigningAction {
-    fn from(item: CliTransactionsSigningAction) -> Self {
+impl TryFrom<CliTransactionsSigningAction> for TransactionsSigningAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliTransactionsSigningAction) -> color_eyre::eyre::Result<Self> {
         let transactions_signing_public_key: near_crypto::PublicKey =
             match item.transactions_signing_public_key {
                 Some(cli_transactions_signing_public_key) => cli_transactions_signing_public_key,
-                None => TransactionsSigningAction::input_public_key(),
+                None => TransactionsSigningAction::input_public_key()?,
             };
         let sign_option = match item.sign_option {
-            Some(cli_sign_transaction) => cli_sign_transaction.into(),
-            None => crate::commands::construct_transaction_command::sign_transaction::SignTransaction::choose_sign_option(),
+            Some(cli_sign_transaction) => cli_sign_transaction.try_into()?,
+            None => crate::commands::construct_transaction_command::sign_transaction::SignTransaction::choose_sign_option()?,
         };
-        Self {
+        Ok(Self {
             transactions_signing_public_key,
             sign_option,
-        }
+        })
     }
 }
 
 impl TransactionsSigningAction {
-    pub fn input_public_key() -> near_crypto::PublicKey {
-        Input::new()
+    pub fn input_public_key() -> color_eyre::eyre::Result<near_crypto::PublicKey> {
+        crate::common::ensure_interactive(
+            "`<TRANSACTIONS_SIGNING_PUBLIC_KEY>` of `transactions-signing-public-key`",
+        )?;
+        Ok(Input::new()
             .with_prompt("Enter a public key for this server")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![self.transactions_signing_public_key.to_string()];
+        args.extend(self.sign_option.to_cli_args());
+        args
     }
 
     pub async fn process(
@@ -96,6 +123,7 @@ impl TransactionsSigningAction {
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
         stake: u128,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let action = near_primitives::transaction::Action::Stake(
             near_primitives::transaction::StakeAction {
@@ -110,7 +138,11 @@ impl TransactionsSigningAction {
             ..prepopulated_unsigned_transaction
         };
         self.sign_option
-            .process(unsigned_transaction, network_connection_config)
+            .process(
+                unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::{TryFrom, TryInto};
 
 #[derive(Debug, clap::Clap)]
 pub enum CliDeposit {
@@ -11,30 +12,45 @@ pub enum Deposit {
     Deposit(TransferNEARTokensAction),
 }
 
-impl From<CliDeposit> for Deposit {
-    fn from(item: CliDeposit) -> Self {
-        match item {
+impl TryFrom<CliDeposit> for Deposit {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliDeposit) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliDeposit::Deposit(cli_transfer_near_action) => {
-                Self::Deposit(cli_transfer_near_action.into())
+                Self::Deposit(cli_transfer_near_action.try_into()?)
             }
-        }
+        })
     }
 }
 
 impl Deposit {
-    pub fn choose_deposit() -> Self {
-        Self::from(CliDeposit::Deposit(Default::default()))
+    pub fn choose_deposit() -> color_eyre::eyre::Result<Self> {
+        Self::try_from(CliDeposit::Deposit(Default::default()))
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (command, mut args) = match self {
+            Self::Deposit(transfer_near_action) => ("deposit", transfer_near_action.to_cli_args()),
+        };
+        args.insert(0, command.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             Deposit::Deposit(transfer_near_action) => {
                 transfer_near_action
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
@@ -58,35 +74,45 @@ pub struct TransferNEARTokensAction {
         crate::commands::construct_transaction_command::sign_transaction::SignTransaction,
 }
 
-impl From<CliTransferNEARTokensAction> for TransferNEARTokensAction {
-    fn from(item: CliTransferNEARTokensAction) -> Self {
+impl TryFrom<CliTransferNEARTokensAction> for TransferNEARTokensAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliTransferNEARTokensAction) -> color_eyre::eyre::Result<Self> {
         let amount: crate::common::NearBalance = match item.amount {
             Some(cli_amount) => cli_amount,
-            None => TransferNEARTokensAction::input_amount(),
+            None => TransferNEARTokensAction::input_amount()?,
         };
         let sign_option = match item.sign_option {
-            Some(cli_sign_transaction) => cli_sign_transaction.into(),
-            None => crate::commands::construct_transaction_command::sign_transaction::SignTransaction::choose_sign_option(),
+            Some(cli_sign_transaction) => cli_sign_transaction.try_into()?,
+            None => crate::commands::construct_transaction_command::sign_transaction::SignTransaction::choose_sign_option()?,
         };
-        Self {
+        Ok(Self {
             amount,
             sign_option,
-        }
+        })
     }
 }
 
 impl TransferNEARTokensAction {
-    pub fn input_amount() -> crate::common::NearBalance {
-        Input::new()
+    pub fn input_amount() -> color_eyre::eyre::Result<crate::common::NearBalance> {
+        crate::common::ensure_interactive("`<AMOUNT>` of `deposit`")?;
+        Ok(Input::new()
             .with_prompt("How many NEAR Tokens do you want to deposit? (example: 10NEAR or 0.5near or 10000yoctonear)")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![self.amount.to_cli_arg()];
+        args.extend(self.sign_option.to_cli_args());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let action = near_primitives::transaction::Action::Transfer(
             near_primitives::transaction::TransferAction {
@@ -100,7 +126,11 @@ impl TransferNEARTokensAction {
             ..prepopulated_unsigned_transaction
         };
         self.sign_option
-            .process(unsigned_transaction, network_connection_config)
+            .process(
+                unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::{TryFrom, TryInto};
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 mod public_key_mode;
@@ -16,20 +17,23 @@ pub enum FullAccessKey {
     SubAccountFullAccess(SubAccountFullAccess),
 }
 
-impl From<CliFullAccessKey> for FullAccessKey {
-    fn from(item: CliFullAccessKey) -> Self {
-        match item {
+impl TryFrom<CliFullAccessKey> for FullAccessKey {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliFullAccessKey) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliFullAccessKey::SubAccountFullAccess(cli_sub_account_full_access) => {
-                FullAccessKey::SubAccountFullAccess(cli_sub_account_full_access.into())
+                FullAccessKey::SubAccountFullAccess(cli_sub_account_full_access.try_into()?)
             }
-        }
+        })
     }
 }
 
 impl FullAccessKey {
-    pub fn choose_full_access_key() -> Self {
+    pub fn choose_full_access_key() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = FullAccessKeyDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let actions = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -45,18 +49,34 @@ impl FullAccessKey {
                 CliFullAccessKey::SubAccountFullAccess(Default::default())
             }
         };
-        Self::from(cli_action)
+        Self::try_from(cli_action)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (command, mut args) = match self {
+            Self::SubAccountFullAccess(sub_account_full_access) => (
+                "sub-account-full-access",
+                sub_account_full_access.to_cli_args(),
+            ),
+        };
+        args.insert(0, command.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             FullAccessKey::SubAccountFullAccess(sub_account_full_access) => {
                 sub_account_full_access
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
@@ -75,26 +95,37 @@ pub struct SubAccountFullAccess {
     pub public_key_mode: self::public_key_mode::PublicKeyMode,
 }
 
-impl From<CliSubAccountFullAccess> for SubAccountFullAccess {
-    fn from(item: CliSubAccountFullAccess) -> Self {
+impl TryFrom<CliSubAccountFullAccess> for SubAccountFullAccess {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSubAccountFullAccess) -> color_eyre::eyre::Result<Self> {
         let public_key_mode = match item.public_key_mode {
             Some(cli_public_key_mode) => {
-                self::public_key_mode::PublicKeyMode::from(cli_public_key_mode)
+                self::public_key_mode::PublicKeyMode::try_from(cli_public_key_mode)?
             }
-            None => self::public_key_mode::PublicKeyMode::choose_public_key_mode(),
+            None => self::public_key_mode::PublicKeyMode::choose_public_key_mode()?,
         };
-        Self { public_key_mode }
+        Ok(Self { public_key_mode })
     }
 }
 
 impl SubAccountFullAccess {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.public_key_mode.to_cli_args()
+    }
+
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         self.public_key_mode
-            .process(prepopulated_unsigned_transaction, network_connection_config)
+            .process(
+                prepopulated_unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 /// Add full access key to the sub-account
 #[derive(Debug, Default, clap::Clap)]
@@ -16,36 +17,46 @@ pub struct AddAccessKeyAction {
     pub deposit: super::super::super::deposit::Deposit,
 }
 
-impl From<CliAddAccessKeyAction> for AddAccessKeyAction {
-    fn from(item: CliAddAccessKeyAction) -> Self {
+impl TryFrom<CliAddAccessKeyAction> for AddAccessKeyAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliAddAccessKeyAction) -> color_eyre::eyre::Result<Self> {
         let public_key: near_crypto::PublicKey = match item.public_key {
             Some(cli_public_key) => cli_public_key,
-            None => AddAccessKeyAction::input_public_key(),
+            None => AddAccessKeyAction::input_public_key()?,
         };
         let deposit = match item.deposit {
-            Some(cli_deposit) => super::super::super::deposit::Deposit::from(cli_deposit),
-            None => super::super::super::deposit::Deposit::choose_deposit(),
+            Some(cli_deposit) => super::super::super::deposit::Deposit::try_from(cli_deposit)?,
+            None => super::super::super::deposit::Deposit::choose_deposit()?,
         };
-        Self {
+        Ok(Self {
             public_key,
             nonce: 0,
             deposit,
-        }
+        })
     }
 }
 
 impl AddAccessKeyAction {
-    pub fn input_public_key() -> near_crypto::PublicKey {
-        Input::new()
+    pub fn input_public_key() -> color_eyre::eyre::Result<near_crypto::PublicKey> {
+        crate::common::ensure_interactive("`<PUBLIC_KEY>` of `public-key`")?;
+        Ok(Input::new()
             .with_prompt("Enter a public key for this access key")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![self.public_key.to_string()];
+        args.extend(self.deposit.to_cli_args());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let access_key: near_primitives::account::AccessKey = near_primitives::account::AccessKey {
             nonce: self.nonce.clone(),
@@ -64,7 +75,11 @@ impl AddAccessKeyAction {
             ..prepopulated_unsigned_transaction
         };
         self.deposit
-            .process(unsigned_transaction, network_connection_config)
+            .process(
+                unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
@@ -1,3 +1,4 @@
+use std::convert::TryFrom;
 use std::io::Write;
 use std::str::FromStr;
 
@@ -33,21 +34,28 @@ pub struct GenerateKeypair {
     pub deposit: super::super::super::deposit::Deposit,
 }
 
-impl From<CliGenerateKeypair> for GenerateKeypair {
-    fn from(item: CliGenerateKeypair) -> Self {
+impl TryFrom<CliGenerateKeypair> for GenerateKeypair {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliGenerateKeypair) -> color_eyre::eyre::Result<Self> {
         let deposit = match item.deposit {
-            Some(cli_deposit) => super::super::super::deposit::Deposit::from(cli_deposit),
-            None => super::super::super::deposit::Deposit::choose_deposit(),
+            Some(cli_deposit) => super::super::super::deposit::Deposit::try_from(cli_deposit)?,
+            None => super::super::super::deposit::Deposit::choose_deposit()?,
         };
-        Self { deposit }
+        Ok(Self { deposit })
     }
 }
 
 impl GenerateKeypair {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.deposit.to_cli_args()
+    }
+
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let new_master_seed_phrase_words_count: usize = 12;
         let seed_phrase_hd_path = slip10::BIP32Path::from_str("m/44'/397'/0'").unwrap();
@@ -141,7 +149,11 @@ impl GenerateKeypair {
             ..prepopulated_unsigned_transaction
         };
         self.deposit
-            .process(unsigned_transaction, network_connection_config)
+            .process(
+                unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 #[derive(Debug, clap::Clap)]
 pub enum CliSendTo {
@@ -11,31 +12,46 @@ pub enum SendTo {
     SubAccount(SubAccount),
 }
 
-impl From<CliSendTo> for SendTo {
-    fn from(item: CliSendTo) -> Self {
-        match item {
+impl TryFrom<CliSendTo> for SendTo {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSendTo) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliSendTo::SubAccount(cli_receiver) => {
-                let receiver = SubAccount::from(cli_receiver);
+                let receiver = SubAccount::try_from(cli_receiver)?;
                 Self::SubAccount(receiver)
             }
-        }
+        })
     }
 }
 
 impl SendTo {
-    pub fn send_to() -> Self {
-        Self::from(CliSendTo::SubAccount(Default::default()))
+    pub fn send_to() -> color_eyre::eyre::Result<Self> {
+        Self::try_from(CliSendTo::SubAccount(Default::default()))
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (command, mut args) = match self {
+            Self::SubAccount(sub_account) => ("sub-account", sub_account.to_cli_args()),
+        };
+        args.insert(0, command.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             SendTo::SubAccount(receiver) => {
                 receiver
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
@@ -56,37 +72,47 @@ pub struct SubAccount {
     pub full_access_key: super::full_access_key::FullAccessKey,
 }
 
-impl From<CliSubAccount> for SubAccount {
-    fn from(item: CliSubAccount) -> Self {
+impl TryFrom<CliSubAccount> for SubAccount {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSubAccount) -> color_eyre::eyre::Result<Self> {
         let sub_account_id: String = match item.sub_account_id {
             Some(cli_sub_account_id) => cli_sub_account_id,
-            None => SubAccount::input_sub_account_id(),
+            None => SubAccount::input_sub_account_id()?,
         };
         let full_access_key = match item.full_access_key {
             Some(cli_full_access_key) => {
-                super::full_access_key::FullAccessKey::from(cli_full_access_key)
+                super::full_access_key::FullAccessKey::try_from(cli_full_access_key)?
             }
-            None => super::full_access_key::FullAccessKey::choose_full_access_key(),
+            None => super::full_access_key::FullAccessKey::choose_full_access_key()?,
         };
-        Self {
+        Ok(Self {
             sub_account_id,
             full_access_key,
-        }
+        })
     }
 }
 
 impl SubAccount {
-    pub fn input_sub_account_id() -> String {
-        Input::new()
+    pub fn input_sub_account_id() -> color_eyre::eyre::Result<String> {
+        crate::common::ensure_interactive("`<SUB_ACCOUNT_ID>` of `sub-account`")?;
+        Ok(Input::new()
             .with_prompt("What is the sub-account ID?")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![self.sub_account_id.clone()];
+        args.extend(self.full_access_key.to_cli_args());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let action = near_primitives::transaction::Action::CreateAccount(
             near_primitives::transaction::CreateAccountAction {},
@@ -99,7 +125,11 @@ impl SubAccount {
             ..prepopulated_unsigned_transaction
         };
         self.full_access_key
-            .process(unsigned_transaction, network_connection_config)
+            .process(
+                unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
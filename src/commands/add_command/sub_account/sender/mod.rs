@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 /// данные об отправителе транзакции
 #[derive(Debug, Default, clap::Clap)]
@@ -14,43 +15,57 @@ pub struct Sender {
     pub send_to: super::receiver::SendTo,
 }
 
-impl From<CliSender> for Sender {
-    fn from(item: CliSender) -> Self {
+impl TryFrom<CliSender> for Sender {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSender) -> color_eyre::eyre::Result<Self> {
         let owner_account_id: String = match item.owner_account_id {
             Some(cli_owner_account_id) => cli_owner_account_id,
-            None => Sender::input_owner_account_id(),
+            None => Sender::input_owner_account_id()?,
         };
         let send_to: super::receiver::SendTo = match item.send_to {
-            Some(cli_send_to) => super::receiver::SendTo::from(cli_send_to),
-            None => super::receiver::SendTo::send_to(),
+            Some(cli_send_to) => super::receiver::SendTo::try_from(cli_send_to)?,
+            None => super::receiver::SendTo::send_to()?,
         };
-        Self {
+        Ok(Self {
             owner_account_id,
             send_to,
-        }
+        })
     }
 }
 
 impl Sender {
-    pub fn input_owner_account_id() -> String {
+    pub fn input_owner_account_id() -> color_eyre::eyre::Result<String> {
+        crate::common::ensure_interactive("`<OWNER_ACCOUNT_ID>` of `owner-account`")?;
         println!();
-        Input::new()
+        Ok(Input::new()
             .with_prompt("What is the owner account ID?")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![self.owner_account_id.clone()];
+        args.extend(self.send_to.to_cli_args());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let unsigned_transaction = near_primitives::transaction::Transaction {
             signer_id: self.owner_account_id.clone(),
             ..prepopulated_unsigned_transaction
         };
         self.send_to
-            .process(unsigned_transaction, network_connection_config)
+            .process(
+                unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
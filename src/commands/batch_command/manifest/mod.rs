@@ -0,0 +1,449 @@
+/// One transaction of a batch manifest.
+/// In a CSV manifest every row is a transaction with a single action: the columns other than
+/// `signer_id`, `receiver_id` and `signer_secret_key` are the fields of the action.
+#[derive(Debug, serde::Deserialize)]
+pub struct ManifestTransaction {
+    pub signer_id: near_primitives::types::AccountId,
+    pub receiver_id: near_primitives::types::AccountId,
+    /// Signs with this key instead of looking for the key of the signer in the keychain
+    #[serde(default)]
+    pub signer_secret_key: Option<near_crypto::SecretKey>,
+    pub actions: Vec<ManifestAction>,
+}
+
+/// The actions of `construct-transaction`, tagged with the name of their subcommand
+#[derive(Debug, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum ManifestAction {
+    TransferNearTokens {
+        #[serde(deserialize_with = "from_str")]
+        amount: crate::common::NearBalance,
+    },
+    CallFunction {
+        method_name: String,
+        /// A JSON string is passed as is, any other JSON value is serialized
+        #[serde(default)]
+        args: serde_json::Value,
+        #[serde(default = "default_gas", deserialize_with = "from_str")]
+        gas: crate::common::NearGas,
+        #[serde(default, deserialize_with = "from_str")]
+        deposit: crate::common::NearBalance,
+    },
+    StakeNearTokens {
+        #[serde(deserialize_with = "from_str")]
+        amount: crate::common::NearBalance,
+        public_key: near_crypto::PublicKey,
+    },
+    CreateAccount,
+    DeleteAccount {
+        beneficiary_id: near_primitives::types::AccountId,
+    },
+    /// A full access key, or a function-call key when `receiver_id` is given
+    AddAccessKey {
+        public_key: near_crypto::PublicKey,
+        receiver_id: Option<near_primitives::types::AccountId>,
+        /// Comma-separated, like `--method-names`
+        #[serde(default)]
+        method_names: Option<String>,
+        #[serde(default, deserialize_with = "option_from_str")]
+        allowance: Option<crate::common::NearBalance>,
+    },
+    DeleteAccessKey {
+        public_key: near_crypto::PublicKey,
+    },
+}
+
+fn default_gas() -> crate::common::NearGas {
+    crate::common::NearGas {
+        inner: 100 * 10u64.pow(12),
+    }
+}
+
+fn from_str<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    let value = <String as serde::Deserialize>::deserialize(deserializer)?;
+    value.parse().map_err(serde::de::Error::custom)
+}
+
+fn option_from_str<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    match <Option<String> as serde::Deserialize>::deserialize(deserializer)? {
+        Some(value) => value.parse().map(Some).map_err(serde::de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+impl ManifestAction {
+    pub fn into_action(self) -> Result<near_primitives::transaction::Action, String> {
+        Ok(match self {
+            Self::TransferNearTokens { amount } => near_primitives::transaction::Action::Transfer(
+                near_primitives::transaction::TransferAction {
+                    deposit: amount.to_yoctonear(),
+                },
+            ),
+            Self::CallFunction {
+                method_name,
+                args,
+                gas,
+                deposit,
+            } => {
+                let args = match args {
+                    serde_json::Value::Null => vec![],
+                    serde_json::Value::String(args) => args.into_bytes(),
+                    args => args.to_string().into_bytes(),
+                };
+                near_primitives::transaction::Action::FunctionCall(
+                    near_primitives::transaction::FunctionCallAction {
+                        method_name,
+                        args,
+                        gas: gas.inner,
+                        deposit: deposit.to_yoctonear(),
+                    },
+                )
+            }
+            Self::StakeNearTokens { amount, public_key } => {
+                near_primitives::transaction::Action::Stake(
+                    near_primitives::transaction::StakeAction {
+                        stake: amount.to_yoctonear(),
+                        public_key,
+                    },
+                )
+            }
+            Self::CreateAccount => near_primitives::transaction::Action::CreateAccount(
+                near_primitives::transaction::CreateAccountAction {},
+            ),
+            Self::DeleteAccount { beneficiary_id } => {
+                near_primitives::transaction::Action::DeleteAccount(
+                    near_primitives::transaction::DeleteAccountAction { beneficiary_id },
+                )
+            }
+            Self::AddAccessKey {
+                public_key,
+                receiver_id,
+                method_names,
+                allowance,
+            } => {
+                let permission = match receiver_id {
+                    Some(receiver_id) => {
+                        near_primitives::account::AccessKeyPermission::FunctionCall(
+                            near_primitives::account::FunctionCallPermission {
+                                allowance: allowance.map(|allowance| allowance.to_yoctonear()),
+                                receiver_id,
+                                method_names: method_names
+                                    .unwrap_or_default()
+                                    .split(',')
+                                    .map(str::trim)
+                                    .filter(|method_name| !method_name.is_empty())
+                                    .map(str::to_string)
+                                    .collect(),
+                            },
+                        )
+                    }
+                    None if method_names.is_some() || allowance.is_some() => return Err(
+                        "`method_names` and `allowance` of `add-access-key` require `receiver_id`"
+                            .to_string(),
+                    ),
+                    None => near_primitives::account::AccessKeyPermission::FullAccess,
+                };
+                near_primitives::transaction::Action::AddKey(
+                    near_primitives::transaction::AddKeyAction {
+                        public_key,
+                        access_key: near_primitives::account::AccessKey {
+                            nonce: 0,
+                            permission,
+                        },
+                    },
+                )
+            }
+            Self::DeleteAccessKey { public_key } => {
+                near_primitives::transaction::Action::DeleteKey(
+                    near_primitives::transaction::DeleteKeyAction { public_key },
+                )
+            }
+        })
+    }
+}
+
+/// Reads a `.json`, `.yaml`/`.yml` or `.csv` manifest
+pub fn load(path: &std::path::Path) -> color_eyre::eyre::Result<Vec<ManifestTransaction>> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    let parse_error = |err: String| {
+        color_eyre::Report::msg(format!("Failed to parse {}: {}", path.display(), err))
+    };
+    match extension.as_str() {
+        "json" => serde_json::from_str(&read(path)?).map_err(|err| parse_error(err.to_string())),
+        "yaml" | "yml" => {
+            serde_yaml::from_str(&read(path)?).map_err(|err| parse_error(err.to_string()))
+        }
+        "csv" => load_csv(path),
+        _ => Err(color_eyre::Report::msg(format!(
+            "Unknown format of the manifest {}: expected a .json, .yaml, .yml or .csv file",
+            path.display()
+        ))),
+    }
+}
+
+fn read(path: &std::path::Path) -> color_eyre::eyre::Result<String> {
+    std::fs::read_to_string(path).map_err(|err| {
+        color_eyre::Report::msg(format!("Failed to read {}: {:?}", path.display(), err))
+    })
+}
+
+fn load_csv(path: &std::path::Path) -> color_eyre::eyre::Result<Vec<ManifestTransaction>> {
+    let csv_error = |err: csv::Error| {
+        color_eyre::Report::msg(format!("Failed to read {}: {}", path.display(), err))
+    };
+    let mut reader = csv::Reader::from_path(path).map_err(csv_error)?;
+    let headers = reader.headers().map_err(csv_error)?.clone();
+    let mut transactions = vec![];
+    for (index, record) in reader.records().enumerate() {
+        let record = record.map_err(csv_error)?;
+        let mut transaction = serde_json::Map::new();
+        let mut action = serde_json::Map::new();
+        for (header, value) in headers.iter().zip(record.iter()) {
+            if value.is_empty() {
+                continue;
+            }
+            let value = serde_json::Value::String(value.to_string());
+            match header {
+                "signer_id" | "receiver_id" | "signer_secret_key" => {
+                    transaction.insert(header.to_string(), value)
+                }
+                _ => action.insert(header.to_string(), value),
+            };
+        }
+        transaction.insert(
+            "actions".to_string(),
+            serde_json::Value::Array(vec![serde_json::Value::Object(action)]),
+        );
+        transactions.push(
+            serde_json::from_value(serde_json::Value::Object(transaction)).map_err(|err| {
+                color_eyre::Report::msg(format!(
+                    "Failed to parse the row {} of {}: {}",
+                    index + 1,
+                    path.display(),
+                    err
+                ))
+            })?,
+        );
+    }
+    Ok(transactions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load, ManifestAction, ManifestTransaction};
+
+    fn load_manifest(extension: &str, contents: &str) -> Vec<ManifestTransaction> {
+        let path = std::env::temp_dir().join(format!(
+            "near-cli-manifest-{}.{}",
+            std::process::id(),
+            extension
+        ));
+        std::fs::write(&path, contents).unwrap();
+        let transactions = load(&path);
+        std::fs::remove_file(&path).unwrap();
+        transactions.unwrap()
+    }
+
+    fn into_actions(transaction: ManifestTransaction) -> Vec<near_primitives::transaction::Action> {
+        transaction
+            .actions
+            .into_iter()
+            .map(|action| action.into_action().unwrap())
+            .collect()
+    }
+
+    fn transfer(yoctonear: u128) -> near_primitives::transaction::Action {
+        near_primitives::transaction::Action::Transfer(
+            near_primitives::transaction::TransferAction { deposit: yoctonear },
+        )
+    }
+
+    fn function_call_args(args: serde_json::Value) -> Vec<u8> {
+        let action: ManifestAction = serde_json::from_value(serde_json::json!({
+            "type": "call-function",
+            "method_name": "deposit_and_stake",
+            "args": args,
+        }))
+        .unwrap();
+        match action.into_action().unwrap() {
+            near_primitives::transaction::Action::FunctionCall(function_call) => function_call.args,
+            action => panic!("Unexpected action {:?}", action),
+        }
+    }
+
+    #[test]
+    fn load_json_manifest() {
+        let secret_key = near_crypto::SecretKey::from_seed(near_crypto::KeyType::ED25519, "batch");
+        let transactions = load_manifest(
+            "json",
+            &serde_json::json!([
+                {
+                    "signer_id": "volodymyr.testnet",
+                    "receiver_id": "21.testnet",
+                    "signer_secret_key": secret_key.to_string(),
+                    "actions": [{ "type": "transfer-near-tokens", "amount": "1 NEAR" }]
+                },
+                {
+                    "signer_id": "volodymyr.testnet",
+                    "receiver_id": "meta.pool.testnet",
+                    "actions": [{
+                        "type": "call-function",
+                        "method_name": "deposit_and_stake",
+                        "args": {},
+                        "gas": "30 TeraGas",
+                        "deposit": "2 NEAR"
+                    }]
+                }
+            ])
+            .to_string(),
+        );
+        assert_eq!(transactions.len(), 2);
+        let mut transactions = transactions.into_iter();
+
+        let transaction = transactions.next().unwrap();
+        assert_eq!(transaction.signer_id, "volodymyr.testnet");
+        assert_eq!(transaction.receiver_id, "21.testnet");
+        assert_eq!(transaction.signer_secret_key, Some(secret_key));
+        assert_eq!(into_actions(transaction), vec![transfer(10u128.pow(24))]);
+
+        let transaction = transactions.next().unwrap();
+        assert_eq!(transaction.signer_secret_key, None);
+        assert_eq!(
+            into_actions(transaction),
+            vec![near_primitives::transaction::Action::FunctionCall(
+                near_primitives::transaction::FunctionCallAction {
+                    method_name: "deposit_and_stake".to_string(),
+                    args: b"{}".to_vec(),
+                    gas: 30 * 10u64.pow(12),
+                    deposit: 2 * 10u128.pow(24),
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn load_yaml_manifest() {
+        let public_key =
+            near_crypto::SecretKey::from_seed(near_crypto::KeyType::ED25519, "batch").public_key();
+        let transactions = load_manifest(
+            "yaml",
+            &format!(
+                r#"
+- signer_id: volodymyr.testnet
+  receiver_id: volodymyr.testnet
+  actions:
+    - type: add-access-key
+      public_key: {}
+      receiver_id: meta.pool.testnet
+      method_names: "deposit_and_stake, unstake"
+      allowance: 0.25 NEAR
+    - type: delete-access-key
+      public_key: {}
+"#,
+                public_key, public_key
+            ),
+        );
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(
+            into_actions(transactions.into_iter().next().unwrap()),
+            vec![
+                near_primitives::transaction::Action::AddKey(
+                    near_primitives::transaction::AddKeyAction {
+                        public_key: public_key.clone(),
+                        access_key: near_primitives::account::AccessKey {
+                            nonce: 0,
+                            permission: near_primitives::account::AccessKeyPermission::FunctionCall(
+                                near_primitives::account::FunctionCallPermission {
+                                    allowance: Some(25 * 10u128.pow(22)),
+                                    receiver_id: "meta.pool.testnet".to_string(),
+                                    method_names: vec![
+                                        "deposit_and_stake".to_string(),
+                                        "unstake".to_string()
+                                    ],
+                                }
+                            ),
+                        },
+                    }
+                ),
+                near_primitives::transaction::Action::DeleteKey(
+                    near_primitives::transaction::DeleteKeyAction { public_key }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn load_csv_manifest() {
+        let transactions = load_manifest(
+            "csv",
+            "signer_id,receiver_id,type,amount,method_name,args\n\
+             volodymyr.testnet,21.testnet,transfer-near-tokens,0.5 NEAR,,\n\
+             volodymyr.testnet,counter.testnet,call-function,,increment,\"{\"\"by\"\": 2}\"\n",
+        );
+        assert_eq!(transactions.len(), 2);
+        let mut transactions = transactions.into_iter();
+
+        let transaction = transactions.next().unwrap();
+        assert_eq!(transaction.receiver_id, "21.testnet");
+        assert_eq!(
+            into_actions(transaction),
+            vec![transfer(5 * 10u128.pow(23))]
+        );
+
+        // A CSV cell is a string, so the args are passed as they are written
+        let transaction = transactions.next().unwrap();
+        assert_eq!(transaction.receiver_id, "counter.testnet");
+        assert_eq!(
+            into_actions(transaction),
+            vec![near_primitives::transaction::Action::FunctionCall(
+                near_primitives::transaction::FunctionCallAction {
+                    method_name: "increment".to_string(),
+                    args: br#"{"by": 2}"#.to_vec(),
+                    gas: 100 * 10u64.pow(12),
+                    deposit: 0,
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn call_function_args_string_or_value() {
+        assert_eq!(
+            function_call_args(serde_json::json!(r#"{"by": 2}"#)),
+            br#"{"by": 2}"#.to_vec()
+        );
+        assert_eq!(
+            function_call_args(serde_json::json!({ "by": 2 })),
+            br#"{"by":2}"#.to_vec()
+        );
+        assert_eq!(
+            function_call_args(serde_json::Value::Null),
+            Vec::<u8>::new()
+        );
+    }
+
+    #[test]
+    fn add_access_key_method_names_require_receiver_id() {
+        let action: ManifestAction = serde_json::from_value(serde_json::json!({
+            "type": "add-access-key",
+            "public_key": near_crypto::PublicKey::empty(near_crypto::KeyType::ED25519).to_string(),
+            "method_names": "unstake",
+        }))
+        .unwrap();
+        assert!(action.into_action().is_err());
+    }
+}
@@ -0,0 +1,506 @@
+use dialoguer::{theme::ColorfulTheme, Input, Select};
+use futures::StreamExt;
+use std::convert::TryFrom;
+
+use crate::commands::construct_transaction_command::sign_transaction::{
+    confirmation::confirm_batch, sign_with_keychain::SignKeychain,
+    sign_with_private_key::broadcast_tx_commit,
+};
+
+mod manifest;
+
+/// Sign and submit the transactions listed in a manifest file
+#[derive(Debug, clap::Clap)]
+pub struct CliBatch {
+    /// The manifest file (.json, .yaml, .yml or .csv)
+    manifest: Option<std::path::PathBuf>,
+    /// The network (testnet, mainnet, betanet, localnet or a network of the configuration file)
+    #[clap(long)]
+    network: Option<crate::common::ConnectionConfig>,
+    /// Where the keys are kept: file (default) or secret-service
+    #[clap(long)]
+    key_store: Option<crate::key_store::KeyStoreKind>,
+    /// How many access keys submit their transactions at the same time
+    #[clap(long, default_value = "4")]
+    concurrency: usize,
+    /// Write the results to this file (.csv or .json) instead of printing them
+    #[clap(long)]
+    report: Option<std::path::PathBuf>,
+    #[clap(flatten)]
+    retry_policy: crate::common::RetryPolicy,
+}
+
+impl Default for CliBatch {
+    fn default() -> Self {
+        Self {
+            manifest: None,
+            network: None,
+            key_store: None,
+            concurrency: 4,
+            report: None,
+            retry_policy: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Batch {
+    pub manifest: std::path::PathBuf,
+    pub network_connection_config: crate::common::ConnectionConfig,
+    pub key_store: crate::key_store::KeyStoreKind,
+    pub concurrency: usize,
+    pub report: Option<std::path::PathBuf>,
+    pub retry_policy: crate::common::RetryPolicy,
+}
+
+impl TryFrom<CliBatch> for Batch {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliBatch) -> color_eyre::eyre::Result<Self> {
+        let manifest = match item.manifest {
+            Some(manifest) => manifest,
+            None => Batch::input_manifest()?,
+        };
+        let network_connection_config = match item.network {
+            Some(network_connection_config) => network_connection_config,
+            None => Batch::choose_network()?,
+        };
+        Ok(Self {
+            manifest,
+            network_connection_config,
+            key_store: item.key_store.unwrap_or_default(),
+            concurrency: std::cmp::max(item.concurrency, 1),
+            report: item.report,
+            retry_policy: item.retry_policy,
+        })
+    }
+}
+
+/// A manifest transaction with its actions and the key pair to sign it with
+struct BatchTransaction {
+    row: usize,
+    signer_id: near_primitives::types::AccountId,
+    receiver_id: near_primitives::types::AccountId,
+    actions: Vec<near_primitives::transaction::Action>,
+    public_key: near_crypto::PublicKey,
+    secret_key: near_crypto::SecretKey,
+}
+
+/// The result of one manifest transaction; `row` is 1-based
+#[derive(Debug, serde::Serialize)]
+struct ReportRow {
+    row: usize,
+    signer_id: near_primitives::types::AccountId,
+    receiver_id: near_primitives::types::AccountId,
+    transaction_hash: Option<String>,
+    status: &'static str,
+    error: Option<String>,
+}
+
+impl ReportRow {
+    fn new(
+        transaction: &BatchTransaction,
+        transaction_hash: Option<String>,
+        status: &'static str,
+        error: Option<String>,
+    ) -> Self {
+        Self {
+            row: transaction.row,
+            signer_id: transaction.signer_id.clone(),
+            receiver_id: transaction.receiver_id.clone(),
+            transaction_hash,
+            status,
+            error,
+        }
+    }
+}
+
+impl Batch {
+    fn input_manifest() -> color_eyre::eyre::Result<std::path::PathBuf> {
+        crate::common::ensure_interactive("`<MANIFEST>` of `batch`")?;
+        println!();
+        let manifest: String = Input::new()
+            .with_prompt("What is the path to the manifest file?")
+            .interact_text()
+            .unwrap();
+        Ok(manifest.into())
+    }
+
+    fn choose_network() -> color_eyre::eyre::Result<crate::common::ConnectionConfig> {
+        if let Some(connection_config) = crate::config::default_network() {
+            return Ok(connection_config);
+        }
+        crate::common::ensure_interactive("`--network <NETWORK>` of `batch`")?;
+        println!();
+        let config = crate::config::Config::load().unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            crate::config::Config::default()
+        });
+        let networks = ["testnet", "mainnet", "betanet", "localnet"]
+            .iter()
+            .map(|name| name.to_string())
+            .chain(
+                config
+                    .networks
+                    .keys()
+                    .filter(|name| {
+                        !["testnet", "mainnet", "betanet", "localnet"].contains(&name.as_str())
+                    })
+                    .cloned(),
+            )
+            .collect::<Vec<_>>();
+        let selected_network = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Select the network")
+            .items(&networks)
+            .default(0)
+            .interact()
+            .unwrap();
+        Ok(config
+            .connection_config(&networks[selected_network])
+            .expect("The listed networks are known"))
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let default = CliBatch::default();
+        let mut args = vec![self.manifest.display().to_string()];
+        args.extend(self.network_connection_config.to_cli_args());
+        if self.key_store != crate::key_store::KeyStoreKind::default() {
+            args.push("--key-store".to_owned());
+            args.push(<&str>::from(self.key_store).to_owned());
+        }
+        if self.concurrency != default.concurrency {
+            args.push("--concurrency".to_owned());
+            args.push(self.concurrency.to_string());
+        }
+        if let Some(report) = &self.report {
+            args.push("--report".to_owned());
+            args.push(report.display().to_string());
+        }
+        args.extend(self.retry_policy.to_cli_args());
+        args
+    }
+
+    /// Validates every row and finds the keys of the signers before anything is sent
+    async fn prepare_transactions(&self) -> color_eyre::eyre::Result<Vec<BatchTransaction>> {
+        let manifest_transactions = self::manifest::load(&self.manifest)?;
+        if manifest_transactions.is_empty() {
+            return Err(color_eyre::Report::msg(format!(
+                "There are no transactions in {}",
+                self.manifest.display()
+            )));
+        }
+        let key_store = self.key_store.open()?;
+        let network_connection_config = Some(self.network_connection_config.clone());
+        // The keys are unlocked once per signer and key, not once per row
+        let mut keychain_secret_keys: std::collections::HashMap<
+            (String, near_crypto::PublicKey),
+            near_crypto::SecretKey,
+        > = std::collections::HashMap::new();
+        let mut transactions = vec![];
+        for (index, manifest_transaction) in manifest_transactions.into_iter().enumerate() {
+            let row = index + 1;
+            if manifest_transaction.actions.is_empty() {
+                return Err(color_eyre::Report::msg(format!(
+                    "The row {} of {} has no actions",
+                    row,
+                    self.manifest.display()
+                )));
+            }
+            let actions = manifest_transaction
+                .actions
+                .into_iter()
+                .map(|action| action.into_action())
+                .collect::<Result<Vec<_>, String>>()
+                .map_err(|err| {
+                    color_eyre::Report::msg(format!(
+                        "The row {} of {}: {}",
+                        row,
+                        self.manifest.display(),
+                        err
+                    ))
+                })?;
+            let (public_key, secret_key) = match manifest_transaction.signer_secret_key {
+                Some(secret_key) => (secret_key.public_key(), secret_key),
+                None => {
+                    // The key is chosen for each row as a function-call key may only permit
+                    // some of the transactions of its signer
+                    let transaction = near_primitives::transaction::Transaction {
+                        signer_id: manifest_transaction.signer_id.clone(),
+                        public_key: near_crypto::PublicKey::empty(near_crypto::KeyType::ED25519),
+                        nonce: 0,
+                        receiver_id: manifest_transaction.receiver_id.clone(),
+                        block_hash: Default::default(),
+                        actions: actions.clone(),
+                    };
+                    let stored_key = SignKeychain::select_signer_key(
+                        &*key_store,
+                        &manifest_transaction.signer_id,
+                        &network_connection_config,
+                        Some(&transaction),
+                    )
+                    .await?;
+                    let public_key = stored_key.public_key.clone();
+                    let cache_key = (manifest_transaction.signer_id.clone(), public_key.clone());
+                    let secret_key = match keychain_secret_keys.get(&cache_key) {
+                        Some(secret_key) => secret_key.clone(),
+                        None => {
+                            let secret_key = stored_key.unlock()?.secret_key()?;
+                            keychain_secret_keys.insert(cache_key, secret_key.clone());
+                            secret_key
+                        }
+                    };
+                    (public_key, secret_key)
+                }
+            };
+            transactions.push(BatchTransaction {
+                row,
+                signer_id: manifest_transaction.signer_id,
+                receiver_id: manifest_transaction.receiver_id,
+                actions,
+                public_key,
+                secret_key,
+            });
+        }
+        Ok(transactions)
+    }
+
+    /// The transactions of one access key, in the manifest order. They are sent one by one
+    /// since a transaction with a lower nonce is rejected once a higher one is executed.
+    async fn submit_access_key_transactions(
+        &self,
+        transactions: Vec<BatchTransaction>,
+    ) -> Vec<ReportRow> {
+        let (signer_id, public_key) = (
+            transactions[0].signer_id.clone(),
+            transactions[0].public_key.clone(),
+        );
+        let (mut nonce, block_hash) = match self.fetch_access_key(signer_id, public_key).await {
+            Ok(nonce_and_block_hash) => nonce_and_block_hash,
+            Err(err) => {
+                return transactions
+                    .iter()
+                    .map(|transaction| {
+                        ReportRow::new(transaction, None, "error", Some(err.to_string()))
+                    })
+                    .collect()
+            }
+        };
+        let mut report_rows = vec![];
+        for transaction in transactions {
+            let unsigned_transaction = near_primitives::transaction::Transaction {
+                signer_id: transaction.signer_id.clone(),
+                public_key: transaction.public_key.clone(),
+                nonce: nonce + 1,
+                receiver_id: transaction.receiver_id.clone(),
+                block_hash,
+                actions: transaction.actions.clone(),
+            };
+            nonce += 1;
+            let signature = transaction
+                .secret_key
+                .sign(unsigned_transaction.get_hash_and_size().0.as_ref());
+            let signed_transaction = near_primitives::transaction::SignedTransaction::new(
+                signature,
+                unsigned_transaction,
+            );
+            let transaction_hash = Some(signed_transaction.get_hash().to_string());
+            let report_row = match broadcast_tx_commit(
+                &self.network_connection_config,
+                &signed_transaction,
+                &self.retry_policy,
+            )
+            .await
+            {
+                Ok(transaction_info) => match transaction_info.status {
+                    near_primitives::views::FinalExecutionStatus::SuccessValue(_) => {
+                        ReportRow::new(&transaction, transaction_hash, "success", None)
+                    }
+                    near_primitives::views::FinalExecutionStatus::Failure(err) => ReportRow::new(
+                        &transaction,
+                        transaction_hash,
+                        "failure",
+                        Some(err.to_string()),
+                    ),
+                    near_primitives::views::FinalExecutionStatus::NotStarted
+                    | near_primitives::views::FinalExecutionStatus::Started => {
+                        ReportRow::new(&transaction, transaction_hash, "pending", None)
+                    }
+                },
+                Err(err) => ReportRow::new(
+                    &transaction,
+                    transaction_hash,
+                    "error",
+                    Some(err.to_string()),
+                ),
+            };
+            eprintln!(
+                "Row {}: {} ({})",
+                report_row.row,
+                report_row.status,
+                report_row.transaction_hash.as_deref().unwrap_or_default()
+            );
+            report_rows.push(report_row);
+        }
+        report_rows
+    }
+
+    /// The current nonce of the access key and the hash of the block it was read at
+    async fn fetch_access_key(
+        &self,
+        signer_id: near_primitives::types::AccountId,
+        public_key: near_crypto::PublicKey,
+    ) -> color_eyre::eyre::Result<(
+        near_primitives::types::Nonce,
+        near_primitives::hash::CryptoHash,
+    )> {
+        let access_key_response = self
+            .network_connection_config
+            .rpc_client()
+            .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
+                block_reference: near_primitives::types::Finality::Final.into(),
+                request: near_primitives::views::QueryRequest::ViewAccessKey {
+                    account_id: signer_id,
+                    public_key,
+                },
+            })
+            .await
+            .map_err(|err| {
+                color_eyre::Report::msg(format!(
+                    "Failed to fetch public key information for nonce: {:?}",
+                    err
+                ))
+            })?;
+        match access_key_response.kind {
+            near_jsonrpc_primitives::types::query::QueryResponseKind::AccessKey(access_key) => {
+                Ok((access_key.nonce, access_key_response.block_hash))
+            }
+            _ => Err(color_eyre::Report::msg("Error current_nonce")),
+        }
+    }
+
+    fn write_report(
+        &self,
+        report_rows: &[ReportRow],
+        output_format: crate::common::OutputFormat,
+    ) -> crate::CliResult {
+        let report = match &self.report {
+            Some(report) => report,
+            None => {
+                match output_format {
+                    crate::common::OutputFormat::Plaintext => {
+                        println!();
+                        for report_row in report_rows {
+                            println!(
+                                "{:>5}  {:<8} {:<46} {} -> {}{}",
+                                report_row.row,
+                                report_row.status,
+                                report_row.transaction_hash.as_deref().unwrap_or("-"),
+                                report_row.signer_id,
+                                report_row.receiver_id,
+                                report_row
+                                    .error
+                                    .as_ref()
+                                    .map(|err| format!("\n       {}", err))
+                                    .unwrap_or_default()
+                            );
+                        }
+                    }
+                    crate::common::OutputFormat::Json => {
+                        println!("{}", serde_json::to_string_pretty(report_rows)?);
+                    }
+                };
+                return Ok(());
+            }
+        };
+        let write_error = |err: String| {
+            color_eyre::Report::msg(format!("Failed to write {}: {}", report.display(), err))
+        };
+        match report.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("csv") => {
+                let mut writer =
+                    csv::Writer::from_path(report).map_err(|err| write_error(err.to_string()))?;
+                for report_row in report_rows {
+                    writer
+                        .serialize(report_row)
+                        .map_err(|err| write_error(err.to_string()))?;
+                }
+                writer.flush().map_err(|err| write_error(err.to_string()))?;
+            }
+            _ => std::fs::write(report, serde_json::to_string_pretty(report_rows)?)
+                .map_err(|err| write_error(err.to_string()))?,
+        };
+        eprintln!("The report is written to {}", report.display());
+        Ok(())
+    }
+
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::CliResult {
+        let transactions = self.prepare_transactions().await?;
+        let total = transactions.len();
+        // The whole batch is confirmed once, before any of its transactions is sent
+        let rows = transactions
+            .iter()
+            .map(|transaction| {
+                (
+                    transaction.row,
+                    near_primitives::transaction::Transaction {
+                        signer_id: transaction.signer_id.clone(),
+                        public_key: transaction.public_key.clone(),
+                        nonce: 0,
+                        receiver_id: transaction.receiver_id.clone(),
+                        block_hash: Default::default(),
+                        actions: transaction.actions.clone(),
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        confirm_batch(&self.network_connection_config, &rows).await?;
+        let mut access_keys: Vec<Vec<BatchTransaction>> = vec![];
+        let mut access_key_indices: std::collections::HashMap<(String, String), usize> =
+            std::collections::HashMap::new();
+        for transaction in transactions {
+            let access_key = (
+                transaction.signer_id.clone(),
+                transaction.public_key.to_string(),
+            );
+            match access_key_indices.get(&access_key) {
+                Some(&index) => access_keys[index].push(transaction),
+                None => {
+                    access_key_indices.insert(access_key, access_keys.len());
+                    access_keys.push(vec![transaction]);
+                }
+            }
+        }
+        eprintln!(
+            "Submitting {} transactions of {} access keys to {} ({} access keys at a time)",
+            total,
+            access_keys.len(),
+            self.network_connection_config.rpc_url(),
+            self.concurrency
+        );
+        let mut report_rows = futures::stream::iter(
+            access_keys
+                .into_iter()
+                .map(|transactions| self.submit_access_key_transactions(transactions)),
+        )
+        .buffer_unordered(self.concurrency)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        report_rows.sort_by_key(|report_row| report_row.row);
+        self.write_report(&report_rows, output_format)?;
+        let failed = report_rows
+            .iter()
+            .filter(|report_row| report_row.status != "success")
+            .count();
+        if failed > 0 {
+            return Err(color_eyre::Report::msg(format!(
+                "{} of {} transactions did not succeed",
+                failed, total
+            )));
+        }
+        eprintln!("All {} transactions succeeded", total);
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 #[derive(Debug, clap::Clap)]
 pub enum CliSendTo {
@@ -11,31 +12,46 @@ pub enum SendTo {
     Receiver(Receiver),
 }
 
-impl From<CliSendTo> for SendTo {
-    fn from(item: CliSendTo) -> Self {
-        match item {
+impl TryFrom<CliSendTo> for SendTo {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSendTo) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliSendTo::Receiver(cli_receiver) => {
-                let receiver = Receiver::from(cli_receiver);
+                let receiver = Receiver::try_from(cli_receiver)?;
                 Self::Receiver(receiver)
             }
-        }
+        })
     }
 }
 
 impl SendTo {
-    pub fn send_to() -> Self {
-        Self::from(CliSendTo::Receiver(Default::default()))
+    pub fn send_to() -> color_eyre::eyre::Result<Self> {
+        Self::try_from(CliSendTo::Receiver(Default::default()))
+    }
+
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let (command, mut args) = match self {
+            Self::Receiver(receiver) => ("receiver", receiver.to_cli_args()?),
+        };
+        args.insert(0, command.to_owned());
+        Some(args)
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             SendTo::Receiver(receiver) => {
                 receiver
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
@@ -56,42 +72,58 @@ pub struct Receiver {
     pub action: super::transaction_actions::NextAction,
 }
 
-impl From<CliReceiver> for Receiver {
-    fn from(item: CliReceiver) -> Self {
+impl TryFrom<CliReceiver> for Receiver {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliReceiver) -> color_eyre::eyre::Result<Self> {
         let receiver_account_id: String = match item.receiver_account_id {
             Some(cli_receiver_account_id) => cli_receiver_account_id,
-            None => Receiver::input_receiver_account_id(),
+            None => Receiver::input_receiver_account_id()?,
         };
         let action: super::transaction_actions::NextAction = match item.action {
-            Some(cli_next_action) => super::transaction_actions::NextAction::from(cli_next_action),
-            None => super::transaction_actions::NextAction::input_next_action(),
+            Some(cli_next_action) => {
+                super::transaction_actions::NextAction::try_from(cli_next_action)?
+            }
+            None => super::transaction_actions::NextAction::input_next_action()?,
         };
-        Self {
+        Ok(Self {
             receiver_account_id,
             action,
-        }
+        })
     }
 }
 
 impl Receiver {
-    pub fn input_receiver_account_id() -> String {
-        Input::new()
+    pub fn input_receiver_account_id() -> color_eyre::eyre::Result<String> {
+        crate::common::ensure_interactive("`<RECEIVER_ACCOUNT_ID>` of `receiver`")?;
+        Ok(Input::new()
             .with_prompt("What is the account ID of the receiver?")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let mut args = vec![self.receiver_account_id.clone()];
+        args.extend(self.action.to_cli_args()?);
+        Some(args)
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let unsigned_transaction = near_primitives::transaction::Transaction {
             receiver_id: self.receiver_account_id.clone(),
             ..prepopulated_unsigned_transaction
         };
         self.action
-            .process(unsigned_transaction, network_connection_config)
+            .process(
+                unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
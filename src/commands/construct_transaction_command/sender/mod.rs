@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 /// данные об отправителе транзакции
 #[derive(Debug, Default, clap::Clap)]
@@ -14,43 +15,57 @@ pub struct Sender {
     pub send_to: super::receiver::SendTo,
 }
 
-impl From<CliSender> for Sender {
-    fn from(item: CliSender) -> Self {
+impl TryFrom<CliSender> for Sender {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSender) -> color_eyre::eyre::Result<Self> {
         let sender_account_id: String = match item.sender_account_id {
             Some(cli_sender_account_id) => cli_sender_account_id,
-            None => Sender::input_sender_account_id(),
+            None => Sender::input_sender_account_id()?,
         };
         let send_to: super::receiver::SendTo = match item.send_to {
-            Some(cli_send_to) => super::receiver::SendTo::from(cli_send_to),
-            None => super::receiver::SendTo::send_to(),
+            Some(cli_send_to) => super::receiver::SendTo::try_from(cli_send_to)?,
+            None => super::receiver::SendTo::send_to()?,
         };
-        Self {
+        Ok(Self {
             sender_account_id,
             send_to,
-        }
+        })
     }
 }
 
 impl Sender {
-    pub fn input_sender_account_id() -> String {
+    pub fn input_sender_account_id() -> color_eyre::eyre::Result<String> {
+        crate::common::ensure_interactive("`<SENDER_ACCOUNT_ID>` of `sender`")?;
         println!();
-        Input::new()
+        Ok(Input::new()
             .with_prompt("What is the account ID of the sender?")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let mut args = vec![self.sender_account_id.clone()];
+        args.extend(self.send_to.to_cli_args()?);
+        Some(args)
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let unsigned_transaction = near_primitives::transaction::Transaction {
             signer_id: self.sender_account_id.clone(),
             ..prepopulated_unsigned_transaction
         };
         self.send_to
-            .process(unsigned_transaction, network_connection_config)
+            .process(
+                unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
@@ -0,0 +1,417 @@
+use dialoguer::Input;
+
+thread_local! {
+    /// Whether the RPC server of a URL is a mainnet one, asked once per run
+    static IS_MAINNET: std::cell::RefCell<std::collections::HashMap<url::Url, bool>> =
+        Default::default();
+}
+
+/// Whether the RPC server is a mainnet one, whatever the network is called: the built-in
+/// networks and the `chain_id` of a configured network tell it, otherwise the chain ID of the
+/// node (or, if the node does not answer, its URL)
+async fn is_mainnet(network_connection_config: &crate::common::ConnectionConfig) -> bool {
+    let rpc_url = match network_connection_config {
+        crate::common::ConnectionConfig::Mainnet => return true,
+        crate::common::ConnectionConfig::Testnet
+        | crate::common::ConnectionConfig::Betanet
+        | crate::common::ConnectionConfig::Localnet => return false,
+        crate::common::ConnectionConfig::Named(crate::config::NetworkConfig {
+            chain_id: Some(chain_id),
+            ..
+        }) => return chain_id == "mainnet",
+        _ => network_connection_config.rpc_url(),
+    };
+    if let Some(is_mainnet) =
+        IS_MAINNET.with(|is_mainnet| is_mainnet.borrow().get(&rpc_url).copied())
+    {
+        return is_mainnet;
+    }
+    let is_mainnet = match network_connection_config.rpc_client().status().await {
+        Ok(status) => status.chain_id == "mainnet",
+        Err(_) => {
+            rpc_url.host_str()
+                == crate::common::ConnectionConfig::Mainnet
+                    .rpc_url()
+                    .host_str()
+        }
+    };
+    IS_MAINNET.with(|cache| cache.borrow_mut().insert(rpc_url, is_mainnet));
+    is_mainnet
+}
+
+/// Why the transaction has to be confirmed before it is sent (none for a usual testnet one)
+async fn confirmation_reasons(
+    network_connection_config: &crate::common::ConnectionConfig,
+    transaction: &near_primitives::transaction::Transaction,
+) -> Vec<String> {
+    let mut reasons = vec![];
+    if is_mainnet(network_connection_config).await {
+        reasons.push("it is sent to mainnet".to_string());
+    }
+    reasons.extend(destructive_reasons(network_connection_config, transaction).await);
+    reasons
+}
+
+/// The actions of the transaction that cannot be undone on any network
+async fn destructive_reasons(
+    network_connection_config: &crate::common::ConnectionConfig,
+    transaction: &near_primitives::transaction::Transaction,
+) -> Vec<String> {
+    let mut reasons = delete_account_reasons(transaction);
+    let deletes_keys = transaction
+        .actions
+        .iter()
+        .any(|action| matches!(action, near_primitives::transaction::Action::DeleteKey(_)));
+    if deletes_keys {
+        match full_access_keys(network_connection_config, &transaction.receiver_id).await {
+            Ok(full_access_keys) => {
+                if deletes_last_full_access_key(transaction, &full_access_keys) {
+                    reasons.push(format!(
+                        "it deletes the last full access key of {}",
+                        transaction.receiver_id
+                    ));
+                }
+            }
+            Err(err) => reasons.push(format!(
+                "it deletes access keys of {}, and whether the last full access key is among them is unknown ({})",
+                transaction.receiver_id, err
+            )),
+        }
+    }
+    reasons
+}
+
+fn delete_account_reasons(transaction: &near_primitives::transaction::Transaction) -> Vec<String> {
+    transaction
+        .actions
+        .iter()
+        .filter_map(|action| match action {
+            near_primitives::transaction::Action::DeleteAccount(delete_account) => Some(format!(
+                "it deletes the account {} (the balance goes to {})",
+                transaction.receiver_id, delete_account.beneficiary_id
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Whether the account had full access keys and the transaction deletes all of them without
+/// adding a new one
+fn deletes_last_full_access_key(
+    transaction: &near_primitives::transaction::Transaction,
+    full_access_keys: &[near_crypto::PublicKey],
+) -> bool {
+    let mut deleted_keys = vec![];
+    let mut added_full_access_keys = 0;
+    for action in &transaction.actions {
+        match action {
+            near_primitives::transaction::Action::DeleteKey(delete_key) => {
+                deleted_keys.push(delete_key.public_key.clone())
+            }
+            near_primitives::transaction::Action::AddKey(add_key) => {
+                if let near_primitives::account::AccessKeyPermission::FullAccess =
+                    add_key.access_key.permission
+                {
+                    added_full_access_keys += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    let remaining = full_access_keys
+        .iter()
+        .filter(|public_key| !deleted_keys.contains(public_key))
+        .count()
+        + added_full_access_keys;
+    remaining == 0 && !full_access_keys.is_empty()
+}
+
+async fn full_access_keys(
+    network_connection_config: &crate::common::ConnectionConfig,
+    account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<Vec<near_crypto::PublicKey>> {
+    let query_view_method_response = network_connection_config
+        .rpc_client()
+        .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
+            block_reference: near_primitives::types::Finality::Final.into(),
+            request: near_primitives::views::QueryRequest::ViewAccessKeyList {
+                account_id: account_id.clone(),
+            },
+        })
+        .await
+        .map_err(|err| {
+            color_eyre::Report::msg(format!(
+                "Failed to fetch query for view key list: {:?}",
+                err
+            ))
+        })?;
+    match query_view_method_response.kind {
+        near_jsonrpc_primitives::types::query::QueryResponseKind::AccessKeyList(result) => {
+            Ok(result
+                .keys
+                .into_iter()
+                .filter(|access_key| {
+                    matches!(
+                        access_key.access_key.permission,
+                        near_primitives::views::AccessKeyPermissionView::FullAccess
+                    )
+                })
+                .map(|access_key| access_key.public_key)
+                .collect())
+        }
+        _ => Err(color_eyre::Report::msg("Error call result".to_string())),
+    }
+}
+
+/// Shows what a mainnet or destructive transaction does and asks to type "yes" before it is
+/// sent; `--yes` skips the question, and without a terminal the transaction is not sent
+pub async fn confirm_sending(
+    network_connection_config: &crate::common::ConnectionConfig,
+    transaction: &near_primitives::transaction::Transaction,
+) -> crate::CliResult {
+    if crate::common::is_assume_yes() {
+        return Ok(());
+    }
+    let reasons = confirmation_reasons(network_connection_config, transaction).await;
+    if reasons.is_empty() {
+        return Ok(());
+    }
+    if crate::common::is_non_interactive() {
+        return Err(color_eyre::Report::msg(format!(
+            "The transaction needs a confirmation because {}; pass --yes to send it anyway",
+            reasons.join(" and ")
+        )));
+    }
+    eprintln!("\n========= CONFIRMATION =========");
+    eprintln!("Network:  {}", network_connection_config.name());
+    eprintln!("Signer:   {}", transaction.signer_id);
+    eprintln!("Receiver: {}", transaction.receiver_id);
+    eprintln!("Actions:");
+    for (index, action) in transaction.actions.iter().enumerate() {
+        eprintln!(
+            "  {}. {}",
+            index + 1,
+            crate::common::describe_action(action)
+        );
+    }
+    match super::cost_estimate::estimate(network_connection_config, transaction).await {
+        Ok(cost_estimate) => {
+            eprintln!(
+                "Estimated fees: up to {} (with the deposits, up to {})",
+                crate::common::NearBalance::from_yoctonear(cost_estimate.gas_cost).to_cli_arg(),
+                crate::common::NearBalance::from_yoctonear(cost_estimate.max_cost).to_cli_arg()
+            );
+            for warning in &cost_estimate.warnings {
+                eprintln!("WARNING: {}", warning);
+            }
+        }
+        Err(err) => eprintln!("Estimated fees: unknown ({})", err),
+    }
+    eprintln!(
+        "\nThis transaction needs a confirmation because {}.",
+        reasons.join(" and ")
+    );
+    let answer: String = Input::new()
+        .with_prompt("Type `yes` to send it")
+        .allow_empty(true)
+        .interact_text()
+        .unwrap();
+    if answer.trim() == "yes" {
+        Ok(())
+    } else {
+        Err(color_eyre::Report::msg("The transaction was not sent"))
+    }
+}
+
+/// Shows the batch (the number of transactions, the total deposit and the destructive rows)
+/// and asks to type "yes" once, before any of its transactions is sent, when it goes to mainnet
+/// or has destructive rows; `--yes` skips the question, and without a terminal nothing is sent
+pub async fn confirm_batch(
+    network_connection_config: &crate::common::ConnectionConfig,
+    rows: &[(usize, near_primitives::transaction::Transaction)],
+) -> crate::CliResult {
+    if crate::common::is_assume_yes() {
+        return Ok(());
+    }
+    let mut reasons = vec![];
+    if is_mainnet(network_connection_config).await {
+        reasons.push("it is sent to mainnet".to_string());
+    }
+    let mut destructive_rows = vec![];
+    for (row, transaction) in rows {
+        let row_reasons = destructive_reasons(network_connection_config, transaction).await;
+        if !row_reasons.is_empty() {
+            destructive_rows.push((row, transaction, row_reasons));
+        }
+    }
+    if !destructive_rows.is_empty() {
+        reasons.push(format!(
+            "{} of its rows cannot be undone",
+            destructive_rows.len()
+        ));
+    }
+    if reasons.is_empty() {
+        return Ok(());
+    }
+    if crate::common::is_non_interactive() {
+        return Err(color_eyre::Report::msg(format!(
+            "The batch needs a confirmation because {}; pass --yes to send it anyway",
+            reasons.join(" and ")
+        )));
+    }
+    let total_deposit: near_primitives::types::Balance = rows
+        .iter()
+        .flat_map(|(_, transaction)| &transaction.actions)
+        .map(|action| match action {
+            near_primitives::transaction::Action::Transfer(transfer) => transfer.deposit,
+            near_primitives::transaction::Action::FunctionCall(function_call) => {
+                function_call.deposit
+            }
+            _ => 0,
+        })
+        .sum();
+    eprintln!("\n========= CONFIRMATION =========");
+    eprintln!("Network:       {}", network_connection_config.name());
+    eprintln!("Transactions:  {}", rows.len());
+    eprintln!(
+        "Total deposit: {}",
+        crate::common::NearBalance::from_yoctonear(total_deposit).to_cli_arg()
+    );
+    if !destructive_rows.is_empty() {
+        eprintln!("Rows that cannot be undone:");
+        for (row, transaction, row_reasons) in &destructive_rows {
+            eprintln!(
+                "  {}. {} -> {}: {}",
+                row,
+                transaction.signer_id,
+                transaction.receiver_id,
+                row_reasons.join(" and ")
+            );
+        }
+    }
+    eprintln!(
+        "\nThis batch needs a confirmation because {}.",
+        reasons.join(" and ")
+    );
+    let answer: String = Input::new()
+        .with_prompt(format!(
+            "Type `yes` to send its {} transactions",
+            rows.len()
+        ))
+        .allow_empty(true)
+        .interact_text()
+        .unwrap();
+    if answer.trim() == "yes" {
+        Ok(())
+    } else {
+        Err(color_eyre::Report::msg("The batch was not sent"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn public_key(seed: &str) -> near_crypto::PublicKey {
+        near_crypto::SecretKey::from_seed(near_crypto::KeyType::ED25519, seed).public_key()
+    }
+
+    fn transaction(
+        actions: Vec<near_primitives::transaction::Action>,
+    ) -> near_primitives::transaction::Transaction {
+        near_primitives::transaction::Transaction {
+            signer_id: "volodymyr.testnet".to_string(),
+            public_key: public_key("signer"),
+            nonce: 2,
+            receiver_id: "volodymyr.testnet".to_string(),
+            block_hash: Default::default(),
+            actions,
+        }
+    }
+
+    fn delete_key(seed: &str) -> near_primitives::transaction::Action {
+        near_primitives::transaction::Action::DeleteKey(
+            near_primitives::transaction::DeleteKeyAction {
+                public_key: public_key(seed),
+            },
+        )
+    }
+
+    fn add_key(
+        seed: &str,
+        permission: near_primitives::account::AccessKeyPermission,
+    ) -> near_primitives::transaction::Action {
+        near_primitives::transaction::Action::AddKey(near_primitives::transaction::AddKeyAction {
+            public_key: public_key(seed),
+            access_key: near_primitives::account::AccessKey {
+                nonce: 0,
+                permission,
+            },
+        })
+    }
+
+    #[test]
+    fn deleting_the_last_full_access_key_needs_a_confirmation() {
+        let full_access_keys = vec![public_key("a"), public_key("b")];
+        assert!(!deletes_last_full_access_key(
+            &transaction(vec![delete_key("a")]),
+            &full_access_keys
+        ));
+        assert!(deletes_last_full_access_key(
+            &transaction(vec![delete_key("a"), delete_key("b")]),
+            &full_access_keys
+        ));
+        // A new full access key keeps the account usable, a function-call key does not
+        assert!(!deletes_last_full_access_key(
+            &transaction(vec![
+                delete_key("a"),
+                delete_key("b"),
+                add_key(
+                    "c",
+                    near_primitives::account::AccessKeyPermission::FullAccess
+                )
+            ]),
+            &full_access_keys
+        ));
+        assert!(deletes_last_full_access_key(
+            &transaction(vec![
+                delete_key("a"),
+                delete_key("b"),
+                add_key(
+                    "c",
+                    near_primitives::account::AccessKeyPermission::FunctionCall(
+                        near_primitives::account::FunctionCallPermission {
+                            allowance: None,
+                            receiver_id: "counter.testnet".to_string(),
+                            method_names: vec![],
+                        }
+                    )
+                )
+            ]),
+            &full_access_keys
+        ));
+        // An account with only function-call keys has no full access key to lose
+        assert!(!deletes_last_full_access_key(
+            &transaction(vec![delete_key("a")]),
+            &[]
+        ));
+    }
+
+    #[test]
+    fn deleting_the_account_needs_a_confirmation() {
+        assert_eq!(
+            delete_account_reasons(&transaction(vec![
+                near_primitives::transaction::Action::DeleteAccount(
+                    near_primitives::transaction::DeleteAccountAction {
+                        beneficiary_id: "fund.testnet".to_string(),
+                    }
+                )
+            ])),
+            vec![
+                "it deletes the account volodymyr.testnet (the balance goes to fund.testnet)"
+                    .to_string()
+            ]
+        );
+        assert!(delete_account_reasons(&transaction(vec![delete_key("a")])).is_empty());
+    }
+}
@@ -0,0 +1,499 @@
+#[derive(Debug, serde::Deserialize)]
+struct Fee {
+    send_sir: near_primitives::types::Gas,
+    send_not_sir: near_primitives::types::Gas,
+    execution: near_primitives::types::Gas,
+}
+
+impl Fee {
+    /// `sir` ("sender is receiver") fees are lower
+    fn total(&self, sir: bool) -> near_primitives::types::Gas {
+        if sir {
+            self.send_sir + self.execution
+        } else {
+            self.send_not_sir + self.execution
+        }
+    }
+
+    fn total_per_byte(&self, sir: bool, bytes: usize) -> near_primitives::types::Gas {
+        self.total(sir) * bytes as near_primitives::types::Gas
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct AccessKeyCreationConfig {
+    full_access_cost: Fee,
+    function_call_cost: Fee,
+    function_call_cost_per_byte: Fee,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ActionCreationConfig {
+    create_account_cost: Fee,
+    deploy_contract_cost: Fee,
+    deploy_contract_cost_per_byte: Fee,
+    function_call_cost: Fee,
+    function_call_cost_per_byte: Fee,
+    transfer_cost: Fee,
+    stake_cost: Fee,
+    add_key_cost: AccessKeyCreationConfig,
+    delete_key_cost: Fee,
+    delete_account_cost: Fee,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct TransactionCosts {
+    action_receipt_creation_config: Fee,
+    action_creation_config: ActionCreationConfig,
+}
+
+/// The part of the `EXPERIMENTAL_protocol_config` response with the fees
+#[derive(Debug, serde::Deserialize)]
+struct ProtocolConfig {
+    runtime_config: RuntimeConfig,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct RuntimeConfig {
+    transaction_costs: TransactionCosts,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct GasPrice {
+    #[serde(with = "near_primitives::serialize::u128_dec_format")]
+    gas_price: near_primitives::types::Balance,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct CostEstimate {
+    /// The action fees and the prepaid gas of the function calls
+    pub gas: near_primitives::types::Gas,
+    #[serde(with = "near_primitives::serialize::u128_dec_format")]
+    pub gas_price: near_primitives::types::Balance,
+    #[serde(with = "near_primitives::serialize::u128_dec_format")]
+    pub gas_cost: near_primitives::types::Balance,
+    /// Transfers and the deposits of the function calls
+    #[serde(with = "near_primitives::serialize::u128_dec_format")]
+    pub deposit: near_primitives::types::Balance,
+    #[serde(with = "near_primitives::serialize::u128_dec_format")]
+    pub max_cost: near_primitives::types::Balance,
+    #[serde(with = "near_primitives::serialize::u128_dec_format")]
+    pub stake: near_primitives::types::Balance,
+    #[serde(with = "near_primitives::serialize::u128_dec_format")]
+    pub signer_balance: near_primitives::types::Balance,
+    #[serde(with = "near_primitives::serialize::u128_dec_format")]
+    pub signer_locked: near_primitives::types::Balance,
+    pub warnings: Vec<String>,
+}
+
+fn transaction_gas(
+    transaction: &near_primitives::transaction::Transaction,
+    costs: &TransactionCosts,
+) -> near_primitives::types::Gas {
+    let sir = transaction.signer_id == transaction.receiver_id;
+    let fees = &costs.action_creation_config;
+    let mut gas = costs.action_receipt_creation_config.total(sir);
+    for action in &transaction.actions {
+        gas += match action {
+            near_primitives::transaction::Action::CreateAccount(_) => {
+                fees.create_account_cost.total(sir)
+            }
+            near_primitives::transaction::Action::DeployContract(deploy_contract) => {
+                fees.deploy_contract_cost.total(sir)
+                    + fees
+                        .deploy_contract_cost_per_byte
+                        .total_per_byte(sir, deploy_contract.code.len())
+            }
+            near_primitives::transaction::Action::FunctionCall(function_call) => {
+                fees.function_call_cost.total(sir)
+                    + fees.function_call_cost_per_byte.total_per_byte(
+                        sir,
+                        function_call.method_name.len() + function_call.args.len(),
+                    )
+                    + function_call.gas
+            }
+            near_primitives::transaction::Action::Transfer(_) => fees.transfer_cost.total(sir),
+            near_primitives::transaction::Action::Stake(_) => fees.stake_cost.total(sir),
+            near_primitives::transaction::Action::AddKey(add_key) => {
+                match &add_key.access_key.permission {
+                    near_primitives::account::AccessKeyPermission::FullAccess => {
+                        fees.add_key_cost.full_access_cost.total(sir)
+                    }
+                    near_primitives::account::AccessKeyPermission::FunctionCall(permission) => {
+                        fees.add_key_cost.function_call_cost.total(sir)
+                            + fees
+                                .add_key_cost
+                                .function_call_cost_per_byte
+                                .total_per_byte(
+                                    sir,
+                                    permission
+                                        .method_names
+                                        .iter()
+                                        .map(|method_name| method_name.len())
+                                        .sum(),
+                                )
+                    }
+                }
+            }
+            near_primitives::transaction::Action::DeleteKey(_) => fees.delete_key_cost.total(sir),
+            near_primitives::transaction::Action::DeleteAccount(_) => {
+                fees.delete_account_cost.total(sir)
+            }
+        };
+    }
+    gas
+}
+
+async fn fetch_transaction_costs(
+    network_connection_config: &crate::common::ConnectionConfig,
+) -> color_eyre::eyre::Result<TransactionCosts> {
+    let protocol_config: ProtocolConfig = serde_json::from_value(
+        network_connection_config
+            .call_json_rpc(
+                "EXPERIMENTAL_protocol_config",
+                serde_json::json!({ "finality": "final" }),
+            )
+            .await?,
+    )
+    .map_err(|err| {
+        color_eyre::Report::msg(format!("Failed to parse the protocol config: {}", err))
+    })?;
+    Ok(protocol_config.runtime_config.transaction_costs)
+}
+
+async fn fetch_gas_price(
+    network_connection_config: &crate::common::ConnectionConfig,
+) -> color_eyre::eyre::Result<near_primitives::types::Balance> {
+    let gas_price: GasPrice = serde_json::from_value(
+        network_connection_config
+            .call_json_rpc("gas_price", serde_json::json!([null]))
+            .await?,
+    )
+    .map_err(|err| color_eyre::Report::msg(format!("Failed to parse the gas price: {}", err)))?;
+    Ok(gas_price.gas_price)
+}
+
+/// The gas of the transaction at the current gas price, which the allowance of
+/// a function-call key has to cover
+pub async fn max_gas_cost(
+    network_connection_config: &crate::common::ConnectionConfig,
+    transaction: &near_primitives::transaction::Transaction,
+) -> color_eyre::eyre::Result<near_primitives::types::Balance> {
+    let transaction_costs = fetch_transaction_costs(network_connection_config).await?;
+    let gas_price = fetch_gas_price(network_connection_config).await?;
+    Ok(transaction_gas(transaction, &transaction_costs) as u128 * gas_price)
+}
+
+/// The largest amount the transaction can cost, computed from the fees of the protocol config
+/// and the current gas price (the refunds of unused gas are not taken into account)
+pub async fn estimate(
+    network_connection_config: &crate::common::ConnectionConfig,
+    transaction: &near_primitives::transaction::Transaction,
+) -> color_eyre::eyre::Result<CostEstimate> {
+    let transaction_costs = fetch_transaction_costs(network_connection_config).await?;
+    let gas_price = fetch_gas_price(network_connection_config).await?;
+    let account_view = match network_connection_config
+        .rpc_client()
+        .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
+            block_reference: near_primitives::types::Finality::Final.into(),
+            request: near_primitives::views::QueryRequest::ViewAccount {
+                account_id: transaction.signer_id.clone(),
+            },
+        })
+        .await
+        .map_err(|err| {
+            color_eyre::Report::msg(format!("Failed to fetch query for view account: {:?}", err))
+        })?
+        .kind
+    {
+        near_jsonrpc_primitives::types::query::QueryResponseKind::ViewAccount(account_view) => {
+            account_view
+        }
+        _ => return Err(color_eyre::Report::msg("Error call result".to_string())),
+    };
+    Ok(cost_estimate(
+        transaction,
+        &transaction_costs,
+        gas_price,
+        account_view.amount,
+        account_view.locked,
+    ))
+}
+
+fn cost_estimate(
+    transaction: &near_primitives::transaction::Transaction,
+    transaction_costs: &TransactionCosts,
+    gas_price: near_primitives::types::Balance,
+    signer_balance: near_primitives::types::Balance,
+    signer_locked: near_primitives::types::Balance,
+) -> CostEstimate {
+    let gas = transaction_gas(transaction, transaction_costs);
+    let gas_cost = gas as u128 * gas_price;
+    let (mut deposit, mut stake) = (0, 0);
+    for action in &transaction.actions {
+        match action {
+            near_primitives::transaction::Action::Transfer(transfer) => deposit += transfer.deposit,
+            near_primitives::transaction::Action::FunctionCall(function_call) => {
+                deposit += function_call.deposit
+            }
+            near_primitives::transaction::Action::Stake(stake_action) => stake = stake_action.stake,
+            _ => {}
+        }
+    }
+    let max_cost = gas_cost + deposit;
+    let mut warnings = vec![];
+    if max_cost > signer_balance {
+        warnings.push(format!(
+            "The balance of {} ({}) is less than the maximum cost of the transaction ({})",
+            transaction.signer_id,
+            crate::common::NearBalance::from_yoctonear(signer_balance).to_cli_arg(),
+            crate::common::NearBalance::from_yoctonear(max_cost).to_cli_arg()
+        ));
+    }
+    // A stake replaces the locked amount, and the costs are paid from the liquid balance first
+    let available_for_stake = signer_balance - max_cost.min(signer_balance) + signer_locked;
+    if stake > available_for_stake {
+        warnings.push(format!(
+            "{} cannot stake {}: only {} is available for staking",
+            transaction.signer_id,
+            crate::common::NearBalance::from_yoctonear(stake).to_cli_arg(),
+            crate::common::NearBalance::from_yoctonear(available_for_stake).to_cli_arg()
+        ));
+    }
+    CostEstimate {
+        gas,
+        gas_price,
+        gas_cost,
+        deposit,
+        max_cost,
+        stake,
+        signer_balance,
+        signer_locked,
+        warnings,
+    }
+}
+
+pub fn display_cost_estimate(
+    cost_estimate: &CostEstimate,
+    output_format: crate::common::OutputFormat,
+) -> crate::CliResult {
+    match output_format {
+        crate::common::OutputFormat::Plaintext => {
+            let near = |amount| crate::common::NearBalance::from_yoctonear(amount).to_cli_arg();
+            println!("\n========= COST ESTIMATE (not sent) =========");
+            println!(
+                "Gas (fees and prepaid gas): {}",
+                crate::common::NearGas {
+                    inner: cost_estimate.gas
+                }
+                .to_cli_arg()
+            );
+            println!(
+                "Gas price:                  {} yoctoNEAR",
+                cost_estimate.gas_price
+            );
+            println!(
+                "Gas cost:                   {}",
+                near(cost_estimate.gas_cost)
+            );
+            println!(
+                "Deposits:                   {}",
+                near(cost_estimate.deposit)
+            );
+            println!(
+                "Maximum cost:               {}",
+                near(cost_estimate.max_cost)
+            );
+            if cost_estimate.stake > 0 {
+                println!("Stake:                      {}", near(cost_estimate.stake));
+            }
+            println!(
+                "Signer balance:             {}",
+                near(cost_estimate.signer_balance)
+            );
+            for warning in &cost_estimate.warnings {
+                println!("WARNING: {}", warning);
+            }
+        }
+        crate::common::OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(cost_estimate)?);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every fee is distinct, so a wrong fee in a sum changes the result
+    fn fee(send_sir: u64, send_not_sir: u64, execution: u64) -> serde_json::Value {
+        serde_json::json!({
+            "send_sir": send_sir,
+            "send_not_sir": send_not_sir,
+            "execution": execution,
+        })
+    }
+
+    fn costs() -> TransactionCosts {
+        serde_json::from_value(serde_json::json!({
+            "action_receipt_creation_config": fee(1, 2, 10),
+            "action_creation_config": {
+                "create_account_cost": fee(100, 200, 1000),
+                "deploy_contract_cost": fee(3, 4, 20),
+                "deploy_contract_cost_per_byte": fee(5, 6, 30),
+                "function_call_cost": fee(7, 8, 40),
+                "function_call_cost_per_byte": fee(9, 11, 50),
+                "transfer_cost": fee(12, 13, 60),
+                "stake_cost": fee(14, 15, 70),
+                "add_key_cost": {
+                    "full_access_cost": fee(16, 17, 80),
+                    "function_call_cost": fee(18, 19, 90),
+                    "function_call_cost_per_byte": fee(21, 22, 110),
+                },
+                "delete_key_cost": fee(23, 24, 120),
+                "delete_account_cost": fee(25, 26, 130),
+            },
+        }))
+        .unwrap()
+    }
+
+    fn transaction(
+        receiver_id: &str,
+        actions: Vec<near_primitives::transaction::Action>,
+    ) -> near_primitives::transaction::Transaction {
+        near_primitives::transaction::Transaction {
+            signer_id: "volodymyr.testnet".to_string(),
+            public_key: near_crypto::PublicKey::empty(near_crypto::KeyType::ED25519),
+            nonce: 2,
+            receiver_id: receiver_id.to_string(),
+            block_hash: Default::default(),
+            actions,
+        }
+    }
+
+    fn transfer(deposit: u128) -> near_primitives::transaction::Action {
+        near_primitives::transaction::Action::Transfer(
+            near_primitives::transaction::TransferAction { deposit },
+        )
+    }
+
+    #[test]
+    fn sender_is_receiver_fees_are_used_for_own_account() {
+        let costs = costs();
+        assert_eq!(
+            transaction_gas(&transaction("volodymyr.testnet", vec![transfer(1)]), &costs),
+            (1 + 10) + (12 + 60)
+        );
+        assert_eq!(
+            transaction_gas(&transaction("alice.testnet", vec![transfer(1)]), &costs),
+            (2 + 10) + (13 + 60)
+        );
+    }
+
+    #[test]
+    fn deploy_contract_is_charged_per_byte() {
+        let deploy = near_primitives::transaction::Action::DeployContract(
+            near_primitives::transaction::DeployContractAction {
+                code: vec![0; 1000],
+            },
+        );
+        assert_eq!(
+            transaction_gas(&transaction("alice.testnet", vec![deploy]), &costs()),
+            (2 + 10) + (4 + 20) + 1000 * (6 + 30)
+        );
+    }
+
+    #[test]
+    fn function_call_is_charged_for_its_bytes_and_prepaid_gas() {
+        let call = near_primitives::transaction::Action::FunctionCall(
+            near_primitives::transaction::FunctionCallAction {
+                method_name: "increment".to_string(),
+                args: b"{\"by\":1}".to_vec(),
+                gas: 30_000_000_000_000,
+                deposit: 0,
+            },
+        );
+        assert_eq!(
+            transaction_gas(&transaction("counter.testnet", vec![call]), &costs()),
+            (2 + 10) + (8 + 40) + (9 + 8) * (11 + 50) + 30_000_000_000_000
+        );
+    }
+
+    #[test]
+    fn function_call_key_is_charged_for_its_method_names() {
+        let add_key = |permission| {
+            near_primitives::transaction::Action::AddKey(
+                near_primitives::transaction::AddKeyAction {
+                    public_key: near_crypto::PublicKey::empty(near_crypto::KeyType::ED25519),
+                    access_key: near_primitives::account::AccessKey {
+                        nonce: 0,
+                        permission,
+                    },
+                },
+            )
+        };
+        let function_call_key =
+            add_key(near_primitives::account::AccessKeyPermission::FunctionCall(
+                near_primitives::account::FunctionCallPermission {
+                    allowance: None,
+                    receiver_id: "counter.testnet".to_string(),
+                    method_names: vec!["increment".to_string(), "reset".to_string()],
+                },
+            ));
+        let full_access_key = add_key(near_primitives::account::AccessKeyPermission::FullAccess);
+        assert_eq!(
+            transaction_gas(
+                &transaction("volodymyr.testnet", vec![function_call_key]),
+                &costs()
+            ),
+            (1 + 10) + (18 + 90) + (9 + 5) * (21 + 110)
+        );
+        assert_eq!(
+            transaction_gas(
+                &transaction("volodymyr.testnet", vec![full_access_key]),
+                &costs()
+            ),
+            (1 + 10) + (16 + 80)
+        );
+    }
+
+    #[test]
+    fn insufficient_balance_is_a_warning() {
+        let transaction = transaction("alice.testnet", vec![transfer(1_000)]);
+        let gas = (2 + 10) + (13 + 60);
+        let estimate = cost_estimate(&transaction, &costs(), 100, 1_000 + gas * 100, 0);
+        assert_eq!(estimate.gas_cost, gas * 100);
+        assert_eq!(estimate.deposit, 1_000);
+        assert_eq!(estimate.max_cost, 1_000 + gas * 100);
+        assert!(estimate.warnings.is_empty());
+
+        let estimate = cost_estimate(&transaction, &costs(), 100, 1_000 + gas * 100 - 1, 0);
+        assert_eq!(estimate.warnings.len(), 1);
+        assert!(estimate.warnings[0].starts_with("The balance of volodymyr.testnet"));
+    }
+
+    #[test]
+    fn stake_above_the_available_amount_is_a_warning() {
+        let stake = |stake| {
+            transaction(
+                "volodymyr.testnet",
+                vec![near_primitives::transaction::Action::Stake(
+                    near_primitives::transaction::StakeAction {
+                        stake,
+                        public_key: near_crypto::PublicKey::empty(near_crypto::KeyType::ED25519),
+                    },
+                )],
+            )
+        };
+        let gas_cost = ((1 + 10) + (14 + 70)) * 100;
+        // The locked amount can be restaked, the costs are paid from the liquid balance
+        let estimate = cost_estimate(&stake(5_000), &costs(), 100, gas_cost + 1_000, 4_000);
+        assert_eq!(estimate.stake, 5_000);
+        assert!(estimate.warnings.is_empty());
+
+        let estimate = cost_estimate(&stake(5_001), &costs(), 100, gas_cost + 1_000, 4_000);
+        assert_eq!(estimate.warnings.len(), 1);
+        assert!(estimate.warnings[0].starts_with("volodymyr.testnet cannot stake"));
+    }
+}
@@ -1,9 +1,14 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::TryFrom;
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
+pub mod confirmation;
+pub mod cost_estimate;
 mod sign_manually;
+mod sign_with_external_signer;
 pub mod sign_with_keychain;
 pub mod sign_with_private_key;
+mod sign_with_wallet;
 
 #[derive(Debug, clap::Clap)]
 pub enum CliSignTransaction {
@@ -11,6 +16,10 @@ pub enum CliSignTransaction {
     SignPrivateKey(self::sign_with_private_key::CliSignPrivateKey),
     /// Provide arguments to sign a keychain transaction
     SignWithKeychain(self::sign_with_keychain::CliSignKeychain),
+    /// Sign and send the transaction in the web wallet of the network
+    SignWithWallet(self::sign_with_wallet::CliSignWallet),
+    /// Sign the transaction with an external program that holds the key
+    SignWithExternalSigner(self::sign_with_external_signer::CliSignExternalSigner),
     /// Provide arguments to sign a manually transaction
     SignManually(self::sign_manually::CliSignManually),
 }
@@ -24,35 +33,56 @@ pub enum SignTransaction {
     SignPrivateKey(self::sign_with_private_key::SignPrivateKey),
     #[strum_discriminants(strum(message = "Yes, I want to sign the transaction with keychain"))]
     SignWithKeychain(self::sign_with_keychain::SignKeychain),
+    #[strum_discriminants(strum(message = "Yes, I want to sign the transaction in the wallet"))]
+    SignWithWallet(self::sign_with_wallet::SignWallet),
+    #[strum_discriminants(strum(
+        message = "Yes, I want to sign the transaction with an external signer"
+    ))]
+    SignWithExternalSigner(self::sign_with_external_signer::SignExternalSigner),
     #[strum_discriminants(strum(
         message = "No, I want to construct the transaction and sign it somewhere else"
     ))]
     SignManually(self::sign_manually::SignManually),
 }
 
-impl From<CliSignTransaction> for SignTransaction {
-    fn from(item: CliSignTransaction) -> Self {
-        match item {
+impl TryFrom<CliSignTransaction> for SignTransaction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSignTransaction) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliSignTransaction::SignPrivateKey(cli_private_key) => {
-                let privat_key = self::sign_with_private_key::SignPrivateKey::from(cli_private_key);
+                let privat_key =
+                    self::sign_with_private_key::SignPrivateKey::try_from(cli_private_key)?;
                 SignTransaction::SignPrivateKey(privat_key)
             }
             CliSignTransaction::SignWithKeychain(cli_key_chain) => {
-                let key_chain = self::sign_with_keychain::SignKeychain::from(cli_key_chain);
+                let key_chain = self::sign_with_keychain::SignKeychain::try_from(cli_key_chain)?;
                 SignTransaction::SignWithKeychain(key_chain)
             }
+            CliSignTransaction::SignWithWallet(cli_wallet) => {
+                let wallet = self::sign_with_wallet::SignWallet::try_from(cli_wallet)?;
+                SignTransaction::SignWithWallet(wallet)
+            }
+            CliSignTransaction::SignWithExternalSigner(cli_external_signer) => {
+                let external_signer =
+                    self::sign_with_external_signer::SignExternalSigner::try_from(
+                        cli_external_signer,
+                    )?;
+                SignTransaction::SignWithExternalSigner(external_signer)
+            }
             CliSignTransaction::SignManually(cli_manually) => {
-                let manually = self::sign_manually::SignManually::from(cli_manually);
+                let manually = self::sign_manually::SignManually::try_from(cli_manually)?;
                 SignTransaction::SignManually(manually)
             }
-        }
+        })
     }
 }
 
 impl SignTransaction {
-    pub fn choose_sign_option() -> Self {
+    pub fn choose_sign_option() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = SignTransactionDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let sign_options = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -70,31 +100,82 @@ impl SignTransaction {
             SignTransactionDiscriminants::SignWithKeychain => {
                 CliSignTransaction::SignWithKeychain(Default::default())
             }
+            SignTransactionDiscriminants::SignWithWallet => {
+                CliSignTransaction::SignWithWallet(Default::default())
+            }
+            SignTransactionDiscriminants::SignWithExternalSigner => {
+                CliSignTransaction::SignWithExternalSigner(Default::default())
+            }
             SignTransactionDiscriminants::SignManually => {
                 CliSignTransaction::SignManually(Default::default())
             }
         };
-        Self::from(cli_sign_option)
+        Self::try_from(cli_sign_option)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (sign_option, mut args) = match self {
+            Self::SignPrivateKey(keys) => ("sign-private-key", keys.to_cli_args()),
+            Self::SignWithKeychain(chain) => ("sign-with-keychain", chain.to_cli_args()),
+            Self::SignWithWallet(wallet) => ("sign-with-wallet", wallet.to_cli_args()),
+            Self::SignWithExternalSigner(external_signer) => {
+                ("sign-with-external-signer", external_signer.to_cli_args())
+            }
+            Self::SignManually(args_manually) => ("sign-manually", args_manually.to_cli_args()),
+        };
+        args.insert(0, sign_option.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             SignTransaction::SignPrivateKey(keys) => {
-                keys.process(prepopulated_unsigned_transaction, network_connection_config)
-                    .await
+                keys.process(
+                    prepopulated_unsigned_transaction,
+                    network_connection_config,
+                    output_format,
+                )
+                .await
             }
             SignTransaction::SignWithKeychain(chain) => {
                 chain
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
+                    .await
+            }
+            SignTransaction::SignWithWallet(wallet) => {
+                wallet
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
+                    .await
+            }
+            SignTransaction::SignWithExternalSigner(external_signer) => {
+                external_signer
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
             SignTransaction::SignManually(args_manually) => {
                 args_manually
-                    .process(prepopulated_unsigned_transaction)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
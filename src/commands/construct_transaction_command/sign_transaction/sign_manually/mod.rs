@@ -1,38 +1,90 @@
 use near_primitives::borsh::BorshSerialize;
+use std::convert::TryFrom;
 
 /// подписание сформированной транзакции в режиме manually
 #[derive(Debug, Default, clap::Clap)]
-pub struct CliSignManually {}
+pub struct CliSignManually {
+    /// Also save the unsigned transaction to this file (JSON envelope for `.json`, raw borsh otherwise)
+    #[clap(long)]
+    output: Option<std::path::PathBuf>,
+    /// Also show the unsigned transaction as a QR code (animated parts for large transactions)
+    #[clap(long)]
+    qr: bool,
+}
 
 #[derive(Debug)]
-pub struct SignManually {}
+pub struct SignManually {
+    output: Option<std::path::PathBuf>,
+    qr: bool,
+}
 
-impl From<CliSignManually> for SignManually {
-    fn from(_: CliSignManually) -> Self {
-        SignManually {}
+impl TryFrom<CliSignManually> for SignManually {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSignManually) -> color_eyre::eyre::Result<Self> {
+        Ok(SignManually {
+            output: item.output,
+            qr: item.qr,
+        })
     }
 }
 
 impl SignManually {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if let Some(output) = &self.output {
+            args.extend(vec!["--output".to_owned(), output.display().to_string()]);
+        }
+        if self.qr {
+            args.push("--qr".to_owned());
+        }
+        args
+    }
+
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
-        println!();
-        println!(
-            "Unsigned transaction:\n\n {:#?}",
-            &prepopulated_unsigned_transaction
-        );
-        println!();
+        if let Some(output) = &self.output {
+            crate::transaction_file::save_unsigned_transaction(
+                output,
+                network_connection_config.map(|connection_config| connection_config.name()),
+                &prepopulated_unsigned_transaction,
+            )?;
+        }
         let serialize_to_base64 = near_primitives::serialize::to_base64(
             prepopulated_unsigned_transaction
                 .try_to_vec()
                 .expect("Transaction is not expected to fail on serialization"),
         );
-        println!(
-            "---  serialize_to_base64:   --- \n   {:#?}",
-            &serialize_to_base64
-        );
+        match output_format {
+            crate::common::OutputFormat::Plaintext => {
+                println!();
+                println!(
+                    "Unsigned transaction:\n\n {:#?}",
+                    &prepopulated_unsigned_transaction
+                );
+                println!();
+                println!(
+                    "---  serialize_to_base64:   --- \n   {:#?}",
+                    &serialize_to_base64
+                );
+            }
+            crate::common::OutputFormat::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "transaction_hash": prepopulated_unsigned_transaction.get_hash_and_size().0,
+                        "serialized_transaction": serialize_to_base64,
+                    }))?
+                );
+            }
+        };
+        if self.qr {
+            crate::common::print_qr_code(&serialize_to_base64)?;
+        }
         Ok(())
     }
 }
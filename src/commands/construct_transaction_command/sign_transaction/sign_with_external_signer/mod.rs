@@ -0,0 +1,307 @@
+use dialoguer::Input;
+use near_primitives::borsh::BorshSerialize;
+use std::convert::TryFrom;
+
+/// The version of the JSON the external signer gets on stdin
+const EXTERNAL_SIGNER_PROTOCOL_VERSION: u32 = 1;
+
+/// подписание сформированной транзакции внешней программой
+#[derive(Debug, Default, clap::Clap)]
+pub struct CliSignExternalSigner {
+    /// The public key of the signer's access key that the external signer holds
+    #[clap(long)]
+    signer_public_key: Option<near_crypto::PublicKey>,
+    /// The signing program; `[external_signer]` of `~/.config/near-cli/config.toml` by default
+    #[clap(long)]
+    program: Option<String>,
+    #[clap(subcommand)]
+    submit: Option<super::sign_with_private_key::Submit>,
+}
+
+#[derive(Debug)]
+pub struct SignExternalSigner {
+    pub signer_public_key: near_crypto::PublicKey,
+    pub program: Option<String>,
+    pub submit: super::sign_with_private_key::Submit,
+}
+
+impl TryFrom<CliSignExternalSigner> for SignExternalSigner {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSignExternalSigner) -> color_eyre::eyre::Result<Self> {
+        let signer_public_key = match item.signer_public_key {
+            Some(signer_public_key) => signer_public_key,
+            None => SignExternalSigner::input_signer_public_key()?,
+        };
+        let submit = match item.submit {
+            Some(submit) => submit,
+            None => super::sign_with_private_key::Submit::choose_submit()?,
+        };
+        Ok(SignExternalSigner {
+            signer_public_key,
+            program: item.program,
+            submit,
+        })
+    }
+}
+
+/// What the external signer gets on stdin
+#[derive(Debug, serde::Serialize)]
+struct SignRequest {
+    version: u32,
+    /// `None` in the offline mode
+    network: Option<String>,
+    signer_id: String,
+    public_key: near_crypto::PublicKey,
+    receiver_id: String,
+    nonce: u64,
+    block_hash: near_primitives::hash::CryptoHash,
+    /// The hash of the borsh-serialized transaction, which is what gets signed
+    transaction_hash: near_primitives::hash::CryptoHash,
+    /// The borsh-serialized transaction in base64, for signers that check it themselves
+    transaction: String,
+    /// The actions in plain language, for signers that ask a person to approve them
+    actions: Vec<String>,
+}
+
+/// What the external signer prints on stdout
+#[derive(Debug, serde::Deserialize)]
+struct SignResponse {
+    signature: near_crypto::Signature,
+}
+
+impl SignExternalSigner {
+    fn input_signer_public_key() -> color_eyre::eyre::Result<near_crypto::PublicKey> {
+        crate::common::ensure_interactive(
+            "`--signer-public-key <SIGNER_PUBLIC_KEY>` of `sign-with-external-signer`",
+        )?;
+        Ok(Input::new()
+            .with_prompt("Enter the public key of the external signer")
+            .interact_text()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--signer-public-key".to_owned(),
+            self.signer_public_key.to_string(),
+        ];
+        if let Some(program) = &self.program {
+            args.extend(vec!["--program".to_owned(), program.clone()]);
+        }
+        args.extend(self.submit.to_cli_args());
+        args
+    }
+
+    /// `--program` (run without arguments) or the `[external_signer]` of the configuration file
+    fn command(&self) -> color_eyre::eyre::Result<std::process::Command> {
+        let (program, args) = match &self.program {
+            Some(program) => (program.clone(), vec![]),
+            None => {
+                let external_signer =
+                    crate::config::Config::load()?
+                        .external_signer
+                        .ok_or_else(|| {
+                            color_eyre::Report::msg(format!(
+                                "There is no `--program` and no `[external_signer]` in {}",
+                                crate::config::Config::path().display()
+                            ))
+                        })?;
+                (external_signer.program, external_signer.args)
+            }
+        };
+        let mut command = std::process::Command::new(program);
+        command
+            .args(args)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::inherit());
+        Ok(command)
+    }
+
+    pub async fn process(
+        self,
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::CliResult {
+        let command = self.command()?;
+        let network = network_connection_config
+            .as_ref()
+            .map(|connection_config| connection_config.name());
+        let public_key = self.signer_public_key.clone();
+        super::sign_with_private_key::sign_and_submit(
+            self.signer_public_key,
+            |unsigned_transaction| sign(command, network, &public_key, unsigned_transaction),
+            self.submit,
+            prepopulated_unsigned_transaction,
+            network_connection_config,
+            output_format,
+        )
+        .await
+    }
+}
+
+/// Runs the external signer with the `SignRequest` on stdin and checks the signature it returns
+fn sign(
+    mut command: std::process::Command,
+    network: Option<String>,
+    public_key: &near_crypto::PublicKey,
+    unsigned_transaction: &near_primitives::transaction::Transaction,
+) -> color_eyre::eyre::Result<near_crypto::Signature> {
+    let transaction_hash = unsigned_transaction.get_hash_and_size().0;
+    let request = SignRequest {
+        version: EXTERNAL_SIGNER_PROTOCOL_VERSION,
+        network,
+        signer_id: unsigned_transaction.signer_id.clone(),
+        public_key: public_key.clone(),
+        receiver_id: unsigned_transaction.receiver_id.clone(),
+        nonce: unsigned_transaction.nonce,
+        block_hash: unsigned_transaction.block_hash,
+        transaction_hash,
+        transaction: near_primitives::serialize::to_base64(
+            unsigned_transaction
+                .try_to_vec()
+                .expect("Transaction is not expected to fail on serialization"),
+        ),
+        actions: unsigned_transaction
+            .actions
+            .iter()
+            .map(crate::common::describe_action)
+            .collect(),
+    };
+    let mut child = command.spawn().map_err(|err| {
+        color_eyre::Report::msg(format!("Failed to run the external signer: {}", err))
+    })?;
+    {
+        let stdin = child
+            .stdin
+            .as_mut()
+            .expect("The stdin of the external signer is piped");
+        serde_json::to_writer(&mut *stdin, &request)?;
+        std::io::Write::write_all(stdin, b"\n")?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(color_eyre::Report::msg(format!(
+            "The external signer did not sign the transaction ({})",
+            output.status
+        )));
+    }
+    let response: SignResponse = serde_json::from_slice(&output.stdout).map_err(|err| {
+        color_eyre::Report::msg(format!(
+            "The external signer printed something other than {{\"signature\": \"...\"}}: {}",
+            err
+        ))
+    })?;
+    if !response
+        .signature
+        .verify(transaction_hash.as_ref(), public_key)
+    {
+        return Err(color_eyre::Report::msg(format!(
+            "The external signer returned a signature that is not made with the key {}",
+            public_key
+        )));
+    }
+    Ok(response.signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction(
+        public_key: near_crypto::PublicKey,
+    ) -> near_primitives::transaction::Transaction {
+        near_primitives::transaction::Transaction {
+            signer_id: "volodymyr.testnet".to_string(),
+            public_key,
+            nonce: 2,
+            receiver_id: "alice.testnet".to_string(),
+            block_hash: Default::default(),
+            actions: vec![near_primitives::transaction::Action::Transfer(
+                near_primitives::transaction::TransferAction { deposit: 1 },
+            )],
+        }
+    }
+
+    /// A signer that reads the request and prints `stdout`, or fails with `exit_code`
+    fn signer(stdout: &str, exit_code: i32) -> std::process::Command {
+        let mut command = std::process::Command::new("sh");
+        command
+            .arg("-c")
+            .arg(format!(
+                "cat > /dev/null; echo '{}'; exit {}",
+                stdout, exit_code
+            ))
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped());
+        command
+    }
+
+    fn signature_response(
+        secret_key: &near_crypto::SecretKey,
+        transaction: &near_primitives::transaction::Transaction,
+    ) -> String {
+        let signature = secret_key.sign(transaction.get_hash_and_size().0.as_ref());
+        serde_json::json!({ "signature": signature }).to_string()
+    }
+
+    #[test]
+    fn signature_of_the_external_signer_is_returned() {
+        let secret_key =
+            near_crypto::SecretKey::from_seed(near_crypto::KeyType::ED25519, "external_signer");
+        let transaction = transaction(secret_key.public_key());
+        let signature = sign(
+            signer(&signature_response(&secret_key, &transaction), 0),
+            Some("testnet".to_string()),
+            &secret_key.public_key(),
+            &transaction,
+        )
+        .unwrap();
+        assert!(signature.verify(
+            transaction.get_hash_and_size().0.as_ref(),
+            &secret_key.public_key()
+        ));
+    }
+
+    #[test]
+    fn failed_external_signer_is_an_error() {
+        let secret_key =
+            near_crypto::SecretKey::from_seed(near_crypto::KeyType::ED25519, "external_signer");
+        let transaction = transaction(secret_key.public_key());
+        let err = sign(
+            signer(&signature_response(&secret_key, &transaction), 3),
+            None,
+            &secret_key.public_key(),
+            &transaction,
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("The external signer did not sign the transaction"));
+    }
+
+    #[test]
+    fn signature_with_another_key_is_rejected() {
+        let secret_key =
+            near_crypto::SecretKey::from_seed(near_crypto::KeyType::ED25519, "external_signer");
+        let other_secret_key =
+            near_crypto::SecretKey::from_seed(near_crypto::KeyType::ED25519, "other");
+        let transaction = transaction(secret_key.public_key());
+        let err = sign(
+            signer(&signature_response(&other_secret_key, &transaction), 0),
+            None,
+            &secret_key.public_key(),
+            &transaction,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "The external signer returned a signature that is not made with the key {}",
+                secret_key.public_key()
+            )
+        );
+    }
+}
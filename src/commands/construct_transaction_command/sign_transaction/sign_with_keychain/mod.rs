@@ -1,54 +1,396 @@
-extern crate dirs;
-use serde::Deserialize;
-use std::path::PathBuf;
+use crate::key_store::{KeyChainNetwork, StoredKey};
+use std::convert::TryFrom;
 
 /// подписание сформированной транзакции с помощью файла с ключами
 #[derive(Debug, Default, clap::Clap)]
 pub struct CliSignKeychain {
+    /// Where the keys are kept: file (default) or secret-service
+    #[clap(long)]
+    key_store: Option<crate::key_store::KeyStoreKind>,
     #[clap(subcommand)]
     submit: Option<super::sign_with_private_key::Submit>,
 }
 
 #[derive(Debug)]
 pub struct SignKeychain {
-    pub submit: Option<super::sign_with_private_key::Submit>,
+    pub key_store: crate::key_store::KeyStoreKind,
+    pub submit: super::sign_with_private_key::Submit,
 }
 
-impl From<CliSignKeychain> for SignKeychain {
-    fn from(item: CliSignKeychain) -> Self {
-        SignKeychain {
-            submit: item.submit,
-        }
-    }
-}
+impl TryFrom<CliSignKeychain> for SignKeychain {
+    type Error = color_eyre::Report;
 
-#[derive(Debug, Deserialize)]
-struct User {
-    account_id: String,
-    public_key: near_crypto::PublicKey,
-    private_key: near_crypto::SecretKey,
+    fn try_from(item: CliSignKeychain) -> color_eyre::eyre::Result<Self> {
+        let submit = match item.submit {
+            Some(submit) => submit,
+            None => super::sign_with_private_key::Submit::choose_submit()?,
+        };
+        Ok(SignKeychain {
+            key_store: item.key_store.unwrap_or_default(),
+            submit,
+        })
+    }
 }
 
 impl SignKeychain {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if self.key_store != crate::key_store::KeyStoreKind::default() {
+            args.push("--key-store".to_owned());
+            args.push(<&str>::from(self.key_store).to_owned());
+        }
+        args.extend(self.submit.to_cli_args());
+        args
+    }
+
+    /// The network-specific keychain goes first, `default` is the fallback.
+    fn key_chain_networks(
+        network_connection_config: &Option<crate::common::ConnectionConfig>,
+    ) -> Vec<KeyChainNetwork> {
+        let mut key_chain_networks = vec![];
+        if let Some(network_connection_config) = network_connection_config {
+            key_chain_networks.push(KeyChainNetwork::from_connection_config(
+                network_connection_config,
+            ));
+        }
+        if !key_chain_networks.contains(&KeyChainNetwork::default_key_chain()) {
+            key_chain_networks.push(KeyChainNetwork::default_key_chain());
+        }
+        key_chain_networks
+    }
+
+    /// Collects every key pair of the signer (plaintext or encrypted), keychain by keychain.
+    fn find_candidates(
+        key_store: &dyn crate::key_store::KeyStore,
+        key_chain_networks: &[KeyChainNetwork],
+        signer_id: &str,
+    ) -> color_eyre::eyre::Result<Vec<StoredKey>> {
+        let mut candidates = vec![];
+        for key_chain_network in key_chain_networks {
+            candidates.extend(
+                key_store
+                    .find(key_chain_network, signer_id)?
+                    .into_iter()
+                    .map(|(_, stored_key)| stored_key),
+            );
+        }
+        Ok(candidates)
+    }
+
+    /// Picks a key that is an access key of the account; for a transaction, a function-call key
+    /// that permits it goes first, then a full access key
+    async fn select_on_chain_key(
+        candidates: Vec<StoredKey>,
+        signer_id: &str,
+        network_connection_config: &crate::common::ConnectionConfig,
+        transaction: Option<&near_primitives::transaction::Transaction>,
+    ) -> color_eyre::eyre::Result<Option<StoredKey>> {
+        let query_view_method_response = network_connection_config
+            .rpc_client()
+            .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
+                block_reference: near_primitives::types::Finality::Final.into(),
+                request: near_primitives::views::QueryRequest::ViewAccessKeyList {
+                    account_id: signer_id.to_string(),
+                },
+            })
+            .await
+            .map_err(|err| {
+                color_eyre::Report::msg(format!(
+                    "Failed to fetch query for view key list: {:?}",
+                    err
+                ))
+            })?;
+        let access_key_view =
+            if let near_jsonrpc_primitives::types::query::QueryResponseKind::AccessKeyList(result) =
+                query_view_method_response.kind
+            {
+                result
+            } else {
+                return Err(color_eyre::Report::msg(format!("Error call result")));
+            };
+        let on_chain_keys = Self::on_chain_keys(candidates, &access_key_view);
+        // A limited allowance has to cover the gas of the transaction, which takes a fee query
+        let max_gas_cost = match transaction {
+            Some(transaction)
+                if on_chain_keys.iter().any(|(_, permission)| {
+                    matches!(
+                        permission,
+                        near_primitives::views::AccessKeyPermissionView::FunctionCall {
+                            allowance: Some(_),
+                            ..
+                        }
+                    )
+                }) =>
+            {
+                super::cost_estimate::max_gas_cost(network_connection_config, transaction).await?
+            }
+            _ => 0,
+        };
+        Ok(Self::pick_on_chain_key(
+            on_chain_keys,
+            transaction,
+            max_gas_cost,
+        ))
+    }
+
+    /// The candidates that are access keys of the account, with their permissions
+    fn on_chain_keys(
+        candidates: Vec<StoredKey>,
+        access_key_list: &near_primitives::views::AccessKeyList,
+    ) -> Vec<(StoredKey, near_primitives::views::AccessKeyPermissionView)> {
+        candidates
+            .into_iter()
+            .filter_map(|candidate| {
+                access_key_list
+                    .keys
+                    .iter()
+                    .find(|access_key| access_key.public_key == candidate.public_key)
+                    .map(|access_key| (candidate, access_key.access_key.permission.clone()))
+            })
+            .collect()
+    }
+
+    fn pick_on_chain_key(
+        mut on_chain_keys: Vec<(StoredKey, near_primitives::views::AccessKeyPermissionView)>,
+        transaction: Option<&near_primitives::transaction::Transaction>,
+        max_gas_cost: near_primitives::types::Balance,
+    ) -> Option<StoredKey> {
+        let transaction = match transaction {
+            Some(transaction) => transaction,
+            None => {
+                return on_chain_keys
+                    .into_iter()
+                    .next()
+                    .map(|(candidate, _)| candidate)
+            }
+        };
+        on_chain_keys.retain(|(_, permission)| {
+            crate::common::access_key_permits(permission, transaction, max_gas_cost)
+        });
+        // A stable sort keeps the keychain order within function-call and full access keys
+        on_chain_keys.sort_by_key(|(_, permission)| {
+            matches!(
+                permission,
+                near_primitives::views::AccessKeyPermissionView::FullAccess
+            )
+        });
+        on_chain_keys
+            .into_iter()
+            .next()
+            .map(|(candidate, _)| candidate)
+    }
+
+    /// The unlocked key pair of the signer from the keychain; online, only a key that is
+    /// an access key of the account (and permits `transaction`, if given) qualifies
+    pub async fn find_signer_key(
+        key_store: &dyn crate::key_store::KeyStore,
+        signer_id: &str,
+        network_connection_config: &Option<crate::common::ConnectionConfig>,
+        transaction: Option<&near_primitives::transaction::Transaction>,
+    ) -> color_eyre::eyre::Result<StoredKey> {
+        Self::select_signer_key(key_store, signer_id, network_connection_config, transaction)
+            .await?
+            .unlock()
+    }
+
+    /// The same key as `find_signer_key`, still locked if it is encrypted
+    pub async fn select_signer_key(
+        key_store: &dyn crate::key_store::KeyStore,
+        signer_id: &str,
+        network_connection_config: &Option<crate::common::ConnectionConfig>,
+        transaction: Option<&near_primitives::transaction::Transaction>,
+    ) -> color_eyre::eyre::Result<StoredKey> {
+        let key_chain_networks = Self::key_chain_networks(network_connection_config);
+        let mut candidates = Self::find_candidates(key_store, &key_chain_networks, signer_id)?;
+        let mut searched_dirs = key_chain_networks
+            .iter()
+            .map(|key_chain_network| key_store.location(key_chain_network))
+            .collect::<Vec<String>>();
+        // The genesis accounts of a local node can sign without `login`
+        if let Some(crate::common::ConnectionConfig::Localnet) = network_connection_config {
+            let (location, validator_key) = crate::key_store::localnet_validator_key()?;
+            if let Some(validator_key) = validator_key {
+                if validator_key.account_id == signer_id {
+                    candidates.push(validator_key);
+                }
+            }
+            searched_dirs.push(location);
+        }
+        let searched_dirs = searched_dirs.join(", ");
+        if candidates.is_empty() {
+            return Err(color_eyre::Report::msg(format!(
+                "There are no access keys for <{}> in the keychain ({})",
+                signer_id, searched_dirs
+            )));
+        }
+        let stored_key = match network_connection_config {
+            Some(network_connection_config) => {
+                Self::select_on_chain_key(
+                    candidates,
+                    signer_id,
+                    network_connection_config,
+                    transaction,
+                )
+                .await?
+                .ok_or_else(|| {
+                    color_eyre::Report::msg(format!(
+                        "None of the keys found for <{}> in the keychain ({}) is an access key of this account{}",
+                        signer_id,
+                        searched_dirs,
+                        if transaction.is_some() {
+                            " that permits the transaction"
+                        } else {
+                            ""
+                        }
+                    ))
+                })?
+            }
+            None => candidates.into_iter().next().unwrap(),
+        };
+        Ok(stored_key)
+    }
+
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
-        let home_dir = dirs::home_dir().expect("Impossible to get your home dir!");
-        let file_name = format!("{}.json", prepopulated_unsigned_transaction.signer_id);
-        let mut path = PathBuf::from(&home_dir);
-        path.push(crate::consts::DIR_NAME_KEY_CHAIN);
-        path.push(file_name);
-        let data = std::fs::read_to_string(path).unwrap();
-        let account_json: User = serde_json::from_str(&data).unwrap();
+        let key_store = self.key_store.open()?;
+        let account_json = Self::find_signer_key(
+            &*key_store,
+            &prepopulated_unsigned_transaction.signer_id,
+            &network_connection_config,
+            Some(&prepopulated_unsigned_transaction),
+        )
+        .await?;
         let sign_with_private_key = super::sign_with_private_key::SignPrivateKey {
             signer_public_key: account_json.public_key,
-            signer_secret_key: account_json.private_key,
+            signer_secret_key: account_json.secret_key()?,
+            secret_key_source: crate::common::SecretKeySource::Argument,
             submit: self.submit.clone(),
         };
         sign_with_private_key
-            .process(prepopulated_unsigned_transaction, network_connection_config)
+            .process(
+                prepopulated_unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored_key() -> StoredKey {
+        let secret_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519);
+        StoredKey {
+            account_id: "volodymyr.testnet".to_string(),
+            public_key: secret_key.public_key(),
+            private_key: Some(secret_key),
+            master_seed_phrase: None,
+            seed_phrase_hd_path: None,
+            encrypted_secrets: None,
+        }
+    }
+
+    fn access_key(
+        stored_key: &StoredKey,
+        permission: near_primitives::views::AccessKeyPermissionView,
+    ) -> near_primitives::views::AccessKeyInfoView {
+        near_primitives::views::AccessKeyInfoView {
+            public_key: stored_key.public_key.clone(),
+            access_key: near_primitives::views::AccessKeyView {
+                nonce: 1,
+                permission,
+            },
+        }
+    }
+
+    fn call(receiver_id: &str, deposit: u128) -> near_primitives::transaction::Transaction {
+        near_primitives::transaction::Transaction {
+            signer_id: "volodymyr.testnet".to_string(),
+            public_key: near_crypto::PublicKey::empty(near_crypto::KeyType::ED25519),
+            nonce: 2,
+            receiver_id: receiver_id.to_string(),
+            block_hash: Default::default(),
+            actions: vec![near_primitives::transaction::Action::FunctionCall(
+                near_primitives::transaction::FunctionCallAction {
+                    method_name: "increment".to_string(),
+                    args: vec![],
+                    gas: 30_000_000_000_000,
+                    deposit,
+                },
+            )],
+        }
+    }
+
+    #[test]
+    fn function_call_key_is_preferred_for_a_call_it_permits() {
+        const MAX_GAS_COST: near_primitives::types::Balance = 3_000_000_000_000_000_000_000;
+        let full_access_key = stored_key();
+        let function_call_key = stored_key();
+        let limited_key = stored_key();
+        let unknown_key = stored_key();
+        let function_call_permission =
+            |allowance| near_primitives::views::AccessKeyPermissionView::FunctionCall {
+                allowance,
+                receiver_id: "counter.testnet".to_string(),
+                method_names: vec!["increment".to_string()],
+            };
+        let access_key_list = near_primitives::views::AccessKeyList {
+            keys: vec![
+                access_key(
+                    &full_access_key,
+                    near_primitives::views::AccessKeyPermissionView::FullAccess,
+                ),
+                access_key(&function_call_key, function_call_permission(None)),
+                access_key(
+                    &limited_key,
+                    function_call_permission(Some(MAX_GAS_COST - 1)),
+                ),
+            ],
+        };
+        // The keychain order: the full access key goes before the function-call keys
+        let on_chain_keys = || {
+            SignKeychain::on_chain_keys(
+                vec![
+                    unknown_key.clone(),
+                    full_access_key.clone(),
+                    limited_key.clone(),
+                    function_call_key.clone(),
+                ],
+                &access_key_list,
+            )
+        };
+        assert_eq!(on_chain_keys().len(), 3);
+
+        let pick = |transaction: Option<&near_primitives::transaction::Transaction>| {
+            SignKeychain::pick_on_chain_key(on_chain_keys(), transaction, MAX_GAS_COST)
+                .unwrap()
+                .public_key
+        };
+        assert_eq!(
+            pick(Some(&call("counter.testnet", 0))),
+            function_call_key.public_key,
+            "a zero-deposit call to the receiver of the function-call key, whose allowance is enough"
+        );
+        assert_eq!(
+            pick(Some(&call("counter.testnet", 1))),
+            full_access_key.public_key,
+            "a deposit needs a full access key"
+        );
+        assert_eq!(
+            pick(Some(&call("other.testnet", 0))),
+            full_access_key.public_key,
+            "another receiver needs a full access key"
+        );
+        assert_eq!(
+            pick(None),
+            full_access_key.public_key,
+            "without a transaction the keychain order is kept"
+        );
+    }
+}
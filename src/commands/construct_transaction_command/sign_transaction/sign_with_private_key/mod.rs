@@ -1,14 +1,20 @@
-use dialoguer::{theme::ColorfulTheme, Input, Select};
+use dialoguer::{theme::ColorfulTheme, Select};
 use near_primitives::borsh::BorshSerialize;
+use std::convert::TryFrom;
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 /// подписание сформированной транзакции с помощью личных ключей
 #[derive(Debug, Default, clap::Clap)]
 pub struct CliSignPrivateKey {
+    /// Only checked against the secret key, which the public key is derived from
     #[clap(long)]
     signer_public_key: Option<near_crypto::PublicKey>,
     #[clap(long)]
     signer_secret_key: Option<near_crypto::SecretKey>,
+    /// Read the secret key from a file (`-` for stdin) instead, so that it stays out of the
+    /// shell history; `NEAR_SIGNER_SECRET_KEY` is used when neither is given
+    #[clap(long, conflicts_with = "signer-secret-key")]
+    signer_secret_key_file: Option<crate::common::SecretKeyFile>,
     #[clap(subcommand)]
     submit: Option<Submit>,
 }
@@ -17,167 +23,161 @@ pub struct CliSignPrivateKey {
 pub struct SignPrivateKey {
     pub signer_public_key: near_crypto::PublicKey,
     pub signer_secret_key: near_crypto::SecretKey,
-    pub submit: Option<Submit>,
+    pub secret_key_source: crate::common::SecretKeySource,
+    pub submit: Submit,
 }
 
-impl From<CliSignPrivateKey> for SignPrivateKey {
-    fn from(item: CliSignPrivateKey) -> Self {
-        let signer_public_key: near_crypto::PublicKey = match item.signer_public_key {
-            Some(cli_public_key) => cli_public_key,
-            None => SignPrivateKey::signer_public_key(),
-        };
-        let signer_secret_key: near_crypto::SecretKey = match item.signer_secret_key {
-            Some(cli_secret_key) => cli_secret_key,
-            None => SignPrivateKey::signer_secret_key(),
-        };
+impl TryFrom<CliSignPrivateKey> for SignPrivateKey {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSignPrivateKey) -> color_eyre::eyre::Result<Self> {
+        let (signer_secret_key, secret_key_source) = crate::common::signer_secret_key(
+            item.signer_secret_key,
+            item.signer_secret_key_file,
+            "`--signer-secret-key <SIGNER_SECRET_KEY>`, `--signer-secret-key-file <PATH>` or `NEAR_SIGNER_SECRET_KEY` of `sign-private-key`",
+        )?;
         let submit: Option<Submit> = item.submit;
         let public_key_origin: near_crypto::PublicKey =
             near_crypto::SecretKey::public_key(&signer_secret_key);
-        if &signer_public_key == &public_key_origin {
-            Self {
-                signer_public_key,
-                signer_secret_key,
-                submit,
+        Ok(match item.signer_public_key {
+            Some(signer_public_key) if signer_public_key != public_key_origin => {
+                crate::common::ensure_interactive(
+                    "a `--signer-secret-key` that matches `--signer-public-key`",
+                )?;
+                println!("\nError: The key pair does not match. Re-enter the secret key.\n");
+                let signer_secret_key: near_crypto::SecretKey = Self::signer_secret_key();
+                Self::try_from(CliSignPrivateKey {
+                    signer_public_key: Some(signer_public_key),
+                    signer_secret_key: Some(signer_secret_key),
+                    signer_secret_key_file: None,
+                    submit,
+                })?
             }
-        } else {
-            println!("\nError: The key pair does not match. Re-enter the keys.\n");
-            let signer_public_key: near_crypto::PublicKey = Self::signer_public_key();
-            let signer_secret_key: near_crypto::SecretKey = Self::signer_secret_key();
-            Self::from(CliSignPrivateKey {
-                signer_public_key: Some(signer_public_key),
-                signer_secret_key: Some(signer_secret_key),
-                submit: None,
-            })
-        }
+            _ => {
+                let submit: Submit = match submit {
+                    Some(submit) => submit,
+                    None => Submit::choose_submit()?,
+                };
+                Self {
+                    signer_public_key: public_key_origin,
+                    signer_secret_key,
+                    secret_key_source,
+                    submit,
+                }
+            }
+        })
     }
 }
 
 impl SignPrivateKey {
-    pub fn signer_public_key() -> near_crypto::PublicKey {
-        Input::new()
-            .with_prompt("Enter sender's public key")
-            .interact_text()
-            .unwrap()
-    }
-
     pub fn signer_secret_key() -> near_crypto::SecretKey {
-        Input::new()
-            .with_prompt("Enter sender's private key")
-            .interact_text()
-            .unwrap()
+        crate::common::input_secret_key("Enter sender's private key (it is not shown)")
     }
 
-    fn rpc_client(self, selected_server_url: &str) -> near_jsonrpc_client::JsonRpcClient {
-        near_jsonrpc_client::new_client(&selected_server_url)
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = self.secret_key_source.to_cli_args(&self.signer_secret_key);
+        args.extend(self.submit.to_cli_args());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
-        let public_key: near_crypto::PublicKey = self.signer_public_key.clone();
         let signer_secret_key: near_crypto::SecretKey = self.signer_secret_key.clone();
-        let submit: Option<Submit> = self.submit.clone();
-        match network_connection_config {
-            None => {
-                let unsigned_transaction = near_primitives::transaction::Transaction {
-                    public_key,
-                    ..prepopulated_unsigned_transaction
-                };
-                let signature =
-                    signer_secret_key.sign(unsigned_transaction.get_hash_and_size().0.as_ref());
-                let signed_transaction = near_primitives::transaction::SignedTransaction::new(
-                    signature,
-                    unsigned_transaction,
-                );
-                let serialize_to_base64 = near_primitives::serialize::to_base64(
-                    signed_transaction
-                        .try_to_vec()
-                        .expect("Transaction is not expected to fail on serialization"),
-                );
-                println!(
-                    "\n\n---  Signed transaction:   ---\n    {:#?}",
-                    &signed_transaction
-                );
-                match submit {
-                    Some(submit) => submit.process_offline(signed_transaction, serialize_to_base64),
-                    None => {
-                        let submit = Submit::choose_submit();
-                        submit.process_offline(signed_transaction, serialize_to_base64)
-                    }
-                }
-            }
-            Some(network_connection_config) => {
-                let online_signer_access_key_response = self
-                    .rpc_client(network_connection_config.rpc_url().as_str())
-                    .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
-                        block_reference: near_primitives::types::Finality::Final.into(),
-                        request: near_primitives::views::QueryRequest::ViewAccessKey {
-                            account_id: prepopulated_unsigned_transaction.signer_id.clone(),
-                            public_key: public_key.clone(),
-                        },
-                    })
-                    .await
-                    .map_err(|err| {
-                        color_eyre::Report::msg(format!(
-                            "Failed to fetch public key information for nonce: {:?}",
-                            err
-                        ))
-                    })?;
-                let current_nonce =
-                    if let near_jsonrpc_primitives::types::query::QueryResponseKind::AccessKey(
-                        online_signer_access_key,
-                    ) = online_signer_access_key_response.kind
-                    {
-                        online_signer_access_key.nonce
-                    } else {
-                        return Err(color_eyre::Report::msg(format!("Error current_nonce")));
-                    };
-                let unsigned_transaction = near_primitives::transaction::Transaction {
-                    public_key,
-                    block_hash: online_signer_access_key_response.block_hash,
-                    nonce: current_nonce + 1,
-                    ..prepopulated_unsigned_transaction
+        sign_and_submit(
+            self.signer_public_key.clone(),
+            |unsigned_transaction| {
+                Ok(signer_secret_key.sign(unsigned_transaction.get_hash_and_size().0.as_ref()))
+            },
+            self.submit.clone(),
+            prepopulated_unsigned_transaction,
+            network_connection_config,
+            output_format,
+        )
+        .await
+    }
+}
+
+/// Fills in the public key (and, online, the nonce and block hash of the access key), signs the
+/// transaction with `sign` and passes it on to `submit`
+pub async fn sign_and_submit(
+    public_key: near_crypto::PublicKey,
+    sign: impl FnOnce(
+        &near_primitives::transaction::Transaction,
+    ) -> color_eyre::eyre::Result<near_crypto::Signature>,
+    submit: Submit,
+    prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+    network_connection_config: Option<crate::common::ConnectionConfig>,
+    output_format: crate::common::OutputFormat,
+) -> crate::CliResult {
+    let unsigned_transaction = match &network_connection_config {
+        None => near_primitives::transaction::Transaction {
+            public_key,
+            ..prepopulated_unsigned_transaction
+        },
+        Some(network_connection_config) => {
+            let online_signer_access_key_response = network_connection_config
+                .rpc_client()
+                .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
+                    block_reference: near_primitives::types::Finality::Final.into(),
+                    request: near_primitives::views::QueryRequest::ViewAccessKey {
+                        account_id: prepopulated_unsigned_transaction.signer_id.clone(),
+                        public_key: public_key.clone(),
+                    },
+                })
+                .await
+                .map_err(|err| {
+                    color_eyre::Report::msg(format!(
+                        "Failed to fetch public key information for nonce: {:?}",
+                        err
+                    ))
+                })?;
+            let current_nonce =
+                if let near_jsonrpc_primitives::types::query::QueryResponseKind::AccessKey(
+                    online_signer_access_key,
+                ) = online_signer_access_key_response.kind
+                {
+                    online_signer_access_key.nonce
+                } else {
+                    return Err(color_eyre::Report::msg(format!("Error current_nonce")));
                 };
-                let signature =
-                    signer_secret_key.sign(unsigned_transaction.get_hash_and_size().0.as_ref());
-                let signed_transaction = near_primitives::transaction::SignedTransaction::new(
-                    signature,
-                    unsigned_transaction,
-                );
-                let serialize_to_base64 = near_primitives::serialize::to_base64(
-                    signed_transaction
-                        .try_to_vec()
-                        .expect("Transaction is not expected to fail on serialization"),
-                );
-                println!(
-                    "\n\n---  Signed transaction:   ---\n    {:#?}",
-                    &signed_transaction
-                );
-                match submit {
-                    None => {
-                        let submit = Submit::choose_submit();
-                        submit
-                            .process_online(
-                                network_connection_config,
-                                signed_transaction,
-                                serialize_to_base64,
-                            )
-                            .await
-                    }
-                    Some(submit) => {
-                        submit
-                            .process_online(
-                                network_connection_config,
-                                signed_transaction,
-                                serialize_to_base64,
-                            )
-                            .await
-                    }
-                }
+            near_primitives::transaction::Transaction {
+                public_key,
+                block_hash: online_signer_access_key_response.block_hash,
+                nonce: current_nonce + 1,
+                ..prepopulated_unsigned_transaction
             }
         }
+    };
+    let signature = sign(&unsigned_transaction)?;
+    let signed_transaction =
+        near_primitives::transaction::SignedTransaction::new(signature, unsigned_transaction);
+    let serialize_to_base64 = near_primitives::serialize::to_base64(
+        signed_transaction
+            .try_to_vec()
+            .expect("Transaction is not expected to fail on serialization"),
+    );
+    if let crate::common::OutputFormat::Plaintext = output_format {
+        println!(
+            "\n\n---  Signed transaction:   ---\n    {:#?}",
+            &signed_transaction
+        );
+    }
+    match network_connection_config {
+        None => submit.process_offline(signed_transaction, serialize_to_base64, output_format),
+        Some(network_connection_config) => {
+            submit
+                .process_online(
+                    network_connection_config,
+                    signed_transaction,
+                    serialize_to_base64,
+                    output_format,
+                )
+                .await
+        }
     }
 }
 
@@ -187,15 +187,74 @@ pub enum Submit {
     #[strum_discriminants(strum(
         message = "Do you want send the transaction to the server (it's works only for online mode)"
     ))]
-    Send,
+    Send(crate::common::RetryPolicy),
+    #[strum_discriminants(strum(
+        message = "Do you want send the transaction without waiting for the result (prints the transaction hash)"
+    ))]
+    SendAsync(SendAsync),
     #[strum_discriminants(strum(message = "Do you want show the transaction on display?"))]
-    Display,
+    Display(DisplayTransaction),
+    #[strum_discriminants(strum(
+        message = "Do you want to estimate the cost of the transaction without sending it (it's works only for online mode)"
+    ))]
+    DryRun,
+}
+
+/// Print the signed transaction instead of sending it
+#[derive(Debug, Default, Clone, clap::Clap)]
+pub struct DisplayTransaction {
+    /// Also save the signed transaction to this file (JSON envelope for `.json`, raw borsh otherwise)
+    #[clap(long)]
+    pub output: Option<std::path::PathBuf>,
+}
+
+impl DisplayTransaction {
+    fn process(
+        self,
+        network: Option<String>,
+        signed_transaction: near_primitives::transaction::SignedTransaction,
+        serialize_to_base64: String,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::CliResult {
+        if let Some(output) = &self.output {
+            crate::transaction_file::save_signed_transaction(output, network, &signed_transaction)?;
+        }
+        display_signed_transaction(signed_transaction, serialize_to_base64, output_format)
+    }
+}
+
+/// Send the transaction with `broadcast_tx_async` and print its hash right away
+#[derive(Debug, Default, Clone, clap::Clap)]
+pub struct SendAsync {
+    /// Poll the transaction status until it is included in a block or executed
+    #[clap(long)]
+    pub wait_until: Option<WaitUntil>,
+    #[clap(flatten)]
+    pub retry_policy: crate::common::RetryPolicy,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    strum_macros::IntoStaticStr,
+    strum_macros::EnumString,
+    strum_macros::EnumVariantNames,
+)]
+#[strum(serialize_all = "kebab-case")]
+pub enum WaitUntil {
+    /// The transaction has an outcome, its receipts may still be executing
+    Included,
+    /// The transaction and all its receipts are executed
+    Executed,
 }
 
 impl Submit {
-    pub fn choose_submit() -> Self {
+    pub fn choose_submit() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = SubmitDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let submits = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -206,9 +265,38 @@ impl Submit {
             .default(0)
             .interact()
             .unwrap();
-        match variants[select_submit] {
-            SubmitDiscriminants::Send => Submit::Send,
-            SubmitDiscriminants::Display => Submit::Display,
+        Ok(match variants[select_submit] {
+            SubmitDiscriminants::Send => Submit::Send(Default::default()),
+            SubmitDiscriminants::SendAsync => Submit::SendAsync(Default::default()),
+            SubmitDiscriminants::Display => Submit::Display(Default::default()),
+            SubmitDiscriminants::DryRun => Submit::DryRun,
+        })
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        match self {
+            Submit::Send(retry_policy) => {
+                let mut args = vec!["send".to_owned()];
+                args.extend(retry_policy.to_cli_args());
+                args
+            }
+            Submit::SendAsync(send_async) => {
+                let mut args = vec!["send-async".to_owned()];
+                if let Some(wait_until) = send_async.wait_until {
+                    args.push("--wait-until".to_owned());
+                    args.push(<&str>::from(wait_until).to_owned());
+                }
+                args.extend(send_async.retry_policy.to_cli_args());
+                args
+            }
+            Submit::Display(display) => {
+                let mut args = vec!["display".to_owned()];
+                if let Some(output) = &display.output {
+                    args.extend(vec!["--output".to_owned(), output.display().to_string()]);
+                }
+                args
+            }
+            Submit::DryRun => vec!["dry-run".to_owned()],
         }
     }
 
@@ -216,17 +304,17 @@ impl Submit {
         self,
         signed_transaction: near_primitives::transaction::SignedTransaction,
         serialize_to_base64: String,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
-        println!("\n\n\n===========  DISPLAY  ==========");
-        println!(
-            "\n\n---  Signed transaction:   ---\n    {:#?}",
-            &signed_transaction
-        );
-        println!(
-            "\n\n---  serialize_to_base64:   --- \n   {:#?}",
-            &serialize_to_base64
-        );
-        Ok(())
+        match self {
+            Submit::Display(display) => {
+                display.process(None, signed_transaction, serialize_to_base64, output_format)
+            }
+            Submit::DryRun => Err(color_eyre::Report::msg(
+                "The cost of a transaction can only be estimated in the online mode",
+            )),
+            _ => display_signed_transaction(signed_transaction, serialize_to_base64, output_format),
+        }
     }
 
     pub async fn process_online(
@@ -234,60 +322,487 @@ impl Submit {
         network_connection_config: crate::common::ConnectionConfig,
         signed_transaction: near_primitives::transaction::SignedTransaction,
         serialize_to_base64: String,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
+        if matches!(self, Submit::Send(_) | Submit::SendAsync(_)) {
+            super::confirmation::confirm_sending(
+                &network_connection_config,
+                &signed_transaction.transaction,
+            )
+            .await?;
+        }
         match self {
-            Submit::Send => {
-                println!("\n\n\n========= SENT =========");
-                println!(
-                    "\n\n---  Signed transaction:   ---\n    {:#?}",
-                    &signed_transaction
-                );
-                println!(
-                    "\n\n---  serialize_to_base64:   --- \n   {:#?}",
-                    &serialize_to_base64
-                );
-                let json_rcp_client =
-                    near_jsonrpc_client::new_client(network_connection_config.rpc_url().as_str());
-                let transaction_info = loop {
-                    let transaction_info_result = json_rcp_client
-                        .broadcast_tx_commit(near_primitives::serialize::to_base64(
-                            signed_transaction
-                                .try_to_vec()
-                                .expect("Transaction is not expected to fail on serialization"),
-                        ))
-                        .await;
-                    match transaction_info_result {
-                        Ok(response) => {
-                            break response;
-                        }
-                        Err(err) => {
-                            if let Some(serde_json::Value::String(data)) = &err.data {
-                                if data.contains("Timeout") {
-                                    println!("Error transaction: {:?}", err);
-                                    continue;
-                                }
+            Submit::Send(retry_policy) => {
+                if let crate::common::OutputFormat::Plaintext = output_format {
+                    println!("\n\n\n========= SENT =========");
+                    println!(
+                        "\n\n---  Signed transaction:   ---\n    {:#?}",
+                        &signed_transaction
+                    );
+                    println!(
+                        "\n\n---  serialize_to_base64:   --- \n   {:#?}",
+                        &serialize_to_base64
+                    );
+                }
+                let transaction_info = broadcast_tx_commit(
+                    &network_connection_config,
+                    &signed_transaction,
+                    &retry_policy,
+                )
+                .await?;
+                match output_format {
+                    crate::common::OutputFormat::Plaintext => {
+                        println!("\n\n---  Success:  ---\n {:#?}", &transaction_info);
+                    }
+                    crate::common::OutputFormat::Json => {
+                        println!(
+                            "{}",
+                            serde_json::to_string_pretty(&transaction_outcome_to_json(
+                                &transaction_info
+                            ))?
+                        );
+                    }
+                };
+            }
+            Submit::SendAsync(send_async) => {
+                let transaction_hash = network_connection_config
+                    .rpc_client()
+                    .broadcast_tx_async(near_primitives::serialize::to_base64(
+                        signed_transaction
+                            .try_to_vec()
+                            .expect("Transaction is not expected to fail on serialization"),
+                    ))
+                    .await
+                    .map_err(|err| {
+                        color_eyre::Report::msg(format!("Error transaction: {:?}", err))
+                    })?;
+                let wait_until = match send_async.wait_until {
+                    Some(wait_until) => wait_until,
+                    None => {
+                        match output_format {
+                            crate::common::OutputFormat::Plaintext => {
+                                println!("Transaction hash: {}", transaction_hash);
+                            }
+                            crate::common::OutputFormat::Json => {
+                                println!(
+                                    "{}",
+                                    serde_json::to_string_pretty(&serde_json::json!({
+                                        "transaction_hash": transaction_hash,
+                                    }))?
+                                );
                             }
-                            return Err(color_eyre::Report::msg(format!(
-                                "Error transaction: {:?}",
-                                err
-                            )));
-                        }
-                    };
+                        };
+                        return Ok(());
+                    }
                 };
-                println!("\n\n---  Success:  ---\n {:#?}", &transaction_info);
+                eprintln!("Transaction hash: {}", transaction_hash);
+                let transaction_info = wait_for_transaction(
+                    &network_connection_config,
+                    transaction_hash,
+                    signed_transaction.transaction.signer_id.clone(),
+                    wait_until,
+                    &send_async.retry_policy,
+                )
+                .await?;
+                match output_format {
+                    crate::common::OutputFormat::Plaintext => {
+                        crate::commands::view_command::view_transaction_status::signer::display_transaction_status(
+                            &transaction_info,
+                            output_format,
+                        )?;
+                    }
+                    crate::common::OutputFormat::Json => {
+                        println!(
+                            "{}",
+                            serde_json::to_string_pretty(&transaction_outcome_to_json(
+                                &transaction_info
+                            ))?
+                        );
+                    }
+                };
+            }
+            Submit::Display(display) => {
+                display.process(
+                    Some(network_connection_config.name()),
+                    signed_transaction,
+                    serialize_to_base64,
+                    output_format,
+                )?;
             }
-            Submit::Display => {
-                println!("\n\n\n===========  DISPLAY  ==========");
-                println!(
-                    "\n\n---  Signed transaction:   ---\n {:#?}",
-                    &signed_transaction
-                );
-                println!(
-                    "\n\n---  serialize_to_base64:   --- \n {:#?}",
-                    &serialize_to_base64
-                );
+            Submit::DryRun => {
+                let cost_estimate = super::cost_estimate::estimate(
+                    &network_connection_config,
+                    &signed_transaction.transaction,
+                )
+                .await?;
+                super::cost_estimate::display_cost_estimate(&cost_estimate, output_format)?;
             }
         }
         Ok(())
     }
 }
+
+/// An RPC error that means the outcome is not known yet, so it is worth asking again:
+/// the node timed out waiting for the outcome or has not seen the transaction yet
+fn is_pending_error(err: &near_jsonrpc_primitives::errors::RpcError) -> bool {
+    match &err.error_struct {
+        Some(near_jsonrpc_primitives::errors::RpcErrorKind::HandlerError(cause)) => matches!(
+            cause.get("name").and_then(|name| name.as_str()),
+            Some("TIMEOUT_ERROR") | Some("UNKNOWN_TRANSACTION")
+        ),
+        _ => false,
+    }
+}
+
+fn unknown_outcome_error(transaction_hash: &str, reason: String) -> color_eyre::Report {
+    color_eyre::Report::msg(format!(
+        "The outcome of the transaction {} is unknown: {}. It may still be executed; check it later with `view transaction-status`.",
+        transaction_hash, reason
+    ))
+}
+
+/// Broadcasts the transaction once and, if the server times out, polls its status by hash
+/// (with exponential backoff) instead of sending it again.
+pub async fn broadcast_tx_commit(
+    network_connection_config: &crate::common::ConnectionConfig,
+    signed_transaction: &near_primitives::transaction::SignedTransaction,
+    retry_policy: &crate::common::RetryPolicy,
+) -> color_eyre::eyre::Result<near_primitives::views::FinalExecutionOutcomeView> {
+    let json_rcp_client = network_connection_config.rpc_client();
+    let transaction_hash = signed_transaction.get_hash().to_string();
+    let signer_id = signed_transaction.transaction.signer_id.clone();
+    let deadline = tokio::time::Instant::now() + retry_policy.deadline();
+
+    let mut attempt = 1;
+    let mut response = tokio::time::timeout_at(
+        deadline,
+        json_rcp_client.broadcast_tx_commit(near_primitives::serialize::to_base64(
+            signed_transaction
+                .try_to_vec()
+                .expect("Transaction is not expected to fail on serialization"),
+        )),
+    )
+    .await;
+    loop {
+        match response {
+            Ok(Ok(transaction_info)) => return Ok(transaction_info),
+            Ok(Err(err)) if is_pending_error(&err) => {
+                eprintln!("The outcome is not available yet: {:?}", err)
+            }
+            Ok(Err(err)) => {
+                return Err(color_eyre::Report::msg(format!(
+                    "Error transaction: {:?}",
+                    err
+                )))
+            }
+            Err(_) => {}
+        };
+        if attempt >= retry_policy.max_attempts {
+            return Err(unknown_outcome_error(
+                &transaction_hash,
+                format!("no result after {} attempts", attempt),
+            ));
+        }
+        let now = tokio::time::Instant::now();
+        if now >= deadline {
+            return Err(unknown_outcome_error(
+                &transaction_hash,
+                format!("no result within {} seconds", retry_policy.deadline_secs),
+            ));
+        }
+        tokio::time::sleep(std::cmp::min(retry_policy.backoff(attempt), deadline - now)).await;
+        attempt += 1;
+        eprintln!(
+            "Checking the status of the transaction {} (attempt {} of {})",
+            transaction_hash, attempt, retry_policy.max_attempts
+        );
+        response = tokio::time::timeout_at(
+            deadline,
+            json_rcp_client.tx(transaction_hash.clone(), signer_id.clone()),
+        )
+        .await;
+    }
+}
+
+/// Polls the status of a transaction sent with `broadcast_tx_async` (with exponential backoff)
+/// until it reaches the requested stage.
+pub async fn wait_for_transaction(
+    network_connection_config: &crate::common::ConnectionConfig,
+    transaction_hash: String,
+    signer_id: String,
+    wait_until: WaitUntil,
+    retry_policy: &crate::common::RetryPolicy,
+) -> color_eyre::eyre::Result<near_primitives::views::FinalExecutionOutcomeView> {
+    let json_rcp_client = network_connection_config.rpc_client();
+    let deadline = tokio::time::Instant::now() + retry_policy.deadline();
+    let mut attempt = 0;
+    loop {
+        if attempt >= retry_policy.max_attempts {
+            return Err(unknown_outcome_error(
+                &transaction_hash,
+                format!(
+                    "not {} after {} attempts",
+                    <&str>::from(wait_until),
+                    attempt
+                ),
+            ));
+        }
+        let now = tokio::time::Instant::now();
+        if now >= deadline {
+            return Err(unknown_outcome_error(
+                &transaction_hash,
+                format!(
+                    "not {} within {} seconds",
+                    <&str>::from(wait_until),
+                    retry_policy.deadline_secs
+                ),
+            ));
+        }
+        tokio::time::sleep(std::cmp::min(
+            retry_policy.backoff(attempt + 1),
+            deadline - now,
+        ))
+        .await;
+        attempt += 1;
+        let response = tokio::time::timeout_at(
+            deadline,
+            crate::commands::view_command::view_transaction_status::signer::fetch_transaction_status(
+                &json_rcp_client,
+                transaction_hash.clone(),
+                signer_id.clone(),
+            ),
+        )
+        .await;
+        match response {
+            Ok(Ok(transaction_info)) => {
+                let is_executed = match transaction_info.status {
+                    near_primitives::views::FinalExecutionStatus::SuccessValue(_)
+                    | near_primitives::views::FinalExecutionStatus::Failure(_) => true,
+                    near_primitives::views::FinalExecutionStatus::NotStarted
+                    | near_primitives::views::FinalExecutionStatus::Started => false,
+                };
+                if is_executed || wait_until == WaitUntil::Included {
+                    return Ok(transaction_info);
+                }
+            }
+            Ok(Err(err)) if is_pending_error(&err) => {}
+            Ok(Err(err)) => {
+                return Err(color_eyre::Report::msg(format!(
+                    "Failed to fetch query for view transaction: {:?}",
+                    err
+                )))
+            }
+            Err(_) => {}
+        };
+        eprintln!(
+            "The transaction {} is not {} yet (attempt {} of {})",
+            transaction_hash,
+            <&str>::from(wait_until),
+            attempt,
+            retry_policy.max_attempts
+        );
+    }
+}
+
+fn display_signed_transaction(
+    signed_transaction: near_primitives::transaction::SignedTransaction,
+    serialize_to_base64: String,
+    output_format: crate::common::OutputFormat,
+) -> crate::CliResult {
+    match output_format {
+        crate::common::OutputFormat::Plaintext => {
+            println!("\n\n\n===========  DISPLAY  ==========");
+            println!(
+                "\n\n---  Signed transaction:   ---\n {:#?}",
+                &signed_transaction
+            );
+            println!(
+                "\n\n---  serialize_to_base64:   --- \n {:#?}",
+                &serialize_to_base64
+            );
+        }
+        crate::common::OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "transaction_hash": signed_transaction.get_hash(),
+                    "signed_transaction": near_primitives::views::SignedTransactionView::from(
+                        signed_transaction
+                    ),
+                    "serialized_transaction": serialize_to_base64,
+                }))?
+            );
+        }
+    };
+    Ok(())
+}
+
+/// Collects the fields of a final execution outcome that scripts usually assert on;
+/// receipt outcomes are included as returned by RPC.
+pub fn transaction_outcome_to_json(
+    transaction_info: &near_primitives::views::FinalExecutionOutcomeView,
+) -> serde_json::Value {
+    let outcomes = std::iter::once(&transaction_info.transaction_outcome)
+        .chain(transaction_info.receipts_outcome.iter())
+        .collect::<Vec<_>>();
+    let gas_burnt: near_primitives::types::Gas = outcomes
+        .iter()
+        .map(|outcome| outcome.outcome.gas_burnt)
+        .sum();
+    let tokens_burnt: near_primitives::types::Balance = outcomes
+        .iter()
+        .map(|outcome| outcome.outcome.tokens_burnt)
+        .sum();
+    let logs = outcomes
+        .iter()
+        .flat_map(|outcome| outcome.outcome.logs.iter().cloned())
+        .collect::<Vec<String>>();
+    let success_value = match &transaction_info.status {
+        near_primitives::views::FinalExecutionStatus::SuccessValue(value) => {
+            decode_success_value(value)
+        }
+        _ => serde_json::Value::Null,
+    };
+    serde_json::json!({
+        "transaction_hash": transaction_info.transaction.hash,
+        "signer_id": transaction_info.transaction.signer_id,
+        "receiver_id": transaction_info.transaction.receiver_id,
+        "status": transaction_info.status,
+        "success_value": success_value,
+        "gas_burnt": gas_burnt,
+        "tokens_burnt": tokens_burnt.to_string(),
+        "logs": logs,
+        "receipts_outcome": transaction_info.receipts_outcome,
+    })
+}
+
+/// `SuccessValue` is base64-encoded. Contracts usually return JSON, so try it first,
+/// then a UTF-8 string, and fall back to the raw base64 value.
+fn decode_success_value(value: &str) -> serde_json::Value {
+    match near_primitives::serialize::from_base64(value) {
+        Ok(bytes) if bytes.is_empty() => serde_json::Value::Null,
+        Ok(bytes) => {
+            if let Ok(json_value) = serde_json::from_slice(&bytes) {
+                json_value
+            } else if let Ok(string_value) = String::from_utf8(bytes) {
+                serde_json::Value::String(string_value)
+            } else {
+                serde_json::Value::String(value.to_owned())
+            }
+        }
+        Err(_) => serde_json::Value::String(value.to_owned()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An outcome the way the RPC returns it: a transaction and one receipt
+    fn transaction_info(success_value: &[u8]) -> near_primitives::views::FinalExecutionOutcomeView {
+        let outcome = |logs: Vec<&str>, gas_burnt: u64, tokens_burnt: u128| {
+            serde_json::json!({
+                "proof": [],
+                "block_hash": near_primitives::hash::CryptoHash::default(),
+                "id": near_primitives::hash::CryptoHash::default(),
+                "outcome": {
+                    "logs": logs,
+                    "receipt_ids": [],
+                    "gas_burnt": gas_burnt,
+                    "tokens_burnt": tokens_burnt.to_string(),
+                    "executor_id": "counter.testnet",
+                    "status": { "SuccessValue": "" },
+                    "metadata": { "version": 1, "gas_profile": null },
+                },
+            })
+        };
+        serde_json::from_value(serde_json::json!({
+            "status": {
+                "SuccessValue": near_primitives::serialize::to_base64(success_value)
+            },
+            "transaction": {
+                "signer_id": "volodymyr.testnet",
+                "public_key": near_crypto::PublicKey::empty(near_crypto::KeyType::ED25519),
+                "nonce": 1,
+                "receiver_id": "counter.testnet",
+                "actions": [],
+                "signature": near_crypto::Signature::empty(near_crypto::KeyType::ED25519),
+                "hash": near_primitives::hash::CryptoHash::default(),
+            },
+            "transaction_outcome": outcome(vec![], 2_000, 200),
+            "receipts_outcome": [outcome(vec!["incremented", "done"], 3_000, 300)],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn transaction_outcome_to_json_shape() {
+        let json = transaction_outcome_to_json(&transaction_info(br#"{"count":2}"#));
+        assert_eq!(
+            json["transaction_hash"],
+            serde_json::json!(near_primitives::hash::CryptoHash::default())
+        );
+        assert_eq!(json["signer_id"], "volodymyr.testnet");
+        assert_eq!(json["receiver_id"], "counter.testnet");
+        assert_eq!(json["success_value"], serde_json::json!({ "count": 2 }));
+        assert_eq!(json["gas_burnt"], 5_000);
+        assert_eq!(json["tokens_burnt"], "500");
+        assert_eq!(json["logs"], serde_json::json!(["incremented", "done"]));
+        assert_eq!(json["receipts_outcome"].as_array().unwrap().len(), 1);
+        assert!(json["status"].get("SuccessValue").is_some());
+    }
+
+    #[test]
+    fn decode_success_value_json_string_or_base64() {
+        let base64 = |bytes: &[u8]| near_primitives::serialize::to_base64(bytes);
+        assert_eq!(
+            decode_success_value(&base64(br#"[1, "two"]"#)),
+            serde_json::json!([1, "two"])
+        );
+        assert_eq!(
+            decode_success_value(&base64(b"plain text")),
+            serde_json::json!("plain text")
+        );
+        let binary = base64(&[0xff, 0xfe, 0x00]);
+        assert_eq!(decode_success_value(&binary), serde_json::json!(binary));
+        assert_eq!(decode_success_value(""), serde_json::Value::Null);
+        assert_eq!(
+            decode_success_value("not base64!"),
+            serde_json::json!("not base64!")
+        );
+    }
+
+    fn rpc_error(error: serde_json::Value) -> near_jsonrpc_primitives::errors::RpcError {
+        serde_json::from_value(error).unwrap()
+    }
+
+    #[test]
+    fn pending_errors() {
+        for name in &["TIMEOUT_ERROR", "UNKNOWN_TRANSACTION"] {
+            assert!(is_pending_error(&rpc_error(serde_json::json!({
+                "code": -32000,
+                "message": "Server error",
+                "name": "HANDLER_ERROR",
+                "cause": { "name": name, "info": {} },
+            }))));
+        }
+        assert!(!is_pending_error(&rpc_error(serde_json::json!({
+            "code": -32000,
+            "message": "Server error",
+            "name": "HANDLER_ERROR",
+            "cause": { "name": "INVALID_TRANSACTION", "info": {} },
+        }))));
+        assert!(!is_pending_error(&rpc_error(serde_json::json!({
+            "code": -32000,
+            "message": "Server error",
+            "name": "INTERNAL_ERROR",
+            "cause": { "name": "INTERNAL_ERROR", "info": { "error_message": "TIMEOUT_ERROR" } },
+        }))));
+        // The text of an error without the structured part does not count
+        assert!(!is_pending_error(&rpc_error(serde_json::json!({
+            "code": -32000,
+            "message": "Server error",
+            "data": "Timeout",
+        }))));
+    }
+}
@@ -0,0 +1,233 @@
+use near_primitives::borsh::BorshSerialize;
+use std::convert::TryFrom;
+use url_open::UrlOpen;
+
+/// подписание сформированной транзакции в веб-кошельке
+#[derive(Debug, Default, clap::Clap)]
+pub struct CliSignWallet {
+    /// How long to wait for the wallet to redirect back with the transaction hashes
+    /// (300 seconds by default)
+    #[clap(long)]
+    callback_timeout_secs: Option<u64>,
+}
+
+#[derive(Debug)]
+pub struct SignWallet {
+    pub callback_timeout_secs: u64,
+}
+
+impl TryFrom<CliSignWallet> for SignWallet {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSignWallet) -> color_eyre::eyre::Result<Self> {
+        Ok(SignWallet {
+            callback_timeout_secs: item
+                .callback_timeout_secs
+                .unwrap_or(crate::wallet_callback::DEFAULT_CALLBACK_TIMEOUT_SECS),
+        })
+    }
+}
+
+impl SignWallet {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if self.callback_timeout_secs != crate::wallet_callback::DEFAULT_CALLBACK_TIMEOUT_SECS {
+            args.push("--callback-timeout-secs".to_owned());
+            args.push(self.callback_timeout_secs.to_string());
+        }
+        args
+    }
+
+    pub async fn process(
+        self,
+        prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::CliResult {
+        let network_connection_config = network_connection_config.ok_or_else(|| {
+            color_eyre::Report::msg(
+                "The wallet sends the transaction itself, so `sign-with-wallet` needs the online mode",
+            )
+        })?;
+        let mut url: url::Url = network_connection_config
+            .wallet_url()
+            .ok_or_else(|| {
+                color_eyre::Report::msg(
+                    "There is no wallet for this network, sign the transaction with a key instead",
+                )
+            })?
+            .join("sign")?;
+        let callback_listener =
+            crate::wallet_callback::CallbackListener::start().map_err(|err| {
+                color_eyre::Report::msg(format!(
+                    "Failed to listen for the wallet redirect: {}",
+                    err
+                ))
+            })?;
+        // The wallet fills in its own public key, nonce and block hash before signing
+        url.query_pairs_mut()
+            .append_pair(
+                "transactions",
+                &near_primitives::serialize::to_base64(
+                    prepopulated_unsigned_transaction
+                        .try_to_vec()
+                        .expect("Transaction is not expected to fail on serialization"),
+                ),
+            )
+            .append_pair("callbackUrl", callback_listener.success_url().as_str());
+        println!(
+            "If your browser doesn't automatically open, please visit this URL:\n {}\n",
+            &url.as_str()
+        );
+        url.open();
+
+        let transaction_hashes = transaction_hashes(
+            callback_listener
+                .wait(std::time::Duration::from_secs(self.callback_timeout_secs))
+                .await,
+            self.callback_timeout_secs,
+        )?;
+        for transaction_hash in transaction_hashes {
+            let transaction_info =
+                crate::commands::view_command::view_transaction_status::signer::fetch_transaction_status(
+                    &network_connection_config.rpc_client(),
+                    transaction_hash.to_string(),
+                    prepopulated_unsigned_transaction.signer_id.clone(),
+                )
+                .await
+                .map_err(|err| {
+                    color_eyre::Report::msg(format!(
+                        "Failed to fetch query for view transaction: {:?}",
+                        err
+                    ))
+                })?;
+            crate::commands::view_command::view_transaction_status::signer::display_transaction_status(
+                &transaction_info,
+                output_format,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The hashes of the transactions the wallet signed and sent, from the `transactionHashes`
+/// (comma-separated) of its redirect
+fn transaction_hashes(
+    redirect: Option<crate::wallet_callback::WalletRedirect>,
+    callback_timeout_secs: u64,
+) -> color_eyre::eyre::Result<Vec<near_primitives::hash::CryptoHash>> {
+    let transaction_hashes = match redirect {
+        Some(crate::wallet_callback::WalletRedirect::Approved(query)) => query
+            .get("transactionHashes")
+            .map(|transaction_hashes| {
+                transaction_hashes
+                    .split(',')
+                    .filter(|transaction_hash| !transaction_hash.is_empty())
+                    .map(|transaction_hash| {
+                        transaction_hash
+                            .parse::<crate::common::BlockHashAsBase58>()
+                            .map(|transaction_hash| transaction_hash.inner)
+                            .map_err(|err| {
+                                color_eyre::Report::msg(format!(
+                                    "The wallet redirected back with the invalid transaction hash {:?}: {}",
+                                    transaction_hash, err
+                                ))
+                            })
+                    })
+                    .collect::<color_eyre::eyre::Result<Vec<_>>>()
+            })
+            .transpose()?
+            .unwrap_or_default(),
+        Some(crate::wallet_callback::WalletRedirect::Rejected(reason)) => {
+            return Err(color_eyre::Report::msg(format!(
+                "The wallet did not sign the transaction: {}",
+                reason
+            )));
+        }
+        None => {
+            return Err(color_eyre::Report::msg(format!(
+                "The wallet did not redirect back in {} seconds; if the transaction was signed, look it up with `view transaction-status`",
+                callback_timeout_secs
+            )));
+        }
+    };
+    if transaction_hashes.is_empty() {
+        return Err(color_eyre::Report::msg(
+            "The wallet redirected back without `transactionHashes`",
+        ));
+    }
+    Ok(transaction_hashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approved(transaction_hashes: &str) -> Option<crate::wallet_callback::WalletRedirect> {
+        Some(crate::wallet_callback::WalletRedirect::Approved(
+            vec![(
+                "transactionHashes".to_string(),
+                transaction_hashes.to_string(),
+            )]
+            .into_iter()
+            .collect(),
+        ))
+    }
+
+    #[test]
+    fn comma_separated_transaction_hashes_are_parsed() {
+        let first = near_primitives::hash::hash(b"first");
+        let second = near_primitives::hash::hash(b"second");
+        assert_eq!(
+            transaction_hashes(approved(&format!("{},{},", first, second)), 300).unwrap(),
+            vec![first, second]
+        );
+        assert_eq!(
+            transaction_hashes(approved(&first.to_string()), 300).unwrap(),
+            vec![first]
+        );
+    }
+
+    #[test]
+    fn redirect_without_transaction_hashes_is_an_error() {
+        assert!(transaction_hashes(approved(""), 300).is_err());
+        assert!(transaction_hashes(
+            Some(crate::wallet_callback::WalletRedirect::Approved(
+                Default::default()
+            )),
+            300
+        )
+        .is_err());
+        assert!(transaction_hashes(None, 300).is_err());
+    }
+
+    #[test]
+    fn invalid_transaction_hash_is_an_error() {
+        let err = transaction_hashes(
+            approved(&format!(
+                "{},not-a-hash",
+                near_primitives::hash::hash(b"first")
+            )),
+            300,
+        )
+        .unwrap_err();
+        assert!(err.to_string().starts_with(
+            "The wallet redirected back with the invalid transaction hash \"not-a-hash\""
+        ));
+    }
+
+    #[test]
+    fn rejected_redirect_is_an_error() {
+        let err = transaction_hashes(
+            Some(crate::wallet_callback::WalletRedirect::Rejected(
+                "userRejected".to_string(),
+            )),
+            300,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The wallet did not sign the transaction: userRejected"
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use async_recursion::async_recursion;
+use std::convert::TryFrom;
 
 /// данные для определения ключа с полным доступом
 #[derive(Debug, Default, clap::Clap)]
@@ -12,19 +13,25 @@ pub struct FullAccessType {
     pub next_action: Box<super::super::NextAction>,
 }
 
-impl From<CliFullAccessType> for FullAccessType {
-    fn from(item: CliFullAccessType) -> Self {
+impl TryFrom<CliFullAccessType> for FullAccessType {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliFullAccessType) -> color_eyre::eyre::Result<Self> {
         let skip_next_action: super::super::NextAction = match item.next_action {
-            Some(cli_skip_action) => super::super::NextAction::from(cli_skip_action),
-            None => super::super::NextAction::input_next_action(),
+            Some(cli_skip_action) => super::super::NextAction::try_from(cli_skip_action)?,
+            None => super::super::NextAction::input_next_action()?,
         };
-        Self {
+        Ok(Self {
             next_action: Box::new(skip_next_action),
-        }
+        })
     }
 }
 
 impl FullAccessType {
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        self.next_action.to_skip_cli_args()
+    }
+
     #[async_recursion(?Send)]
     pub async fn process(
         self,
@@ -32,6 +39,7 @@ impl FullAccessType {
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
         public_key: near_crypto::PublicKey,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let access_key: near_primitives::account::AccessKey = near_primitives::account::AccessKey {
             nonce,
@@ -52,12 +60,20 @@ impl FullAccessType {
         match *self.next_action {
             super::super::NextAction::AddAction(select_action) => {
                 select_action
-                    .process(unsigned_transaction, network_connection_config)
+                    .process(
+                        unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
             super::super::NextAction::Skip(skip_action) => {
                 skip_action
-                    .process(unsigned_transaction, network_connection_config)
+                    .process(
+                        unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
@@ -1,12 +1,14 @@
 use async_recursion::async_recursion;
 use dialoguer::{console::Term, theme::ColorfulTheme, Input, Select};
+use std::convert::TryFrom;
 use std::vec;
 
 /// данные для определения ключа с function call
 #[derive(Debug, Default, clap::Clap)]
 pub struct CliFunctionCallType {
+    /// An amount of NEAR or `unlimited`
     #[clap(long)]
-    allowance: Option<crate::common::NearBalance>,
+    allowance: Option<crate::common::Allowance>,
     #[clap(long)]
     receiver_id: Option<near_primitives::types::AccountId>,
     #[clap(long)]
@@ -23,15 +25,17 @@ pub struct FunctionCallType {
     pub next_action: Box<super::super::NextAction>,
 }
 
-impl From<CliFunctionCallType> for FunctionCallType {
-    fn from(item: CliFunctionCallType) -> Self {
+impl TryFrom<CliFunctionCallType> for FunctionCallType {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliFunctionCallType) -> color_eyre::eyre::Result<Self> {
         let allowance: Option<near_primitives::types::Balance> = match item.allowance {
-            Some(cli_allowance) => Some(cli_allowance.to_yoctonear()),
-            None => FunctionCallType::input_allowance(),
+            Some(cli_allowance) => cli_allowance.to_yoctonear(),
+            None => FunctionCallType::input_allowance()?,
         };
         let receiver_id: near_primitives::types::AccountId = match item.receiver_id {
             Some(cli_receiver_id) => near_primitives::types::AccountId::from(cli_receiver_id),
-            None => FunctionCallType::input_receiver_id(),
+            None => FunctionCallType::input_receiver_id()?,
         };
         let method_names: Vec<String> = match item.method_names {
             Some(cli_method_names) => {
@@ -44,23 +48,24 @@ impl From<CliFunctionCallType> for FunctionCallType {
                         .collect::<Vec<String>>()
                 }
             }
-            None => FunctionCallType::input_method_names(),
+            None => FunctionCallType::input_method_names()?,
         };
         let skip_next_action: super::super::NextAction = match item.next_action {
-            Some(cli_skip_action) => super::super::NextAction::from(cli_skip_action),
-            None => super::super::NextAction::input_next_action(),
+            Some(cli_skip_action) => super::super::NextAction::try_from(cli_skip_action)?,
+            None => super::super::NextAction::input_next_action()?,
         };
-        Self {
+        Ok(Self {
             allowance,
             receiver_id,
             method_names,
             next_action: Box::new(skip_next_action),
-        }
+        })
     }
 }
 
 impl FunctionCallType {
-    pub fn input_method_names() -> Vec<String> {
+    pub fn input_method_names() -> color_eyre::eyre::Result<Vec<String>> {
+        crate::common::ensure_interactive("`--method-names <METHOD_NAMES>` of `function-call-action` (an empty string allows any method)")?;
         println!();
         let choose_input = vec![
             "Yes, I want to input a list of method names that can be used",
@@ -72,7 +77,7 @@ impl FunctionCallType {
             .default(0)
             .interact_on_opt(&Term::stderr())
             .unwrap();
-        match select_choose_input {
+        Ok(match select_choose_input {
             Some(0) => {
                 let mut input_method_names: String = Input::new()
                     .with_prompt("Enter a list of method names that can be used. The access key only allows transactions with the function call of one of the given method names. Empty list means any method name can be used.")
@@ -92,10 +97,13 @@ impl FunctionCallType {
             }
             Some(1) => vec![],
             _ => unreachable!("Error"),
-        }
+        })
     }
 
-    pub fn input_allowance() -> Option<near_primitives::types::Balance> {
+    pub fn input_allowance() -> color_eyre::eyre::Result<Option<near_primitives::types::Balance>> {
+        crate::common::ensure_interactive(
+            "`--allowance <ALLOWANCE>` of `function-call-action` (`unlimited` for no limit)",
+        )?;
         println!();
         let choose_input = vec![
             "Yes, I want to input allowance for receiver ID",
@@ -107,7 +115,7 @@ impl FunctionCallType {
             .default(0)
             .interact_on_opt(&Term::stderr())
             .unwrap();
-        match select_choose_input {
+        Ok(match select_choose_input {
             Some(0) => {
                 let allowance_near_balance: crate::common::NearBalance = Input::new()
                     .with_prompt("Enter an allowance which is a balance limit to use by this access key to pay for function call gas and transaction fees.")
@@ -117,15 +125,31 @@ impl FunctionCallType {
             }
             Some(1) => None,
             _ => unreachable!("Error"),
-        }
+        })
     }
 
-    pub fn input_receiver_id() -> near_primitives::types::AccountId {
+    pub fn input_receiver_id() -> color_eyre::eyre::Result<near_primitives::types::AccountId> {
+        crate::common::ensure_interactive(
+            "`--receiver-id <RECEIVER_ID>` of `function-call-action`",
+        )?;
         println!();
-        Input::new()
+        Ok(Input::new()
             .with_prompt("Enter a receiver to use by this access key to pay for function call gas and transaction fees.")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let mut args = vec![
+            "--allowance".to_owned(),
+            crate::common::Allowance::from_yoctonear(self.allowance).to_cli_arg(),
+            "--receiver-id".to_owned(),
+            self.receiver_id.clone(),
+            "--method-names".to_owned(),
+            self.method_names.join(","),
+        ];
+        args.extend(self.next_action.to_skip_cli_args()?);
+        Some(args)
     }
 
     #[async_recursion(?Send)]
@@ -135,6 +159,7 @@ impl FunctionCallType {
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
         public_key: near_crypto::PublicKey,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let access_key: near_primitives::account::AccessKey = near_primitives::account::AccessKey {
             nonce,
@@ -161,12 +186,20 @@ impl FunctionCallType {
         match *self.next_action {
             super::super::NextAction::AddAction(select_action) => {
                 select_action
-                    .process(unsigned_transaction, network_connection_config)
+                    .process(
+                        unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
             super::super::NextAction::Skip(skip_action) => {
                 skip_action
-                    .process(unsigned_transaction, network_connection_config)
+                    .process(
+                        unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
@@ -1,5 +1,6 @@
 use async_recursion::async_recursion;
 use dialoguer::{theme::ColorfulTheme, Input, Select};
+use std::convert::TryFrom;
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 mod full_access_type;
@@ -22,41 +23,55 @@ pub struct AddAccessKeyAction {
     pub permission: AccessKeyPermission,
 }
 
-impl From<CliAddAccessKeyAction> for AddAccessKeyAction {
-    fn from(item: CliAddAccessKeyAction) -> Self {
+impl TryFrom<CliAddAccessKeyAction> for AddAccessKeyAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliAddAccessKeyAction) -> color_eyre::eyre::Result<Self> {
         let public_key: near_crypto::PublicKey = match item.public_key {
             Some(cli_public_key) => cli_public_key,
-            None => AddAccessKeyAction::input_public_key(),
+            None => AddAccessKeyAction::input_public_key()?,
         };
         let nonce: near_primitives::types::Nonce = match item.nonce {
             Some(cli_nonce) => near_primitives::types::Nonce::from(cli_nonce),
-            None => AddAccessKeyAction::input_nonce(),
+            None => AddAccessKeyAction::input_nonce()?,
         };
         let permission: AccessKeyPermission = match item.permission {
-            Some(cli_permission) => AccessKeyPermission::from(cli_permission),
-            None => AccessKeyPermission::choose_permission(),
+            Some(cli_permission) => AccessKeyPermission::try_from(cli_permission)?,
+            None => AccessKeyPermission::choose_permission()?,
         };
-        Self {
+        Ok(Self {
             public_key,
             nonce,
             permission,
-        }
+        })
     }
 }
 
 impl AddAccessKeyAction {
-    pub fn input_nonce() -> near_primitives::types::Nonce {
-        Input::new()
+    pub fn input_nonce() -> color_eyre::eyre::Result<near_primitives::types::Nonce> {
+        crate::common::ensure_interactive("`--nonce <NONCE>` of `add-access-key`")?;
+        Ok(Input::new()
             .with_prompt("Enter the nonce for this access key")
             .interact_text()
-            .unwrap()
+            .unwrap())
     }
 
-    pub fn input_public_key() -> near_crypto::PublicKey {
-        Input::new()
+    pub fn input_public_key() -> color_eyre::eyre::Result<near_crypto::PublicKey> {
+        crate::common::ensure_interactive("`<PUBLIC_KEY>` of `add-access-key`")?;
+        Ok(Input::new()
             .with_prompt("Enter a public key for this access key")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let mut args = vec![
+            self.public_key.to_string(),
+            "--nonce".to_owned(),
+            self.nonce.to_string(),
+        ];
+        args.extend(self.permission.to_cli_args()?);
+        Some(args)
     }
 
     #[async_recursion(?Send)]
@@ -64,6 +79,7 @@ impl AddAccessKeyAction {
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self.permission {
             AccessKeyPermission::FullAccessAction(full_access_type) => {
@@ -73,6 +89,7 @@ impl AddAccessKeyAction {
                         prepopulated_unsigned_transaction,
                         network_connection_config,
                         self.public_key,
+                        output_format,
                     )
                     .await
             }
@@ -83,6 +100,7 @@ impl AddAccessKeyAction {
                         prepopulated_unsigned_transaction,
                         network_connection_config,
                         self.public_key,
+                        output_format,
                     )
                     .await
             }
@@ -107,26 +125,29 @@ pub enum AccessKeyPermission {
     FullAccessAction(self::full_access_type::FullAccessType),
 }
 
-impl From<CliAccessKeyPermission> for AccessKeyPermission {
-    fn from(item: CliAccessKeyPermission) -> Self {
-        match item {
+impl TryFrom<CliAccessKeyPermission> for AccessKeyPermission {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliAccessKeyPermission) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliAccessKeyPermission::FunctionCallAction(cli_function_call_type) => {
                 let function_call_type =
-                    self::function_call_type::FunctionCallType::from(cli_function_call_type);
+                    self::function_call_type::FunctionCallType::try_from(cli_function_call_type)?;
                 AccessKeyPermission::FunctionCallAction(function_call_type)
             }
             CliAccessKeyPermission::FullAccessAction(cli_full_access_type) => {
                 let full_access_type =
-                    self::full_access_type::FullAccessType::from(cli_full_access_type);
+                    self::full_access_type::FullAccessType::try_from(cli_full_access_type)?;
                 AccessKeyPermission::FullAccessAction(full_access_type)
             }
-        }
+        })
     }
 }
 
 impl AccessKeyPermission {
-    pub fn choose_permission() -> Self {
+    pub fn choose_permission() -> color_eyre::eyre::Result<Self> {
         let variants = AccessKeyPermissionDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let permissions = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -137,13 +158,26 @@ impl AccessKeyPermission {
             .default(0)
             .interact()
             .unwrap();
-        match variants[select_permission] {
-            AccessKeyPermissionDiscriminants::FunctionCallAction => Self::from(
+        Ok(match variants[select_permission] {
+            AccessKeyPermissionDiscriminants::FunctionCallAction => Self::try_from(
                 CliAccessKeyPermission::FunctionCallAction(Default::default()),
-            ),
+            )?,
             AccessKeyPermissionDiscriminants::FullAccessAction => {
-                Self::from(CliAccessKeyPermission::FullAccessAction(Default::default()))
+                Self::try_from(CliAccessKeyPermission::FullAccessAction(Default::default()))?
             }
-        }
+        })
+    }
+
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let (permission, mut args) = match self {
+            Self::FunctionCallAction(function_call_type) => {
+                ("function-call-action", function_call_type.to_cli_args()?)
+            }
+            Self::FullAccessAction(full_access_type) => {
+                ("full-access-action", full_access_type.to_cli_args()?)
+            }
+        };
+        args.insert(0, permission.to_owned());
+        Some(args)
     }
 }
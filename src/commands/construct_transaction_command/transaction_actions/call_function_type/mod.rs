@@ -1,5 +1,6 @@
 use async_recursion::async_recursion;
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 /// вызов CallFunction
 #[derive(Debug, Default, clap::Clap)]
@@ -25,50 +26,54 @@ pub struct CallFunctionAction {
     next_action: Box<super::NextAction>,
 }
 
-impl From<CliCallFunctionAction> for CallFunctionAction {
-    fn from(item: CliCallFunctionAction) -> Self {
+impl TryFrom<CliCallFunctionAction> for CallFunctionAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliCallFunctionAction) -> color_eyre::eyre::Result<Self> {
         let method_name: String = match item.method_name {
             Some(cli_method_name) => cli_method_name,
-            None => CallFunctionAction::input_method_name(),
+            None => CallFunctionAction::input_method_name()?,
         };
         let args: Vec<u8> = match item.args {
             Some(cli_args) => cli_args.into_bytes(),
-            None => CallFunctionAction::input_args(),
+            None => CallFunctionAction::input_args()?,
         };
         let gas: near_primitives::types::Gas = match item.gas {
             Some(cli_gas) => match cli_gas {
                 crate::common::NearGas { inner: num } => num,
             },
-            None => CallFunctionAction::input_gas(),
+            None => CallFunctionAction::input_gas()?,
         };
         let deposit: near_primitives::types::Balance = match item.deposit {
             Some(cli_deposit) => cli_deposit.to_yoctonear(),
-            None => CallFunctionAction::input_deposit(),
+            None => CallFunctionAction::input_deposit()?,
         };
         let skip_next_action: super::NextAction = match item.next_action {
-            Some(cli_skip_action) => super::NextAction::from(cli_skip_action),
-            None => super::NextAction::input_next_action(),
+            Some(cli_skip_action) => super::NextAction::try_from(cli_skip_action)?,
+            None => super::NextAction::input_next_action()?,
         };
-        Self {
+        Ok(Self {
             method_name,
             args,
             gas,
             deposit,
             next_action: Box::new(skip_next_action),
-        }
+        })
     }
 }
 
 impl CallFunctionAction {
-    fn input_method_name() -> String {
+    fn input_method_name() -> color_eyre::eyre::Result<String> {
+        crate::common::ensure_interactive("`--method-name <METHOD_NAME>` of `call-function`")?;
         println!();
-        Input::new()
+        Ok(Input::new()
             .with_prompt("Enter a method name")
             .interact_text()
-            .unwrap()
+            .unwrap())
     }
 
-    fn input_gas() -> near_primitives::types::Gas {
+    fn input_gas() -> color_eyre::eyre::Result<near_primitives::types::Gas> {
+        crate::common::ensure_interactive("`--gas <GAS>` of `call-function`")?;
         println!();
         let gas: u64 = loop {
             let input_gas: crate::common::NearGas = Input::new()
@@ -85,19 +90,21 @@ impl CallFunctionAction {
                 println!("You need to enter a value of no more than 200 TERAGAS")
             }
         };
-        gas
+        Ok(gas)
     }
 
-    fn input_args() -> Vec<u8> {
+    fn input_args() -> color_eyre::eyre::Result<Vec<u8>> {
+        crate::common::ensure_interactive("`--args <ARGS>` of `call-function`")?;
         println!();
         let input: String = Input::new()
             .with_prompt("Enter args for function")
             .interact_text()
             .unwrap();
-        input.into_bytes()
+        Ok(input.into_bytes())
     }
 
-    fn input_deposit() -> near_primitives::types::Balance {
+    fn input_deposit() -> color_eyre::eyre::Result<near_primitives::types::Balance> {
+        crate::common::ensure_interactive("`--deposit <DEPOSIT>` of `call-function`")?;
         println!();
         let deposit: crate::common::NearBalance = Input::new()
             .with_prompt(
@@ -106,7 +113,22 @@ impl CallFunctionAction {
             .with_initial_text("0 NEAR")
             .interact_text()
             .unwrap();
-        deposit.to_yoctonear()
+        Ok(deposit.to_yoctonear())
+    }
+
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let mut args = vec![
+            "--method-name".to_owned(),
+            self.method_name.clone(),
+            "--args".to_owned(),
+            String::from_utf8_lossy(&self.args).into_owned(),
+            "--gas".to_owned(),
+            crate::common::NearGas { inner: self.gas }.to_cli_arg(),
+            "--deposit".to_owned(),
+            crate::common::NearBalance::from_yoctonear(self.deposit).to_cli_arg(),
+        ];
+        args.extend(self.next_action.to_skip_cli_args()?);
+        Some(args)
     }
 
     #[async_recursion(?Send)]
@@ -114,6 +136,7 @@ impl CallFunctionAction {
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let action = near_primitives::transaction::Action::FunctionCall(
             near_primitives::transaction::FunctionCallAction {
@@ -132,12 +155,20 @@ impl CallFunctionAction {
         match *self.next_action {
             super::NextAction::AddAction(select_action) => {
                 select_action
-                    .process(unsigned_transaction, network_connection_config)
+                    .process(
+                        unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
             super::NextAction::Skip(skip_action) => {
                 skip_action
-                    .process(unsigned_transaction, network_connection_config)
+                    .process(
+                        unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
@@ -1,4 +1,5 @@
 use async_recursion::async_recursion;
+use std::convert::TryFrom;
 
 /// создание аккаунта
 #[derive(Debug, Default, clap::Clap)]
@@ -12,24 +13,31 @@ pub struct CreateAccountAction {
     pub next_action: Box<super::NextAction>,
 }
 
-impl From<CliCreateAccountAction> for CreateAccountAction {
-    fn from(item: CliCreateAccountAction) -> Self {
+impl TryFrom<CliCreateAccountAction> for CreateAccountAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliCreateAccountAction) -> color_eyre::eyre::Result<Self> {
         let skip_next_action: super::NextAction = match item.next_action {
-            Some(cli_skip_action) => super::NextAction::from(cli_skip_action),
-            None => super::NextAction::input_next_action(),
+            Some(cli_skip_action) => super::NextAction::try_from(cli_skip_action)?,
+            None => super::NextAction::input_next_action()?,
         };
-        Self {
+        Ok(Self {
             next_action: Box::new(skip_next_action),
-        }
+        })
     }
 }
 
 impl CreateAccountAction {
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        self.next_action.to_skip_cli_args()
+    }
+
     #[async_recursion(?Send)]
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let action = near_primitives::transaction::Action::CreateAccount(
             near_primitives::transaction::CreateAccountAction {},
@@ -43,12 +51,20 @@ impl CreateAccountAction {
         match *self.next_action {
             super::NextAction::AddAction(select_action) => {
                 select_action
-                    .process(unsigned_transaction, network_connection_config)
+                    .process(
+                        unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
             super::NextAction::Skip(skip_action) => {
                 skip_action
-                    .process(unsigned_transaction, network_connection_config)
+                    .process(
+                        unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
@@ -1,5 +1,6 @@
 use async_recursion::async_recursion;
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 /// удаление ключа доступа у пользователя
 #[derive(Debug, Default, clap::Clap)]
@@ -16,29 +17,38 @@ pub struct DeleteAccessKeyAction {
     pub next_action: Box<super::NextAction>,
 }
 
-impl From<CliDeleteAccessKeyAction> for DeleteAccessKeyAction {
-    fn from(item: CliDeleteAccessKeyAction) -> Self {
+impl TryFrom<CliDeleteAccessKeyAction> for DeleteAccessKeyAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliDeleteAccessKeyAction) -> color_eyre::eyre::Result<Self> {
         let public_key: near_crypto::PublicKey = match item.public_key {
             Some(cli_public_key) => cli_public_key,
-            None => DeleteAccessKeyAction::input_public_key(),
+            None => DeleteAccessKeyAction::input_public_key()?,
         };
         let skip_next_action: super::NextAction = match item.next_action {
-            Some(cli_skip_action) => super::NextAction::from(cli_skip_action),
-            None => super::NextAction::input_next_action(),
+            Some(cli_skip_action) => super::NextAction::try_from(cli_skip_action)?,
+            None => super::NextAction::input_next_action()?,
         };
-        Self {
+        Ok(Self {
             public_key,
             next_action: Box::new(skip_next_action),
-        }
+        })
     }
 }
 
 impl DeleteAccessKeyAction {
-    pub fn input_public_key() -> near_crypto::PublicKey {
-        Input::new()
+    pub fn input_public_key() -> color_eyre::eyre::Result<near_crypto::PublicKey> {
+        crate::common::ensure_interactive("`--public-key <PUBLIC_KEY>` of `delete-access-key`")?;
+        Ok(Input::new()
             .with_prompt("Enter the access key to remove it")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let mut args = vec!["--public-key".to_owned(), self.public_key.to_string()];
+        args.extend(self.next_action.to_skip_cli_args()?);
+        Some(args)
     }
 
     #[async_recursion(?Send)]
@@ -46,6 +56,7 @@ impl DeleteAccessKeyAction {
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let action = near_primitives::transaction::Action::DeleteKey(
             near_primitives::transaction::DeleteKeyAction {
@@ -61,12 +72,20 @@ impl DeleteAccessKeyAction {
         match *self.next_action {
             super::NextAction::AddAction(select_action) => {
                 select_action
-                    .process(unsigned_transaction, network_connection_config)
+                    .process(
+                        unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
             super::NextAction::Skip(skip_action) => {
                 skip_action
-                    .process(unsigned_transaction, network_connection_config)
+                    .process(
+                        unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
@@ -1,5 +1,6 @@
 use async_recursion::async_recursion;
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 /// удаление аккаунта
 #[derive(Debug, Default, clap::Clap)]
@@ -16,30 +17,41 @@ pub struct DeleteAccountAction {
     pub next_action: Box<super::NextAction>,
 }
 
-impl From<CliDeleteAccountAction> for DeleteAccountAction {
-    fn from(item: CliDeleteAccountAction) -> Self {
+impl TryFrom<CliDeleteAccountAction> for DeleteAccountAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliDeleteAccountAction) -> color_eyre::eyre::Result<Self> {
         let beneficiary_id: near_primitives::types::AccountId = match item.beneficiary_id {
             Some(cli_account_id) => cli_account_id,
-            None => DeleteAccountAction::input_beneficiary_id(),
+            None => DeleteAccountAction::input_beneficiary_id()?,
         };
         let skip_next_action: super::NextAction = match item.next_action {
-            Some(cli_skip_action) => super::NextAction::from(cli_skip_action),
-            None => super::NextAction::input_next_action(),
+            Some(cli_skip_action) => super::NextAction::try_from(cli_skip_action)?,
+            None => super::NextAction::input_next_action()?,
         };
-        Self {
+        Ok(Self {
             beneficiary_id,
             next_action: Box::new(skip_next_action),
-        }
+        })
     }
 }
 
 impl DeleteAccountAction {
-    pub fn input_beneficiary_id() -> near_primitives::types::AccountId {
+    pub fn input_beneficiary_id() -> color_eyre::eyre::Result<near_primitives::types::AccountId> {
+        crate::common::ensure_interactive(
+            "`--beneficiary-id <BENEFICIARY_ID>` of `delete-account`",
+        )?;
         println!();
-        Input::new()
+        Ok(Input::new()
             .with_prompt("Enter the beneficiary ID to delete this account ID")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let mut args = vec!["--beneficiary-id".to_owned(), self.beneficiary_id.clone()];
+        args.extend(self.next_action.to_skip_cli_args()?);
+        Some(args)
     }
 
     #[async_recursion(?Send)]
@@ -47,6 +59,7 @@ impl DeleteAccountAction {
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let beneficiary_id: near_primitives::types::AccountId = self.beneficiary_id.clone();
         let action = near_primitives::transaction::Action::DeleteAccount(
@@ -61,12 +74,20 @@ impl DeleteAccountAction {
         match *self.next_action {
             super::NextAction::AddAction(select_action) => {
                 select_action
-                    .process(unsigned_transaction, network_connection_config)
+                    .process(
+                        unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
             super::NextAction::Skip(skip_action) => {
                 skip_action
-                    .process(unsigned_transaction, network_connection_config)
+                    .process(
+                        unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::{TryFrom, TryInto};
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 mod add_access_key_type;
@@ -32,44 +33,51 @@ pub enum NextAction {
     Skip(SkipAction),
 }
 
-impl From<CliNextAction> for NextAction {
-    fn from(item: CliNextAction) -> Self {
-        match item {
+impl TryFrom<CliNextAction> for NextAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliNextAction) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliNextAction::AddAction(cli_select_action) => {
-                let select_action: SelectAction = SelectAction::from(cli_select_action);
+                let select_action: SelectAction = SelectAction::try_from(cli_select_action)?;
                 Self::AddAction(select_action)
             }
             CliNextAction::Skip(cli_skip_action) => {
-                let skip_action: SkipAction = SkipAction::from(cli_skip_action);
+                let skip_action: SkipAction = SkipAction::try_from(cli_skip_action)?;
                 Self::Skip(skip_action)
             }
-        }
+        })
     }
 }
 
-impl From<CliSkipNextAction> for NextAction {
-    fn from(item: CliSkipNextAction) -> Self {
-        match item {
+impl TryFrom<CliSkipNextAction> for NextAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSkipNextAction) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliSkipNextAction::Skip(cli_skip_action) => {
-                let skip_action: SkipAction = SkipAction::from(cli_skip_action);
+                let skip_action: SkipAction = SkipAction::try_from(cli_skip_action)?;
                 Self::Skip(skip_action)
             }
-        }
+        })
     }
 }
 
-impl From<CliSkipNextAction> for CliNextAction {
-    fn from(item: CliSkipNextAction) -> Self {
-        match item {
+impl TryFrom<CliSkipNextAction> for CliNextAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSkipNextAction) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliSkipNextAction::Skip(cli_skip_action) => Self::Skip(cli_skip_action),
-        }
+        })
     }
 }
 
 impl NextAction {
-    pub fn input_next_action() -> Self {
+    pub fn input_next_action() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = NextActionDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let next_action = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -84,23 +92,50 @@ impl NextAction {
             NextActionDiscriminants::AddAction => CliNextAction::AddAction(Default::default()),
             NextActionDiscriminants::Skip => CliNextAction::Skip(Default::default()),
         };
-        Self::from(cli_next_action)
+        Self::try_from(cli_next_action)
+    }
+
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let (next_action, mut args) = match self {
+            Self::AddAction(select_action) => ("add-action", select_action.to_cli_args()?),
+            Self::Skip(skip_action) => ("skip", skip_action.to_cli_args()?),
+        };
+        args.insert(0, next_action.to_owned());
+        Some(args)
+    }
+
+    /// Only `skip` can follow an action on the command line, so the next actions of
+    /// a transaction can only be added with the prompts
+    pub fn to_skip_cli_args(&self) -> Option<Vec<String>> {
+        match self {
+            Self::AddAction(_) => None,
+            Self::Skip(_) => self.to_cli_args(),
+        }
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             NextAction::AddAction(select_action) => {
                 select_action
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
             NextAction::Skip(skip_action) => {
                 skip_action
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
@@ -119,26 +154,39 @@ pub struct SelectAction {
     transaction_subcommand: ActionSubcommand,
 }
 
-impl From<CliSelectAction> for SelectAction {
-    fn from(item: CliSelectAction) -> Self {
+impl TryFrom<CliSelectAction> for SelectAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSelectAction) -> color_eyre::eyre::Result<Self> {
         let transaction_subcommand: ActionSubcommand = match item.transaction_subcommand {
-            Some(cli_transaction_subcommand) => ActionSubcommand::from(cli_transaction_subcommand),
-            None => ActionSubcommand::choose_action_command(),
+            Some(cli_transaction_subcommand) => {
+                ActionSubcommand::try_from(cli_transaction_subcommand)?
+            }
+            None => ActionSubcommand::choose_action_command()?,
         };
-        Self {
+        Ok(Self {
             transaction_subcommand,
-        }
+        })
     }
 }
 
 impl SelectAction {
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        self.transaction_subcommand.to_cli_args()
+    }
+
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         self.transaction_subcommand
-            .process(prepopulated_unsigned_transaction, network_connection_config)
+            .process(
+                prepopulated_unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
@@ -180,38 +228,41 @@ pub enum ActionSubcommand {
     DeleteAccessKey(self::delete_access_key_type::DeleteAccessKeyAction),
 }
 
-impl From<CliActionSubcommand> for ActionSubcommand {
-    fn from(item: CliActionSubcommand) -> Self {
-        match item {
+impl TryFrom<CliActionSubcommand> for ActionSubcommand {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliActionSubcommand) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliActionSubcommand::TransferNEARTokens(cli_transfer_near_token) => {
-                Self::TransferNEARTokens(cli_transfer_near_token.into())
+                Self::TransferNEARTokens(cli_transfer_near_token.try_into()?)
             }
             CliActionSubcommand::CreateAccount(cli_create_account) => {
-                Self::CreateAccount(cli_create_account.into())
+                Self::CreateAccount(cli_create_account.try_into()?)
             }
             CliActionSubcommand::DeleteAccount(cli_delete_account) => {
-                Self::DeleteAccount(cli_delete_account.into())
+                Self::DeleteAccount(cli_delete_account.try_into()?)
             }
             CliActionSubcommand::AddAccessKey(cli_add_access_key) => {
-                Self::AddAccessKey(cli_add_access_key.into())
+                Self::AddAccessKey(cli_add_access_key.try_into()?)
             }
             CliActionSubcommand::DeleteAccessKey(cli_delete_access_key) => {
-                Self::DeleteAccessKey(cli_delete_access_key.into())
+                Self::DeleteAccessKey(cli_delete_access_key.try_into()?)
             }
             CliActionSubcommand::StakeNEARTokens(cli_stake_near_token) => {
-                Self::StakeNEARTokens(cli_stake_near_token.into())
+                Self::StakeNEARTokens(cli_stake_near_token.try_into()?)
             }
             CliActionSubcommand::CallFunction(cli_call_function) => {
-                Self::CallFunction(cli_call_function.into())
+                Self::CallFunction(cli_call_function.try_into()?)
             }
-        }
+        })
     }
 }
 
 impl ActionSubcommand {
-    pub fn choose_action_command() -> ActionSubcommand {
+    pub fn choose_action_command() -> color_eyre::eyre::Result<ActionSubcommand> {
         println!();
         let variants = ActionSubcommandDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let action_subcommands = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -245,48 +296,101 @@ impl ActionSubcommand {
                 CliActionSubcommand::DeleteAccessKey(Default::default())
             }
         };
-        Self::from(cli_action_subcomand)
+        Self::try_from(cli_action_subcomand)
+    }
+
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let (action, mut args) = match self {
+            Self::TransferNEARTokens(args_transfer) => {
+                ("transfer-near-tokens", args_transfer.to_cli_args()?)
+            }
+            Self::CallFunction(args_function) => ("call-function", args_function.to_cli_args()?),
+            Self::StakeNEARTokens(args_stake) => ("stake-near-tokens", args_stake.to_cli_args()?),
+            Self::CreateAccount(args_create_account) => {
+                ("create-account", args_create_account.to_cli_args()?)
+            }
+            Self::DeleteAccount(args_delete_account) => {
+                ("delete-account", args_delete_account.to_cli_args()?)
+            }
+            Self::AddAccessKey(args_add_access_key) => {
+                ("add-access-key", args_add_access_key.to_cli_args()?)
+            }
+            Self::DeleteAccessKey(args_delete_access_key) => {
+                ("delete-access-key", args_delete_access_key.to_cli_args()?)
+            }
+        };
+        args.insert(0, action.to_owned());
+        Some(args)
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             ActionSubcommand::TransferNEARTokens(args_transfer) => {
                 args_transfer
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
             ActionSubcommand::CallFunction(args_function) => {
                 args_function
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
             ActionSubcommand::StakeNEARTokens(args_stake) => {
                 args_stake
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
             ActionSubcommand::CreateAccount(args_create_account) => {
                 args_create_account
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
             ActionSubcommand::DeleteAccount(args_delete_account) => {
                 args_delete_account
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
             ActionSubcommand::AddAccessKey(args_add_access_key) => {
                 args_add_access_key
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
             ActionSubcommand::DeleteAccessKey(args_delete_access_key) => {
                 args_delete_access_key
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
@@ -305,24 +409,35 @@ pub struct SkipAction {
     pub sign_option: super::sign_transaction::SignTransaction,
 }
 
-impl From<CliSkipAction> for SkipAction {
-    fn from(item: CliSkipAction) -> Self {
+impl TryFrom<CliSkipAction> for SkipAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSkipAction) -> color_eyre::eyre::Result<Self> {
         let sign_option: super::sign_transaction::SignTransaction = match item.sign_option {
-            Some(cli_sign_transaction) => cli_sign_transaction.into(),
-            None => super::sign_transaction::SignTransaction::choose_sign_option(),
+            Some(cli_sign_transaction) => cli_sign_transaction.try_into()?,
+            None => super::sign_transaction::SignTransaction::choose_sign_option()?,
         };
-        Self { sign_option }
+        Ok(Self { sign_option })
     }
 }
 
 impl SkipAction {
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        Some(self.sign_option.to_cli_args())
+    }
+
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         self.sign_option
-            .process(prepopulated_unsigned_transaction, network_connection_config)
+            .process(
+                prepopulated_unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
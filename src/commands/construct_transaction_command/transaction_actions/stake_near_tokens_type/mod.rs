@@ -1,5 +1,6 @@
 use async_recursion::async_recursion;
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 /// создание ставки
 #[derive(Debug, Default, clap::Clap)]
@@ -17,41 +18,51 @@ pub struct StakeNEARTokensAction {
     pub next_action: Box<super::NextAction>,
 }
 
-impl From<CliStakeNEARTokensAction> for StakeNEARTokensAction {
-    fn from(item: CliStakeNEARTokensAction) -> Self {
+impl TryFrom<CliStakeNEARTokensAction> for StakeNEARTokensAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliStakeNEARTokensAction) -> color_eyre::eyre::Result<Self> {
         let stake: crate::common::NearBalance = match item.stake {
             Some(cli_stake) => cli_stake,
-            None => StakeNEARTokensAction::input_stake(),
+            None => StakeNEARTokensAction::input_stake()?,
         };
         let public_key: near_crypto::PublicKey = match item.public_key {
             Some(cli_public_key) => cli_public_key,
-            None => StakeNEARTokensAction::input_public_key(),
+            None => StakeNEARTokensAction::input_public_key()?,
         };
         let skip_next_action: super::NextAction = match item.next_action {
-            Some(cli_skip_action) => super::NextAction::from(cli_skip_action),
-            None => super::NextAction::input_next_action(),
+            Some(cli_skip_action) => super::NextAction::try_from(cli_skip_action)?,
+            None => super::NextAction::input_next_action()?,
         };
-        Self {
+        Ok(Self {
             stake,
             public_key,
             next_action: Box::new(skip_next_action),
-        }
+        })
     }
 }
 
 impl StakeNEARTokensAction {
-    fn input_public_key() -> near_crypto::PublicKey {
-        Input::new()
+    fn input_public_key() -> color_eyre::eyre::Result<near_crypto::PublicKey> {
+        crate::common::ensure_interactive("`<PUBLIC_KEY>` of `stake-near-tokens`")?;
+        Ok(Input::new()
             .with_prompt("Enter a public key for this stake")
             .interact_text()
-            .unwrap()
+            .unwrap())
     }
 
-    fn input_stake() -> crate::common::NearBalance {
-        Input::new()
+    fn input_stake() -> color_eyre::eyre::Result<crate::common::NearBalance> {
+        crate::common::ensure_interactive("`<STAKE>` of `stake-near-tokens`")?;
+        Ok(Input::new()
             .with_prompt("How many NEAR Tokens do you want to stake?")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let mut args = vec![self.stake.to_cli_arg(), self.public_key.to_string()];
+        args.extend(self.next_action.to_skip_cli_args()?);
+        Some(args)
     }
 
     #[async_recursion(?Send)]
@@ -59,6 +70,7 @@ impl StakeNEARTokensAction {
         self,
         mut prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let action = near_primitives::transaction::Action::Stake(
             near_primitives::transaction::StakeAction {
@@ -70,12 +82,20 @@ impl StakeNEARTokensAction {
         match *self.next_action {
             super::NextAction::AddAction(select_action) => {
                 select_action
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
             super::NextAction::Skip(skip_action) => {
                 skip_action
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
@@ -1,5 +1,6 @@
 use async_recursion::async_recursion;
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 /// создание перевода токенов
 #[derive(Debug, Default, clap::Clap)]
@@ -15,29 +16,38 @@ pub struct TransferNEARTokensAction {
     pub next_action: Box<super::NextAction>,
 }
 
-impl From<CliTransferNEARTokensAction> for TransferNEARTokensAction {
-    fn from(item: CliTransferNEARTokensAction) -> Self {
+impl TryFrom<CliTransferNEARTokensAction> for TransferNEARTokensAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliTransferNEARTokensAction) -> color_eyre::eyre::Result<Self> {
         let amount: crate::common::NearBalance = match item.amount {
             Some(cli_amount) => cli_amount,
-            None => TransferNEARTokensAction::input_amount(),
+            None => TransferNEARTokensAction::input_amount()?,
         };
         let skip_next_action: super::NextAction = match item.next_action {
-            Some(cli_skip_action) => super::NextAction::from(cli_skip_action),
-            None => super::NextAction::input_next_action(),
+            Some(cli_skip_action) => super::NextAction::try_from(cli_skip_action)?,
+            None => super::NextAction::input_next_action()?,
         };
-        Self {
+        Ok(Self {
             amount,
             next_action: Box::new(skip_next_action),
-        }
+        })
     }
 }
 
 impl TransferNEARTokensAction {
-    fn input_amount() -> crate::common::NearBalance {
-        Input::new()
+    fn input_amount() -> color_eyre::eyre::Result<crate::common::NearBalance> {
+        crate::common::ensure_interactive("`<AMOUNT>` of `transfer-near-tokens`")?;
+        Ok(Input::new()
             .with_prompt("How many NEAR Tokens do you want to transfer? (example: 10NEAR or 0.5near or 10000yoctonear)")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let mut args = vec![self.amount.to_cli_arg()];
+        args.extend(self.next_action.to_skip_cli_args()?);
+        Some(args)
     }
 
     #[async_recursion(?Send)]
@@ -45,6 +55,7 @@ impl TransferNEARTokensAction {
         self,
         mut prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let action = near_primitives::transaction::Action::Transfer(
             near_primitives::transaction::TransferAction {
@@ -55,12 +66,20 @@ impl TransferNEARTokensAction {
         match *self.next_action {
             super::NextAction::AddAction(select_action) => {
                 select_action
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
             super::NextAction::Skip(skip_action) => {
                 skip_action
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
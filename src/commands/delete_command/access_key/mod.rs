@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::{TryFrom, TryInto};
 
 pub mod operation_mode;
 mod sender;
@@ -14,30 +15,47 @@ pub enum DeleteAccessKeyAction {
     PublicKey(DeleteAccessKeyType),
 }
 
-impl From<CliDeleteAccessKeyAction> for DeleteAccessKeyAction {
-    fn from(item: CliDeleteAccessKeyAction) -> Self {
-        match item {
+impl TryFrom<CliDeleteAccessKeyAction> for DeleteAccessKeyAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliDeleteAccessKeyAction) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliDeleteAccessKeyAction::PublicKey(cli_delete_access_key_type) => {
-                Self::PublicKey(cli_delete_access_key_type.into())
+                Self::PublicKey(cli_delete_access_key_type.try_into()?)
             }
-        }
+        })
     }
 }
 
 impl DeleteAccessKeyAction {
-    pub fn choose_delete_access_key_action() -> Self {
-        Self::from(CliDeleteAccessKeyAction::PublicKey(Default::default()))
+    pub fn choose_delete_access_key_action() -> color_eyre::eyre::Result<Self> {
+        Self::try_from(CliDeleteAccessKeyAction::PublicKey(Default::default()))
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (command, mut args) = match self {
+            Self::PublicKey(delete_access_key_type) => {
+                ("public-key", delete_access_key_type.to_cli_args())
+            }
+        };
+        args.insert(0, command.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             DeleteAccessKeyAction::PublicKey(delete_access_key_type) => {
                 delete_access_key_type
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
@@ -61,35 +79,45 @@ pub struct DeleteAccessKeyType {
         crate::commands::construct_transaction_command::sign_transaction::SignTransaction,
 }
 
-impl From<CliDeleteAccessKeyType> for DeleteAccessKeyType {
-    fn from(item: CliDeleteAccessKeyType) -> Self {
+impl TryFrom<CliDeleteAccessKeyType> for DeleteAccessKeyType {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliDeleteAccessKeyType) -> color_eyre::eyre::Result<Self> {
         let public_key: near_crypto::PublicKey = match item.public_key {
             Some(cli_public_key) => cli_public_key,
-            None => DeleteAccessKeyType::input_public_key(),
+            None => DeleteAccessKeyType::input_public_key()?,
         };
         let sign_option = match item.sign_option {
-            Some(cli_sign_transaction) => cli_sign_transaction.into(),
-            None => crate::commands::construct_transaction_command::sign_transaction::SignTransaction::choose_sign_option(),
+            Some(cli_sign_transaction) => cli_sign_transaction.try_into()?,
+            None => crate::commands::construct_transaction_command::sign_transaction::SignTransaction::choose_sign_option()?,
         };
-        Self {
+        Ok(Self {
             public_key,
             sign_option,
-        }
+        })
     }
 }
 
 impl DeleteAccessKeyType {
-    pub fn input_public_key() -> near_crypto::PublicKey {
-        Input::new()
+    pub fn input_public_key() -> color_eyre::eyre::Result<near_crypto::PublicKey> {
+        crate::common::ensure_interactive("`<PUBLIC_KEY>` of `public-key`")?;
+        Ok(Input::new()
             .with_prompt("Enter a public key for this access key")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![self.public_key.to_string()];
+        args.extend(self.sign_option.to_cli_args());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let action = near_primitives::transaction::Action::DeleteKey(
             near_primitives::transaction::DeleteKeyAction {
@@ -103,7 +131,11 @@ impl DeleteAccessKeyType {
             ..prepopulated_unsigned_transaction
         };
         self.sign_option
-            .process(unsigned_transaction, network_connection_config)
+            .process(
+                unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
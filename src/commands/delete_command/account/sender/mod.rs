@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 /// Specify the account to be deleted
 #[derive(Debug, Default, clap::Clap)]
@@ -14,36 +15,46 @@ pub struct Sender {
     pub send_to: SendTo,
 }
 
-impl From<CliSender> for Sender {
-    fn from(item: CliSender) -> Self {
+impl TryFrom<CliSender> for Sender {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSender) -> color_eyre::eyre::Result<Self> {
         let sender_account_id: String = match item.sender_account_id {
             Some(cli_sender_account_id) => cli_sender_account_id,
-            None => Sender::input_sender_account_id(),
+            None => Sender::input_sender_account_id()?,
         };
         let send_to: SendTo = match item.send_to {
-            Some(cli_send_to) => SendTo::from(cli_send_to),
-            None => SendTo::send_to(),
+            Some(cli_send_to) => SendTo::try_from(cli_send_to)?,
+            None => SendTo::send_to()?,
         };
-        Self {
+        Ok(Self {
             sender_account_id,
             send_to,
-        }
+        })
     }
 }
 
 impl Sender {
-    pub fn input_sender_account_id() -> String {
+    pub fn input_sender_account_id() -> color_eyre::eyre::Result<String> {
+        crate::common::ensure_interactive("`<SENDER_ACCOUNT_ID>` of `account`")?;
         println!();
-        Input::new()
+        Ok(Input::new()
             .with_prompt("Which account ID do you need to remove?")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![self.sender_account_id.clone()];
+        args.extend(self.send_to.to_cli_args());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let unsigned_transaction = near_primitives::transaction::Transaction {
             signer_id: self.sender_account_id.clone(),
@@ -51,7 +62,11 @@ impl Sender {
             ..prepopulated_unsigned_transaction
         };
         self.send_to
-            .process(unsigned_transaction, network_connection_config)
+            .process(
+                unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
@@ -67,31 +82,48 @@ pub enum SendTo {
     Beneficiary(super::DeleteAccountAction),
 }
 
-impl From<CliSendTo> for SendTo {
-    fn from(item: CliSendTo) -> Self {
-        match item {
+impl TryFrom<CliSendTo> for SendTo {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSendTo) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliSendTo::Beneficiary(cli_delete_accaunt) => {
-                let delete_accaunt = super::DeleteAccountAction::from(cli_delete_accaunt);
+                let delete_accaunt = super::DeleteAccountAction::try_from(cli_delete_accaunt)?;
                 Self::Beneficiary(delete_accaunt)
             }
-        }
+        })
     }
 }
 
 impl SendTo {
-    pub fn send_to() -> Self {
-        Self::from(CliSendTo::Beneficiary(Default::default()))
+    pub fn send_to() -> color_eyre::eyre::Result<Self> {
+        Self::try_from(CliSendTo::Beneficiary(Default::default()))
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (command, mut args) = match self {
+            Self::Beneficiary(delete_account_action) => {
+                ("beneficiary", delete_account_action.to_cli_args())
+            }
+        };
+        args.insert(0, command.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             SendTo::Beneficiary(delete_account_action) => {
                 delete_account_action
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
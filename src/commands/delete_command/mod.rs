@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::{TryFrom, TryInto};
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 mod access_key;
@@ -16,22 +17,31 @@ pub struct DeleteAction {
     pub action: Action,
 }
 
-impl From<CliDeleteAction> for DeleteAction {
-    fn from(item: CliDeleteAction) -> Self {
+impl TryFrom<CliDeleteAction> for DeleteAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliDeleteAction) -> color_eyre::eyre::Result<Self> {
         let action = match item.action {
-            Some(cli_action) => cli_action.into(),
-            None => Action::choose_action(),
+            Some(cli_action) => cli_action.try_into()?,
+            None => Action::choose_action()?,
         };
-        Self { action }
+        Ok(Self { action })
     }
 }
 
 impl DeleteAction {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.action.to_cli_args()
+    }
+
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
-        self.action.process(prepopulated_unsigned_transaction).await
+        self.action
+            .process(prepopulated_unsigned_transaction, output_format)
+            .await
     }
 }
 
@@ -55,21 +65,26 @@ pub enum Action {
     // SubAccount,
 }
 
-impl From<CliAction> for Action {
-    fn from(item: CliAction) -> Self {
-        match item {
+impl TryFrom<CliAction> for Action {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliAction) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliAction::AccessKey(cli_operation_mode) => {
-                Action::AccessKey(cli_operation_mode.into())
+                Action::AccessKey(cli_operation_mode.try_into()?)
             }
-            CliAction::Account(cli_operation_mode) => Action::Account(cli_operation_mode.into()),
-        }
+            CliAction::Account(cli_operation_mode) => {
+                Action::Account(cli_operation_mode.try_into()?)
+            }
+        })
     }
 }
 
 impl Action {
-    fn choose_action() -> Self {
+    fn choose_action() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = ActionDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let actions = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -84,22 +99,32 @@ impl Action {
             ActionDiscriminants::AccessKey => CliAction::AccessKey(Default::default()),
             ActionDiscriminants::Account => CliAction::Account(Default::default()),
         };
-        Self::from(cli_action)
+        Self::try_from(cli_action)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (action, mut args) = match self {
+            Self::AccessKey(operation_mode) => ("access-key", operation_mode.to_cli_args()),
+            Self::Account(operation_mode) => ("account", operation_mode.to_cli_args()),
+        };
+        args.insert(0, action.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             Action::AccessKey(operation_mode) => {
                 operation_mode
-                    .process(prepopulated_unsigned_transaction)
+                    .process(prepopulated_unsigned_transaction, output_format)
                     .await
             }
             Action::Account(operation_mode) => {
                 operation_mode
-                    .process(prepopulated_unsigned_transaction)
+                    .process(prepopulated_unsigned_transaction, output_format)
                     .await
             }
         }
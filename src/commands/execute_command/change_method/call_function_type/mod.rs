@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 /// вызов CallFunction
 #[derive(Debug, Default, clap::Clap)]
@@ -22,50 +23,54 @@ pub struct CallFunctionAction {
     send_from: super::sender::SendFrom,
 }
 
-impl From<CliCallFunctionAction> for CallFunctionAction {
-    fn from(item: CliCallFunctionAction) -> Self {
+impl TryFrom<CliCallFunctionAction> for CallFunctionAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliCallFunctionAction) -> color_eyre::eyre::Result<Self> {
         let method_name: String = match item.method_name {
             Some(cli_method_name) => cli_method_name,
-            None => CallFunctionAction::input_method_name(),
+            None => CallFunctionAction::input_method_name()?,
         };
         let args: Vec<u8> = match item.args {
             Some(cli_args) => cli_args.into_bytes(),
-            None => CallFunctionAction::input_args(),
+            None => CallFunctionAction::input_args()?,
         };
         let gas: near_primitives::types::Gas = match item.gas {
             Some(cli_gas) => match cli_gas {
                 crate::common::NearGas { inner: num } => num,
             },
-            None => CallFunctionAction::input_gas(),
+            None => CallFunctionAction::input_gas()?,
         };
         let deposit: near_primitives::types::Balance = match item.deposit {
             Some(cli_deposit) => cli_deposit.to_yoctonear(),
-            None => CallFunctionAction::input_deposit(),
+            None => CallFunctionAction::input_deposit()?,
         };
         let send_from = match item.send_from {
-            Some(cli_send_from) => super::sender::SendFrom::from(cli_send_from),
-            None => super::sender::SendFrom::choose_send_from(),
+            Some(cli_send_from) => super::sender::SendFrom::try_from(cli_send_from)?,
+            None => super::sender::SendFrom::choose_send_from()?,
         };
-        Self {
+        Ok(Self {
             method_name,
             args,
             gas,
             deposit,
             send_from,
-        }
+        })
     }
 }
 
 impl CallFunctionAction {
-    fn input_method_name() -> String {
+    fn input_method_name() -> color_eyre::eyre::Result<String> {
+        crate::common::ensure_interactive("`<METHOD_NAME>` of `call`")?;
         println!();
-        Input::new()
+        Ok(Input::new()
             .with_prompt("Enter a method name")
             .interact_text()
-            .unwrap()
+            .unwrap())
     }
 
-    fn input_gas() -> near_primitives::types::Gas {
+    fn input_gas() -> color_eyre::eyre::Result<near_primitives::types::Gas> {
+        crate::common::ensure_interactive("`--prepaid-gas <PREPAID_GAS>` of `call`")?;
         println!();
         let gas: u64 = loop {
             let input_gas: crate::common::NearGas = Input::new()
@@ -82,19 +87,21 @@ impl CallFunctionAction {
                 println!("You need to enter a value of no more than 200 TERAGAS")
             }
         };
-        gas
+        Ok(gas)
     }
 
-    fn input_args() -> Vec<u8> {
+    fn input_args() -> color_eyre::eyre::Result<Vec<u8>> {
+        crate::common::ensure_interactive("`<ARGS>` of `call`")?;
         println!();
         let input: String = Input::new()
             .with_prompt("Enter args for function")
             .interact_text()
             .unwrap();
-        input.into_bytes()
+        Ok(input.into_bytes())
     }
 
-    fn input_deposit() -> near_primitives::types::Balance {
+    fn input_deposit() -> color_eyre::eyre::Result<near_primitives::types::Balance> {
+        crate::common::ensure_interactive("`--attached-deposit <ATTACHED_DEPOSIT>` of `call`")?;
         println!();
         let deposit: crate::common::NearBalance = Input::new()
             .with_prompt(
@@ -103,13 +110,27 @@ impl CallFunctionAction {
             .with_initial_text("0 NEAR")
             .interact_text()
             .unwrap();
-        deposit.to_yoctonear()
+        Ok(deposit.to_yoctonear())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![
+            self.method_name.clone(),
+            String::from_utf8_lossy(&self.args).into_owned(),
+            "--attached-deposit".to_owned(),
+            crate::common::NearBalance::from_yoctonear(self.deposit).to_cli_arg(),
+            "--prepaid-gas".to_owned(),
+            crate::common::NearGas { inner: self.gas }.to_cli_arg(),
+        ];
+        args.extend(self.send_from.to_cli_args());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let action = near_primitives::transaction::Action::FunctionCall(
             near_primitives::transaction::FunctionCallAction {
@@ -126,7 +147,11 @@ impl CallFunctionAction {
             ..prepopulated_unsigned_transaction
         };
         self.send_from
-            .process(unsigned_transaction, network_connection_config)
+            .process(
+                unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::{TryFrom, TryInto};
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 mod call_function_type;
@@ -19,20 +20,23 @@ pub enum CallFunction {
     Call(self::call_function_type::CallFunctionAction),
 }
 
-impl From<CliCallFunction> for CallFunction {
-    fn from(item: CliCallFunction) -> Self {
-        match item {
+impl TryFrom<CliCallFunction> for CallFunction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliCallFunction) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliCallFunction::Call(cli_call_function_action) => {
-                CallFunction::Call(cli_call_function_action.into())
+                CallFunction::Call(cli_call_function_action.try_into()?)
             }
-        }
+        })
     }
 }
 
 impl CallFunction {
-    fn choose_call_function() -> Self {
+    fn choose_call_function() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = CallFunctionDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let commands = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -46,18 +50,31 @@ impl CallFunction {
         let cli_call = match variants[selection] {
             CallFunctionDiscriminants::Call => CliCallFunction::Call(Default::default()),
         };
-        Self::from(cli_call)
+        Self::try_from(cli_call)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (call, mut args) = match self {
+            Self::Call(call_function_action) => ("call", call_function_action.to_cli_args()),
+        };
+        args.insert(0, call.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             Self::Call(call_function_action) => {
                 call_function_action
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
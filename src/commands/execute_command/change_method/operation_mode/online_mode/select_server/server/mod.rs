@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 /// предустановленный RPC-сервер
 #[derive(Debug, Default, clap::Clap)]
@@ -16,6 +17,16 @@ pub struct CliCustomServer {
     send_to: Option<super::super::super::super::receiver::CliSendTo>,
 }
 
+/// A network of the configuration file or a built-in one, selected by its name
+#[derive(Debug, Default, clap::Clap)]
+pub struct CliNamedServer {
+    /// The name of the network (~/.config/near-cli/config.toml)
+    #[clap(long)]
+    pub network: Option<crate::common::ConnectionConfig>,
+    #[clap(flatten)]
+    pub server: CliServer,
+}
+
 #[derive(Debug)]
 pub struct Server {
     pub network_connection_config: Option<crate::common::ConnectionConfig>,
@@ -23,49 +34,74 @@ pub struct Server {
 }
 
 impl CliServer {
-    pub fn into_server(self, network_connection_config: crate::common::ConnectionConfig) -> Server {
+    pub fn into_server(
+        self,
+        network_connection_config: crate::common::ConnectionConfig,
+    ) -> color_eyre::eyre::Result<Server> {
         let send_to = match self.send_to {
-            Some(cli_send_to) => super::super::super::super::receiver::SendTo::from(cli_send_to),
-            None => super::super::super::super::receiver::SendTo::send_to(),
+            Some(cli_send_to) => {
+                super::super::super::super::receiver::SendTo::try_from(cli_send_to)?
+            }
+            None => super::super::super::super::receiver::SendTo::send_to()?,
         };
-        Server {
+        Ok(Server {
             network_connection_config: Some(network_connection_config),
             send_to,
-        }
+        })
     }
 }
 
 impl CliCustomServer {
-    pub fn into_server(self) -> Server {
+    pub fn into_server(self) -> color_eyre::eyre::Result<Server> {
         let url: crate::common::AvailableRpcServerUrl = match self.url {
             Some(url) => url,
-            None => Input::new()
-                .with_prompt("What is the RPC endpoint?")
-                .interact_text()
-                .unwrap(),
+            None => {
+                crate::common::ensure_interactive("`--url <URL>` of `custom`")?;
+                Input::new()
+                    .with_prompt("What is the RPC endpoint?")
+                    .interact_text()
+                    .unwrap()
+            }
         };
+        let connection_config = crate::common::ConnectionConfig::Custom { url: url.inner };
         let send_to = match self.send_to {
-            Some(cli_send_to) => super::super::super::super::receiver::SendTo::from(cli_send_to),
-            None => super::super::super::super::receiver::SendTo::send_to(),
+            Some(cli_send_to) => {
+                super::super::super::super::receiver::SendTo::try_from(cli_send_to)?
+            }
+            None => super::super::super::super::receiver::SendTo::send_to()?,
         };
-        Server {
-            network_connection_config: Some(crate::common::ConnectionConfig::Custom {
-                url: url.inner,
-            }),
+        Ok(Server {
+            network_connection_config: Some(connection_config),
             send_to,
-        }
+        })
+    }
+}
+
+impl CliNamedServer {
+    pub fn into_server(self) -> color_eyre::eyre::Result<Server> {
+        let connection_config = match self.network {
+            Some(connection_config) => connection_config,
+            None => crate::config::choose_network()?,
+        };
+        self.server.into_server(connection_config)
     }
 }
 
 impl Server {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.send_to.to_cli_args()
+    }
+
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         self.send_to
             .process(
                 prepopulated_unsigned_transaction,
                 self.network_connection_config,
+                output_format,
             )
             .await
     }
@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::{TryFrom, TryInto};
 
 #[derive(Debug, clap::Clap)]
 pub enum CliSendTo {
@@ -11,31 +12,46 @@ pub enum SendTo {
     Contract(Receiver),
 }
 
-impl From<CliSendTo> for SendTo {
-    fn from(item: CliSendTo) -> Self {
-        match item {
+impl TryFrom<CliSendTo> for SendTo {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSendTo) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliSendTo::Contract(cli_receiver) => {
-                let receiver = Receiver::from(cli_receiver);
+                let receiver = Receiver::try_from(cli_receiver)?;
                 Self::Contract(receiver)
             }
-        }
+        })
     }
 }
 
 impl SendTo {
-    pub fn send_to() -> Self {
-        Self::from(CliSendTo::Contract(Default::default()))
+    pub fn send_to() -> color_eyre::eyre::Result<Self> {
+        Self::try_from(CliSendTo::Contract(Default::default()))
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (command, mut args) = match self {
+            Self::Contract(receiver) => ("contract", receiver.to_cli_args()),
+        };
+        args.insert(0, command.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             SendTo::Contract(receiver) => {
                 receiver
-                    .process(prepopulated_unsigned_transaction, network_connection_config)
+                    .process(
+                        prepopulated_unsigned_transaction,
+                        network_connection_config,
+                        output_format,
+                    )
                     .await
             }
         }
@@ -56,42 +72,56 @@ pub struct Receiver {
     pub call: super::CallFunction,
 }
 
-impl From<CliReceiver> for Receiver {
-    fn from(item: CliReceiver) -> Self {
+impl TryFrom<CliReceiver> for Receiver {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliReceiver) -> color_eyre::eyre::Result<Self> {
         let receiver_account_id: String = match item.receiver_account_id {
             Some(cli_receiver_account_id) => cli_receiver_account_id,
-            None => Receiver::input_receiver_account_id(),
+            None => Receiver::input_receiver_account_id()?,
         };
         let call = match item.call {
-            Some(cli_call) => cli_call.into(),
-            None => super::CallFunction::choose_call_function(),
+            Some(cli_call) => cli_call.try_into()?,
+            None => super::CallFunction::choose_call_function()?,
         };
-        Self {
+        Ok(Self {
             receiver_account_id,
             call,
-        }
+        })
     }
 }
 
 impl Receiver {
-    pub fn input_receiver_account_id() -> String {
-        Input::new()
+    pub fn input_receiver_account_id() -> color_eyre::eyre::Result<String> {
+        crate::common::ensure_interactive("`<RECEIVER_ACCOUNT_ID>` of `contract`")?;
+        Ok(Input::new()
             .with_prompt("What is the account ID of the contract?")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![self.receiver_account_id.clone()];
+        args.extend(self.call.to_cli_args());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
         network_connection_config: Option<crate::common::ConnectionConfig>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let unsigned_transaction = near_primitives::transaction::Transaction {
             receiver_id: self.receiver_account_id.clone(),
             ..prepopulated_unsigned_transaction
         };
         self.call
-            .process(unsigned_transaction, network_connection_config)
+            .process(
+                unsigned_transaction,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::{TryFrom, TryInto};
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 mod change_method;
@@ -16,22 +17,31 @@ pub struct OptionMethod {
     method: Method,
 }
 
-impl From<CliOptionMethod> for OptionMethod {
-    fn from(item: CliOptionMethod) -> Self {
+impl TryFrom<CliOptionMethod> for OptionMethod {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliOptionMethod) -> color_eyre::eyre::Result<Self> {
         let method = match item.method {
-            Some(cli_method) => Method::from(cli_method),
-            None => Method::choose_method(),
+            Some(cli_method) => Method::try_from(cli_method)?,
+            None => Method::choose_method()?,
         };
-        Self { method }
+        Ok(Self { method })
     }
 }
 
 impl OptionMethod {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.method.to_cli_args()
+    }
+
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
-        self.method.process(prepopulated_unsigned_transaction).await
+        self.method
+            .process(prepopulated_unsigned_transaction, output_format)
+            .await
     }
 }
 
@@ -52,23 +62,26 @@ enum Method {
     ViewMethod(self::view_method::operation_mode::OperationMode),
 }
 
-impl From<CliMethod> for Method {
-    fn from(item: CliMethod) -> Self {
-        match item {
+impl TryFrom<CliMethod> for Method {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliMethod) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliMethod::ChangeMethod(cli_operation_mode) => {
-                Method::ChangeMethod(cli_operation_mode.into())
+                Method::ChangeMethod(cli_operation_mode.try_into()?)
             }
             CliMethod::ViewMethod(cli_operation_mode) => {
-                Method::ViewMethod(cli_operation_mode.into())
+                Method::ViewMethod(cli_operation_mode.try_into()?)
             }
-        }
+        })
     }
 }
 
 impl Method {
-    fn choose_method() -> Self {
+    fn choose_method() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = MethodDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let methods = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -83,17 +96,27 @@ impl Method {
             MethodDiscriminants::ChangeMethod => CliMethod::ChangeMethod(Default::default()),
             MethodDiscriminants::ViewMethod => CliMethod::ViewMethod(Default::default()),
         };
-        Self::from(cli_method)
+        Self::try_from(cli_method)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (method, mut args) = match self {
+            Self::ChangeMethod(operation_mode) => ("change-method", operation_mode.to_cli_args()),
+            Self::ViewMethod(operation_mode) => ("view-method", operation_mode.to_cli_args()),
+        };
+        args.insert(0, method.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             Self::ChangeMethod(operation_mode) => {
                 operation_mode
-                    .process(prepopulated_unsigned_transaction)
+                    .process(prepopulated_unsigned_transaction, output_format)
                     .await
             }
             Self::ViewMethod(operation_mode) => operation_mode.process().await,
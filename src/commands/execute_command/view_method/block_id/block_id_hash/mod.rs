@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 /// Specify the block_id hash for this contract to view
 #[derive(Debug, Default, clap::Clap)]
@@ -11,26 +12,29 @@ pub struct BlockIdHash {
     block_id_hash: near_primitives::hash::CryptoHash,
 }
 
-impl From<CliBlockIdHash> for BlockIdHash {
-    fn from(item: CliBlockIdHash) -> Self {
+impl TryFrom<CliBlockIdHash> for BlockIdHash {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliBlockIdHash) -> color_eyre::eyre::Result<Self> {
         let block_id_hash: near_primitives::hash::CryptoHash = match item.block_id_hash {
             Some(cli_block_id_hash) => cli_block_id_hash,
-            None => BlockIdHash::input_block_id_hash(),
+            None => BlockIdHash::input_block_id_hash()?,
         };
-        Self { block_id_hash }
+        Ok(Self { block_id_hash })
     }
 }
 
 impl BlockIdHash {
-    pub fn input_block_id_hash() -> near_primitives::hash::CryptoHash {
-        Input::new()
+    pub fn input_block_id_hash() -> color_eyre::eyre::Result<near_primitives::hash::CryptoHash> {
+        crate::common::ensure_interactive("`<BLOCK_ID_HASH>` of `at-block-hash`")?;
+        Ok(Input::new()
             .with_prompt("Type the block ID hash for this contract")
             .interact_text()
-            .unwrap()
+            .unwrap())
     }
 
-    fn rpc_client(&self, selected_server_url: &str) -> near_jsonrpc_client::JsonRpcClient {
-        near_jsonrpc_client::new_client(&selected_server_url)
+    pub fn to_cli_args(&self) -> Vec<String> {
+        vec![self.block_id_hash.to_string()]
     }
 
     pub async fn process(
@@ -42,8 +46,8 @@ impl BlockIdHash {
     ) -> crate::CliResult {
         let args: near_primitives::types::FunctionArgs =
             near_primitives::types::FunctionArgs::from(args);
-        let query_view_method_response = self
-            .rpc_client(network_connection_config.archival_rpc_url().as_str())
+        let query_view_method_response = network_connection_config
+            .archival_rpc_client()
             .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
                 block_reference: near_primitives::types::BlockReference::BlockId(
                     near_primitives::types::BlockId::Hash(self.block_id_hash.clone()),
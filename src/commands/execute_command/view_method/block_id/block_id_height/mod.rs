@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 /// Specify the block_id height for this contract to view
 #[derive(Debug, Default, clap::Clap)]
@@ -11,26 +12,30 @@ pub struct BlockIdHeight {
     block_id_height: near_primitives::types::BlockHeight,
 }
 
-impl From<CliBlockIdHeight> for BlockIdHeight {
-    fn from(item: CliBlockIdHeight) -> Self {
+impl TryFrom<CliBlockIdHeight> for BlockIdHeight {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliBlockIdHeight) -> color_eyre::eyre::Result<Self> {
         let block_id_height: near_primitives::types::BlockHeight = match item.block_id_height {
             Some(cli_block_id_hash) => cli_block_id_hash,
-            None => BlockIdHeight::input_block_id_height(),
+            None => BlockIdHeight::input_block_id_height()?,
         };
-        Self { block_id_height }
+        Ok(Self { block_id_height })
     }
 }
 
 impl BlockIdHeight {
-    pub fn input_block_id_height() -> near_primitives::types::BlockHeight {
-        Input::new()
+    pub fn input_block_id_height() -> color_eyre::eyre::Result<near_primitives::types::BlockHeight>
+    {
+        crate::common::ensure_interactive("`<BLOCK_ID_HEIGHT>` of `at-block-height`")?;
+        Ok(Input::new()
             .with_prompt("Type the block ID height for this contract")
             .interact_text()
-            .unwrap()
+            .unwrap())
     }
 
-    fn rpc_client(&self, selected_server_url: &str) -> near_jsonrpc_client::JsonRpcClient {
-        near_jsonrpc_client::new_client(&selected_server_url)
+    pub fn to_cli_args(&self) -> Vec<String> {
+        vec![self.block_id_height.to_string()]
     }
 
     pub async fn process(
@@ -42,8 +47,8 @@ impl BlockIdHeight {
     ) -> crate::CliResult {
         let args: near_primitives::types::FunctionArgs =
             near_primitives::types::FunctionArgs::from(args);
-        let query_view_method_response = self
-            .rpc_client(network_connection_config.archival_rpc_url().as_str())
+        let query_view_method_response = network_connection_config
+            .archival_rpc_client()
             .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
                 block_reference: near_primitives::types::BlockReference::BlockId(
                     near_primitives::types::BlockId::Height(self.block_id_height.clone()),
@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::{TryFrom, TryInto};
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 mod block_id_hash;
@@ -25,24 +26,27 @@ pub enum BlockId {
     AtBlockHash(self::block_id_hash::BlockIdHash),
 }
 
-impl From<CliBlockId> for BlockId {
-    fn from(item: CliBlockId) -> Self {
-        match item {
+impl TryFrom<CliBlockId> for BlockId {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliBlockId) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliBlockId::AtFinalBlock => Self::AtFinalBlock,
             CliBlockId::AtBlockHeight(cli_block_id_height) => {
-                Self::AtBlockHeight(cli_block_id_height.into())
+                Self::AtBlockHeight(cli_block_id_height.try_into()?)
             }
             CliBlockId::AtBlockHash(cli_block_id_hash) => {
-                Self::AtBlockHash(cli_block_id_hash.into())
+                Self::AtBlockHash(cli_block_id_hash.try_into()?)
             }
-        }
+        })
     }
 }
 
 impl BlockId {
-    pub fn choose_block_id() -> Self {
+    pub fn choose_block_id() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = BlockIdDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let blocks = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -58,7 +62,19 @@ impl BlockId {
             BlockIdDiscriminants::AtBlockHeight => CliBlockId::AtBlockHeight(Default::default()),
             BlockIdDiscriminants::AtBlockHash => CliBlockId::AtBlockHash(Default::default()),
         };
-        Self::from(cli_block_id)
+        Self::try_from(cli_block_id)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (block_id, mut args) = match self {
+            Self::AtFinalBlock => ("at-final-block", vec![]),
+            Self::AtBlockHeight(block_id_height) => {
+                ("at-block-height", block_id_height.to_cli_args())
+            }
+            Self::AtBlockHash(block_id_hash) => ("at-block-hash", block_id_hash.to_cli_args()),
+        };
+        args.insert(0, block_id.to_owned());
+        args
     }
 
     pub async fn process(
@@ -102,10 +118,6 @@ impl BlockId {
         }
     }
 
-    fn rpc_client(&self, selected_server_url: &str) -> near_jsonrpc_client::JsonRpcClient {
-        near_jsonrpc_client::new_client(&selected_server_url)
-    }
-
     async fn at_final_block(
         self,
         network_connection_config: crate::common::ConnectionConfig,
@@ -115,8 +127,8 @@ impl BlockId {
     ) -> crate::CliResult {
         let args: near_primitives::types::FunctionArgs =
             near_primitives::types::FunctionArgs::from(args);
-        let query_view_method_response = self
-            .rpc_client(network_connection_config.rpc_url().as_str())
+        let query_view_method_response = network_connection_config
+            .rpc_client()
             .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
                 block_reference: near_primitives::types::Finality::Final.into(),
                 request: near_primitives::views::QueryRequest::CallFunction {
@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::{TryFrom, TryInto};
 
 /// Call view function
 #[derive(Debug, Default, clap::Clap)]
@@ -16,44 +17,57 @@ pub struct CallFunctionView {
     selected_block_id: super::block_id::BlockId,
 }
 
-impl From<CliCallFunctionView> for CallFunctionView {
-    fn from(item: CliCallFunctionView) -> Self {
+impl TryFrom<CliCallFunctionView> for CallFunctionView {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliCallFunctionView) -> color_eyre::eyre::Result<Self> {
         let method_name: String = match item.method_name {
             Some(cli_method_name) => cli_method_name,
-            None => CallFunctionView::input_method_name(),
+            None => CallFunctionView::input_method_name()?,
         };
         let args: Vec<u8> = match item.args {
             Some(cli_args) => cli_args.into_bytes(),
-            None => CallFunctionView::input_args(),
+            None => CallFunctionView::input_args()?,
         };
         let selected_block_id: super::block_id::BlockId = match item.selected_block_id {
-            Some(cli_block_id) => cli_block_id.into(),
-            None => super::block_id::BlockId::choose_block_id(),
+            Some(cli_block_id) => cli_block_id.try_into()?,
+            None => super::block_id::BlockId::choose_block_id()?,
         };
-        Self {
+        Ok(Self {
             method_name,
             args,
             selected_block_id,
-        }
+        })
     }
 }
 
 impl CallFunctionView {
-    fn input_method_name() -> String {
+    fn input_method_name() -> color_eyre::eyre::Result<String> {
+        crate::common::ensure_interactive("`<METHOD_NAME>` of `call`")?;
         println!();
-        Input::new()
+        Ok(Input::new()
             .with_prompt("Enter a method name")
             .interact_text()
-            .unwrap()
+            .unwrap())
     }
 
-    fn input_args() -> Vec<u8> {
+    fn input_args() -> color_eyre::eyre::Result<Vec<u8>> {
+        crate::common::ensure_interactive("`<ARGS>` of `call`")?;
         println!();
         let input: String = Input::new()
             .with_prompt("Enter args for function")
             .interact_text()
             .unwrap();
-        input.into_bytes()
+        Ok(input.into_bytes())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![
+            self.method_name.clone(),
+            String::from_utf8_lossy(&self.args).into_owned(),
+        ];
+        args.extend(self.selected_block_id.to_cli_args());
+        args
     }
 
     pub async fn process(
@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::{TryFrom, TryInto};
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 mod block_id;
@@ -19,20 +20,23 @@ pub enum CallFunction {
     Call(self::call_function_type::CallFunctionView),
 }
 
-impl From<CliCallFunction> for CallFunction {
-    fn from(item: CliCallFunction) -> Self {
-        match item {
+impl TryFrom<CliCallFunction> for CallFunction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliCallFunction) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliCallFunction::Call(cli_call_function_view) => {
-                CallFunction::Call(cli_call_function_view.into())
+                CallFunction::Call(cli_call_function_view.try_into()?)
             }
-        }
+        })
     }
 }
 
 impl CallFunction {
-    pub fn choose_call_function() -> Self {
+    pub fn choose_call_function() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = CallFunctionDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let commands = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -46,7 +50,15 @@ impl CallFunction {
         let cli_call = match variants[selection] {
             CallFunctionDiscriminants::Call => CliCallFunction::Call(Default::default()),
         };
-        Self::from(cli_call)
+        Self::try_from(cli_call)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (call, mut args) = match self {
+            Self::Call(call_function_view) => ("call", call_function_view.to_cli_args()),
+        };
+        args.insert(0, call.to_owned());
+        args
     }
 
     pub async fn process(
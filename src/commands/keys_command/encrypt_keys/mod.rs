@@ -0,0 +1,88 @@
+use std::convert::TryFrom;
+
+/// Encrypt the plaintext keys with a passphrase (the entries are converted in place)
+#[derive(Debug, Default, clap::Clap)]
+pub struct CliEncryptKeys {
+    /// Only encrypt the keys of this network (testnet, mainnet, betanet, localnet, default or a network of the configuration file)
+    #[clap(long)]
+    network: Option<crate::key_store::KeyChainNetwork>,
+    /// Only encrypt the keys of this account
+    #[clap(long)]
+    account_id: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct EncryptKeys {
+    pub networks: Vec<crate::key_store::KeyChainNetwork>,
+    pub account_id: Option<String>,
+}
+
+impl TryFrom<CliEncryptKeys> for EncryptKeys {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliEncryptKeys) -> color_eyre::eyre::Result<Self> {
+        let networks = match item.network {
+            Some(network) => vec![network],
+            None => crate::key_store::KeyChainNetwork::all(),
+        };
+        Ok(Self {
+            networks,
+            account_id: item.account_id,
+        })
+    }
+}
+
+impl EncryptKeys {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        // Without `--network` the keys of all the keychains are encrypted
+        let mut args = match self.networks.as_slice() {
+            [network] => vec!["--network".to_owned(), network.name().to_owned()],
+            _ => vec![],
+        };
+        if let Some(account_id) = &self.account_id {
+            args.push("--account-id".to_owned());
+            args.push(account_id.clone());
+        }
+        args
+    }
+
+    pub async fn process(self, key_store: &dyn crate::key_store::KeyStore) -> crate::CliResult {
+        self.encrypt(key_store, super::input_new_passphrase)
+    }
+
+    pub(super) fn encrypt(
+        self,
+        key_store: &dyn crate::key_store::KeyStore,
+        input_passphrase: impl FnOnce() -> color_eyre::eyre::Result<String>,
+    ) -> crate::CliResult {
+        let mut plaintext_keys = vec![];
+        for network in &self.networks {
+            for (location, stored_key) in key_store.list(network)? {
+                plaintext_keys.push((network, location, stored_key));
+            }
+        }
+        let plaintext_keys = plaintext_keys
+            .into_iter()
+            .filter(|(_, _, stored_key)| !stored_key.is_encrypted())
+            .filter(|(_, _, stored_key)| match &self.account_id {
+                Some(account_id) => &stored_key.account_id == account_id,
+                None => true,
+            })
+            .collect::<Vec<_>>();
+        if plaintext_keys.is_empty() {
+            println!("There are no plaintext keys to encrypt");
+            return Ok(());
+        }
+        println!("The following keys will be encrypted:");
+        for (_, location, _) in &plaintext_keys {
+            println!("   {}", location);
+        }
+        let passphrase = input_passphrase()?;
+        for (network, _, stored_key) in plaintext_keys {
+            let encrypted_key = stored_key.encrypt(&passphrase)?;
+            let location = key_store.update(network, &encrypted_key)?;
+            println!("Encrypted {}", location);
+        }
+        Ok(())
+    }
+}
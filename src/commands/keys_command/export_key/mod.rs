@@ -0,0 +1,89 @@
+use std::convert::TryFrom;
+
+/// Print a stored key pair (including the secret key)
+#[derive(Debug, Default, clap::Clap)]
+pub struct CliExportKey {
+    /// The keychain network (testnet, mainnet, betanet, localnet, default or a network of the configuration file)
+    #[clap(long)]
+    network: Option<crate::key_store::KeyChainNetwork>,
+    /// The account whose key should be exported
+    #[clap(long)]
+    account_id: Option<String>,
+    /// The public key of the key to be exported, when the account has several keys
+    #[clap(long)]
+    public_key: Option<near_crypto::PublicKey>,
+}
+
+#[derive(Debug)]
+pub struct ExportKey {
+    pub network: crate::key_store::KeyChainNetwork,
+    pub account_id: Option<String>,
+    pub public_key: Option<near_crypto::PublicKey>,
+}
+
+impl TryFrom<CliExportKey> for ExportKey {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliExportKey) -> color_eyre::eyre::Result<Self> {
+        let network = match item.network {
+            Some(network) => network,
+            None => crate::key_store::KeyChainNetwork::choose_network()?,
+        };
+        Ok(Self {
+            network,
+            account_id: item.account_id,
+            public_key: item.public_key,
+        })
+    }
+}
+
+impl ExportKey {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec!["--network".to_owned(), self.network.name().to_owned()];
+        if let Some(account_id) = &self.account_id {
+            args.push("--account-id".to_owned());
+            args.push(account_id.clone());
+        }
+        if let Some(public_key) = &self.public_key {
+            args.push("--public-key".to_owned());
+            args.push(public_key.to_string());
+        }
+        args
+    }
+
+    pub async fn process(
+        self,
+        key_store: &dyn crate::key_store::KeyStore,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::CliResult {
+        let (location, stored_key) = super::select_stored_key(
+            key_store,
+            &self.network,
+            self.account_id,
+            self.public_key.as_ref(),
+        )?;
+        let stored_key = stored_key.unlock()?;
+        let private_key = stored_key.secret_key()?;
+        match output_format {
+            crate::common::OutputFormat::Plaintext => {
+                println!(
+                    "\nLocation: {}\nAccount ID: {}\nPublic Key: {}\nSECRET KEYPAIR: {}",
+                    location, stored_key.account_id, stored_key.public_key, private_key,
+                );
+                if let (Some(master_seed_phrase), Some(seed_phrase_hd_path)) = (
+                    &stored_key.master_seed_phrase,
+                    &stored_key.seed_phrase_hd_path,
+                ) {
+                    println!(
+                        "Master Seed Phrase: {}\nSeed Phrase HD Path: {}",
+                        master_seed_phrase, seed_phrase_hd_path
+                    );
+                }
+            }
+            crate::common::OutputFormat::Json => {
+                println!("{}", serde_json::to_string_pretty(&stored_key)?);
+            }
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,333 @@
+use dialoguer::{theme::ColorfulTheme, Input, Password, Select};
+use std::convert::{TryFrom, TryInto};
+use std::str::FromStr;
+use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
+
+/// Import a key into the keychain
+#[derive(Debug, Default, clap::Clap)]
+pub struct CliImportKey {
+    /// The keychain network (testnet, mainnet, betanet, localnet, default or a network of the configuration file)
+    #[clap(long)]
+    network: Option<crate::key_store::KeyChainNetwork>,
+    /// Encrypt the key file with a passphrase
+    #[clap(long)]
+    encrypt: bool,
+    #[clap(subcommand)]
+    source: Option<CliKeySource>,
+}
+
+#[derive(Debug)]
+pub struct ImportKey {
+    pub network: crate::key_store::KeyChainNetwork,
+    pub encrypt: bool,
+    pub source: KeySource,
+}
+
+impl TryFrom<CliImportKey> for ImportKey {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliImportKey) -> color_eyre::eyre::Result<Self> {
+        let network = match item.network {
+            Some(network) => network,
+            None => crate::key_store::KeyChainNetwork::choose_network()?,
+        };
+        let source = match item.source {
+            Some(cli_source) => KeySource::try_from(cli_source)?,
+            None => KeySource::choose_source()?,
+        };
+        Ok(Self {
+            network,
+            encrypt: item.encrypt,
+            source,
+        })
+    }
+}
+
+impl ImportKey {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec!["--network".to_owned(), self.network.name().to_owned()];
+        if self.encrypt {
+            args.push("--encrypt".to_owned());
+        }
+        args.extend(self.source.to_cli_args());
+        args
+    }
+
+    pub async fn process(self, key_store: &dyn crate::key_store::KeyStore) -> crate::CliResult {
+        let mut stored_key = self.source.into_stored_key().await?;
+        if self.encrypt {
+            stored_key = stored_key.encrypt(&super::input_new_passphrase()?)?;
+        }
+        let other_keys = key_store.find(&self.network, &stored_key.account_id)?;
+        if let Some((location, _)) = other_keys
+            .iter()
+            .find(|(_, other_key)| other_key.public_key == stored_key.public_key)
+        {
+            return Err(color_eyre::Report::msg(format!(
+                "The key {} of <{}> is stored already ({})",
+                stored_key.public_key, stored_key.account_id, location
+            )));
+        }
+        let location = key_store.save(&self.network, &stored_key)?;
+        println!(
+            "The key {} of <{}> is saved in {}",
+            stored_key.public_key, stored_key.account_id, location
+        );
+        if !other_keys.is_empty() {
+            println!(
+                "It is the preferred key of <{}> now; the other {} key(s) of the account are kept",
+                stored_key.account_id,
+                other_keys.len()
+            );
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, clap::Clap)]
+pub enum CliKeySource {
+    /// Import a secret key
+    SecretKey(CliSecretKey),
+    /// Derive the key from a seed phrase
+    SeedPhrase(CliSeedPhrase),
+}
+
+#[derive(Debug, EnumDiscriminants)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+pub enum KeySource {
+    #[strum_discriminants(strum(message = "Import a secret key"))]
+    SecretKey(SecretKey),
+    #[strum_discriminants(strum(message = "Derive the key from a seed phrase"))]
+    SeedPhrase(SeedPhrase),
+}
+
+impl TryFrom<CliKeySource> for KeySource {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliKeySource) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
+            CliKeySource::SecretKey(cli_secret_key) => Self::SecretKey(cli_secret_key.try_into()?),
+            CliKeySource::SeedPhrase(cli_seed_phrase) => {
+                Self::SeedPhrase(cli_seed_phrase.try_into()?)
+            }
+        })
+    }
+}
+
+impl KeySource {
+    pub fn choose_source() -> color_eyre::eyre::Result<Self> {
+        println!();
+        let variants = KeySourceDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
+        let sources = variants
+            .iter()
+            .map(|p| p.get_message().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        let selected_source = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Where does the key come from?")
+            .items(&sources)
+            .default(0)
+            .interact()
+            .unwrap();
+        let cli_source = match variants[selected_source] {
+            KeySourceDiscriminants::SecretKey => CliKeySource::SecretKey(Default::default()),
+            KeySourceDiscriminants::SeedPhrase => CliKeySource::SeedPhrase(Default::default()),
+        };
+        Self::try_from(cli_source)
+    }
+
+    async fn into_stored_key(self) -> color_eyre::eyre::Result<crate::key_store::StoredKey> {
+        match self {
+            Self::SecretKey(secret_key) => Ok(crate::key_store::StoredKey {
+                account_id: secret_key.account_id,
+                public_key: secret_key.secret_key.public_key(),
+                private_key: Some(secret_key.secret_key),
+                master_seed_phrase: None,
+                seed_phrase_hd_path: None,
+                encrypted_secrets: None,
+            }),
+            Self::SeedPhrase(seed_phrase) => {
+                let key_pair_properties = crate::common::generate_keypair(
+                    Some(&seed_phrase.master_seed_phrase),
+                    12,
+                    seed_phrase.seed_phrase_hd_path,
+                )
+                .await?;
+                let account_id = match seed_phrase.account_id {
+                    Some(account_id) => account_id,
+                    None => key_pair_properties.implicit_account_id,
+                };
+                Ok(crate::key_store::StoredKey {
+                    account_id,
+                    public_key: near_crypto::PublicKey::from_str(
+                        &key_pair_properties.public_key_str,
+                    )?,
+                    private_key: Some(near_crypto::SecretKey::from_str(
+                        &key_pair_properties.secret_keypair_str,
+                    )?),
+                    master_seed_phrase: Some(key_pair_properties.master_seed_phrase),
+                    seed_phrase_hd_path: Some(
+                        crate::commands::utils_command::generate_keypair_subcommand::bip32path_to_string(
+                            &key_pair_properties.seed_phrase_hd_path,
+                        ),
+                    ),
+                    encrypted_secrets: None,
+                })
+            }
+        }
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (command, mut args) = match self {
+            Self::SecretKey(secret_key) => ("secret-key", secret_key.to_cli_args()),
+            Self::SeedPhrase(seed_phrase) => ("seed-phrase", seed_phrase.to_cli_args()),
+        };
+        args.insert(0, command.to_owned());
+        args
+    }
+}
+
+/// Import a secret key
+#[derive(Debug, Default, clap::Clap)]
+pub struct CliSecretKey {
+    #[clap(long)]
+    account_id: Option<String>,
+    #[clap(long)]
+    secret_key: Option<near_crypto::SecretKey>,
+}
+
+#[derive(Debug)]
+pub struct SecretKey {
+    pub account_id: String,
+    pub secret_key: near_crypto::SecretKey,
+}
+
+impl TryFrom<CliSecretKey> for SecretKey {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSecretKey) -> color_eyre::eyre::Result<Self> {
+        let account_id = match item.account_id {
+            Some(account_id) => account_id,
+            None => {
+                crate::common::ensure_interactive("`--account-id <ACCOUNT_ID>` of `secret-key`")?;
+                Input::new()
+                    .with_prompt("What is the account ID of the key?")
+                    .interact_text()
+                    .unwrap()
+            }
+        };
+        let secret_key = match item.secret_key {
+            Some(secret_key) => secret_key,
+            None => {
+                crate::common::ensure_interactive("`--secret-key <SECRET_KEY>` of `secret-key`")?;
+                crate::common::input_secret_key("Enter the secret key")
+            }
+        };
+        Ok(Self {
+            account_id,
+            secret_key,
+        })
+    }
+}
+
+impl SecretKey {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        vec![
+            "--account-id".to_owned(),
+            self.account_id.clone(),
+            "--secret-key".to_owned(),
+            self.secret_key.to_string(),
+        ]
+    }
+}
+
+/// Derive the key from a seed phrase
+#[derive(Debug, clap::Clap)]
+pub struct CliSeedPhrase {
+    /// The account ID of the key (the implicit account ID is used when omitted)
+    #[clap(long)]
+    account_id: Option<String>,
+    #[clap(long)]
+    master_seed_phrase: Option<String>,
+    #[clap(long, default_value = "m/44'/397'/0'")]
+    seed_phrase_hd_path: slip10::BIP32Path,
+}
+
+impl Default for CliSeedPhrase {
+    fn default() -> Self {
+        Self {
+            account_id: None,
+            master_seed_phrase: None,
+            seed_phrase_hd_path: slip10::BIP32Path::from_str("m/44'/397'/0'").unwrap(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SeedPhrase {
+    pub account_id: Option<String>,
+    pub master_seed_phrase: String,
+    pub seed_phrase_hd_path: slip10::BIP32Path,
+}
+
+impl TryFrom<CliSeedPhrase> for SeedPhrase {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSeedPhrase) -> color_eyre::eyre::Result<Self> {
+        let master_seed_phrase = match item.master_seed_phrase {
+            Some(master_seed_phrase) => master_seed_phrase,
+            None => {
+                crate::common::ensure_interactive(
+                    "`--master-seed-phrase <MASTER_SEED_PHRASE>` of `seed-phrase`",
+                )?;
+                Password::new()
+                    .with_prompt("Enter the master seed phrase")
+                    .interact()
+                    .unwrap()
+            }
+        };
+        let account_id = match item.account_id {
+            Some(account_id) => Some(account_id),
+            // The implicit account ID unless `--account-id` is given
+            None if crate::common::is_non_interactive() => None,
+            None => {
+                let account_id: String = Input::new()
+                    .with_prompt(
+                        "What is the account ID of the key? (leave empty for the implicit account ID)",
+                    )
+                    .allow_empty(true)
+                    .interact_text()
+                    .unwrap();
+                if account_id.is_empty() {
+                    None
+                } else {
+                    Some(account_id)
+                }
+            }
+        };
+        Ok(Self {
+            account_id,
+            master_seed_phrase,
+            seed_phrase_hd_path: item.seed_phrase_hd_path,
+        })
+    }
+}
+
+impl SeedPhrase {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if let Some(account_id) = &self.account_id {
+            args.push("--account-id".to_owned());
+            args.push(account_id.clone());
+        }
+        args.push("--master-seed-phrase".to_owned());
+        args.push(self.master_seed_phrase.clone());
+        args.push("--seed-phrase-hd-path".to_owned());
+        args.push(
+            crate::commands::utils_command::generate_keypair_subcommand::bip32path_to_string(
+                &self.seed_phrase_hd_path,
+            ),
+        );
+        args
+    }
+}
@@ -0,0 +1,117 @@
+use std::convert::TryFrom;
+
+/// List the accounts stored in the keychain
+#[derive(Debug, Default, clap::Clap)]
+pub struct CliListKeys {
+    /// Only list the keys of this network (testnet, mainnet, betanet, localnet, default or a network of the configuration file)
+    #[clap(long)]
+    network: Option<crate::key_store::KeyChainNetwork>,
+    /// Only list the entries of this public key
+    #[clap(long)]
+    public_key: Option<near_crypto::PublicKey>,
+}
+
+#[derive(Debug)]
+pub struct ListKeys {
+    pub networks: Vec<crate::key_store::KeyChainNetwork>,
+    pub public_key: Option<near_crypto::PublicKey>,
+}
+
+impl TryFrom<CliListKeys> for ListKeys {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliListKeys) -> color_eyre::eyre::Result<Self> {
+        let networks = match item.network {
+            Some(network) => vec![network],
+            None => crate::key_store::KeyChainNetwork::all(),
+        };
+        Ok(Self {
+            networks,
+            public_key: item.public_key,
+        })
+    }
+}
+
+impl ListKeys {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        // Without `--network` all the keychains are listed
+        let mut args = match self.networks.as_slice() {
+            [network] => vec!["--network".to_owned(), network.name().to_owned()],
+            _ => vec![],
+        };
+        if let Some(public_key) = &self.public_key {
+            args.push("--public-key".to_owned());
+            args.push(public_key.to_string());
+        }
+        args
+    }
+
+    fn stored_keys(
+        &self,
+        key_store: &dyn crate::key_store::KeyStore,
+        network: &crate::key_store::KeyChainNetwork,
+    ) -> color_eyre::eyre::Result<Vec<(String, crate::key_store::StoredKey)>> {
+        let mut stored_keys = key_store.list(network)?;
+        if let Some(public_key) = &self.public_key {
+            stored_keys.retain(|(_, stored_key)| &stored_key.public_key == public_key);
+        }
+        Ok(stored_keys)
+    }
+
+    pub async fn process(
+        self,
+        key_store: &dyn crate::key_store::KeyStore,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::CliResult {
+        match output_format {
+            crate::common::OutputFormat::Plaintext => {
+                for network in &self.networks {
+                    let stored_keys = self.stored_keys(key_store, network)?;
+                    println!("\n{} ({}):", network.name(), key_store.location(network));
+                    if stored_keys.is_empty() {
+                        println!("   no keys");
+                    }
+                    for (index, (_, stored_key)) in stored_keys.iter().enumerate() {
+                        println!(
+                            "   {:>2}. {} {}{}",
+                            index + 1,
+                            stored_key.account_id,
+                            stored_key.public_key,
+                            if stored_key.is_encrypted() {
+                                " (encrypted)"
+                            } else {
+                                ""
+                            }
+                        );
+                    }
+                }
+            }
+            crate::common::OutputFormat::Json => {
+                let networks = self
+                    .networks
+                    .iter()
+                    .map(|network| {
+                        let stored_keys = self
+                            .stored_keys(key_store, network)?
+                            .into_iter()
+                            .map(|(location, stored_key)| {
+                                serde_json::json!({
+                                    "account_id": stored_key.account_id,
+                                    "public_key": stored_key.public_key,
+                                    "encrypted": stored_key.is_encrypted(),
+                                    "location": location,
+                                })
+                            })
+                            .collect::<Vec<_>>();
+                        Ok((
+                            network.name().to_owned(),
+                            serde_json::Value::from(stored_keys),
+                        ))
+                    })
+                    .collect::<color_eyre::eyre::Result<serde_json::Map<_, _>>>()?;
+                println!("{}", serde_json::to_string_pretty(&networks)?);
+            }
+        };
+        Ok(())
+    }
+}
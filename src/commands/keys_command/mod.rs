@@ -0,0 +1,358 @@
+use dialoguer::{theme::ColorfulTheme, Password, Select};
+use std::convert::{TryFrom, TryInto};
+use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
+
+mod encrypt_keys;
+mod export_key;
+mod import_key;
+mod list_keys;
+mod remove_key;
+
+/// Manage the keys stored in ~/.near-credentials
+#[derive(Debug, Default, clap::Clap)]
+pub struct CliKeys {
+    /// Where the keys are kept: file (default) or secret-service
+    #[clap(long)]
+    key_store: Option<crate::key_store::KeyStoreKind>,
+    #[clap(subcommand)]
+    action: Option<CliKeysAction>,
+}
+
+#[derive(Debug)]
+pub struct Keys {
+    pub key_store: crate::key_store::KeyStoreKind,
+    pub action: KeysAction,
+}
+
+impl TryFrom<CliKeys> for Keys {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliKeys) -> color_eyre::eyre::Result<Self> {
+        let action = match item.action {
+            Some(cli_action) => KeysAction::try_from(cli_action)?,
+            None => KeysAction::choose_action()?,
+        };
+        Ok(Self {
+            key_store: item.key_store.unwrap_or_default(),
+            action,
+        })
+    }
+}
+
+impl Keys {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if self.key_store != crate::key_store::KeyStoreKind::default() {
+            args.push("--key-store".to_owned());
+            args.push(<&str>::from(self.key_store).to_owned());
+        }
+        args.extend(self.action.to_cli_args());
+        args
+    }
+
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::CliResult {
+        self.action.process(self.key_store, output_format).await
+    }
+}
+
+#[derive(Debug, clap::Clap)]
+pub enum CliKeysAction {
+    /// List the accounts stored in the keychain
+    List(self::list_keys::CliListKeys),
+    /// Import a key from a secret key or a seed phrase
+    Import(self::import_key::CliImportKey),
+    /// Print a stored key pair
+    Export(self::export_key::CliExportKey),
+    /// Remove a stored key pair
+    Remove(self::remove_key::CliRemoveKey),
+    /// Encrypt the plaintext key files with a passphrase
+    Encrypt(self::encrypt_keys::CliEncryptKeys),
+}
+
+#[derive(Debug, EnumDiscriminants)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+pub enum KeysAction {
+    #[strum_discriminants(strum(message = "List the accounts stored in the keychain"))]
+    List(self::list_keys::ListKeys),
+    #[strum_discriminants(strum(message = "Import a key from a secret key or a seed phrase"))]
+    Import(self::import_key::ImportKey),
+    #[strum_discriminants(strum(message = "Print a stored key pair"))]
+    Export(self::export_key::ExportKey),
+    #[strum_discriminants(strum(message = "Remove a stored key pair"))]
+    Remove(self::remove_key::RemoveKey),
+    #[strum_discriminants(strum(message = "Encrypt the plaintext key files with a passphrase"))]
+    Encrypt(self::encrypt_keys::EncryptKeys),
+}
+
+impl TryFrom<CliKeysAction> for KeysAction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliKeysAction) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
+            CliKeysAction::List(cli_list_keys) => Self::List(cli_list_keys.try_into()?),
+            CliKeysAction::Import(cli_import_key) => Self::Import(cli_import_key.try_into()?),
+            CliKeysAction::Export(cli_export_key) => Self::Export(cli_export_key.try_into()?),
+            CliKeysAction::Remove(cli_remove_key) => Self::Remove(cli_remove_key.try_into()?),
+            CliKeysAction::Encrypt(cli_encrypt_keys) => Self::Encrypt(cli_encrypt_keys.try_into()?),
+        })
+    }
+}
+
+impl KeysAction {
+    pub fn choose_action() -> color_eyre::eyre::Result<Self> {
+        println!();
+        let variants = KeysActionDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
+        let actions = variants
+            .iter()
+            .map(|p| p.get_message().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        let selected_action = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Choose your action")
+            .items(&actions)
+            .default(0)
+            .interact()
+            .unwrap();
+        let cli_action = match variants[selected_action] {
+            KeysActionDiscriminants::List => CliKeysAction::List(Default::default()),
+            KeysActionDiscriminants::Import => CliKeysAction::Import(Default::default()),
+            KeysActionDiscriminants::Export => CliKeysAction::Export(Default::default()),
+            KeysActionDiscriminants::Remove => CliKeysAction::Remove(Default::default()),
+            KeysActionDiscriminants::Encrypt => CliKeysAction::Encrypt(Default::default()),
+        };
+        Self::try_from(cli_action)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (command, mut args) = match self {
+            Self::List(list_keys) => ("list", list_keys.to_cli_args()),
+            Self::Import(import_key) => ("import", import_key.to_cli_args()),
+            Self::Export(export_key) => ("export", export_key.to_cli_args()),
+            Self::Remove(remove_key) => ("remove", remove_key.to_cli_args()),
+            Self::Encrypt(encrypt_keys) => ("encrypt", encrypt_keys.to_cli_args()),
+        };
+        args.insert(0, command.to_owned());
+        args
+    }
+
+    pub async fn process(
+        self,
+        key_store: crate::key_store::KeyStoreKind,
+        output_format: crate::common::OutputFormat,
+    ) -> crate::CliResult {
+        let key_store = key_store.open()?;
+        match self {
+            Self::List(list_keys) => list_keys.process(&*key_store, output_format).await,
+            Self::Import(import_key) => import_key.process(&*key_store).await,
+            Self::Export(export_key) => export_key.process(&*key_store, output_format).await,
+            Self::Remove(remove_key) => remove_key.process(&*key_store).await,
+            Self::Encrypt(encrypt_keys) => encrypt_keys.process(&*key_store).await,
+        }
+    }
+}
+
+/// Asks for a new passphrase to encrypt keys with
+pub fn input_new_passphrase() -> color_eyre::eyre::Result<String> {
+    crate::common::ensure_interactive("a new passphrase")?;
+    Ok(Password::with_theme(&ColorfulTheme::default())
+        .with_prompt("Enter a passphrase to encrypt the keys")
+        .with_confirmation("Repeat the passphrase", "The passphrases do not match")
+        .interact()?)
+}
+
+/// Picks a stored key of the account (with the public key, if given),
+/// asking the user when there are several of them
+pub fn select_stored_key(
+    key_store: &dyn crate::key_store::KeyStore,
+    network: &crate::key_store::KeyChainNetwork,
+    account_id: Option<String>,
+    public_key: Option<&near_crypto::PublicKey>,
+) -> color_eyre::eyre::Result<(String, crate::key_store::StoredKey)> {
+    let mut stored_keys = match &account_id {
+        Some(account_id) => key_store.find(network, account_id)?,
+        None => key_store.list(network)?,
+    };
+    if let Some(public_key) = public_key {
+        stored_keys.retain(|(_, stored_key)| &stored_key.public_key == public_key);
+    }
+    match stored_keys.len() {
+        0 => Err(color_eyre::Report::msg(format!(
+            "There are no keys{}{} in {}",
+            account_id
+                .map(|account_id| format!(" for <{}>", account_id))
+                .unwrap_or_default(),
+            public_key
+                .map(|public_key| format!(" with the public key {}", public_key))
+                .unwrap_or_default(),
+            key_store.location(network)
+        ))),
+        1 => Ok(stored_keys.remove(0)),
+        _ => {
+            crate::common::ensure_interactive(&match account_id {
+                Some(account_id) => format!(
+                    "`--public-key <PUBLIC_KEY>` to choose one of the keys of <{}>",
+                    account_id
+                ),
+                None => "`--account-id <ACCOUNT_ID>`".to_string(),
+            })?;
+            println!();
+            let items = stored_keys
+                .iter()
+                .map(|(_, stored_key)| {
+                    format!("{} ({})", stored_key.account_id, stored_key.public_key)
+                })
+                .collect::<Vec<_>>();
+            let selected_key = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Select the key")
+                .items(&items)
+                .default(0)
+                .interact()
+                .unwrap();
+            Ok(stored_keys.remove(selected_key))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import_key(
+        network: &crate::key_store::KeyChainNetwork,
+        account_id: &str,
+    ) -> (self::import_key::ImportKey, near_crypto::SecretKey) {
+        let secret_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519);
+        let import_key = self::import_key::ImportKey {
+            network: network.clone(),
+            encrypt: false,
+            source: self::import_key::KeySource::SecretKey(self::import_key::SecretKey {
+                account_id: account_id.to_string(),
+                secret_key: secret_key.clone(),
+            }),
+        };
+        (import_key, secret_key)
+    }
+
+    #[test]
+    fn keys_commands_file_store_round_trip() {
+        // An absolute directory name replaces the home directory in `key_chain_dir`
+        let dir =
+            std::env::temp_dir().join(format!("near-cli-keys-command-{}", std::process::id()));
+        let network = crate::key_store::KeyChainNetwork::new("test", dir.to_str().unwrap());
+        let key_store = crate::key_store::KeyStoreKind::File.open().unwrap();
+        let key_store = &*key_store;
+
+        actix::System::new().block_on(async {
+            // Importing a second key of the account keeps the first one
+            let (first_import, first_secret_key) = import_key(&network, "alice.testnet");
+            first_import.process(key_store).await.unwrap();
+            let (second_import, second_secret_key) = import_key(&network, "alice.testnet");
+            second_import.process(key_store).await.unwrap();
+            let stored_keys = key_store.find(&network, "alice.testnet").unwrap();
+            assert_eq!(
+                stored_keys
+                    .iter()
+                    .map(|(_, stored_key)| stored_key.public_key.clone())
+                    .collect::<Vec<_>>(),
+                vec![
+                    second_secret_key.public_key(),
+                    first_secret_key.public_key()
+                ]
+            );
+
+            // The same key is not imported twice
+            let again_import = self::import_key::ImportKey {
+                network: network.clone(),
+                encrypt: false,
+                source: self::import_key::KeySource::SecretKey(self::import_key::SecretKey {
+                    account_id: "alice.testnet".to_string(),
+                    secret_key: first_secret_key.clone(),
+                }),
+            };
+            assert!(again_import.process(key_store).await.is_err());
+
+            // `--public-key` picks one of the keys of the account and filters the list
+            let (_, selected_key) = select_stored_key(
+                key_store,
+                &network,
+                Some("alice.testnet".to_string()),
+                Some(&first_secret_key.public_key()),
+            )
+            .unwrap();
+            assert_eq!(selected_key.private_key, Some(first_secret_key.clone()));
+            assert!(select_stored_key(
+                key_store,
+                &network,
+                Some("bob.testnet".to_string()),
+                Some(&first_secret_key.public_key()),
+            )
+            .is_err());
+            let list_keys = self::list_keys::ListKeys {
+                networks: vec![network.clone()],
+                public_key: Some(second_secret_key.public_key()),
+            };
+            assert_eq!(
+                list_keys
+                    .to_cli_args()
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>(),
+                vec![
+                    "--network",
+                    "test",
+                    "--public-key",
+                    &second_secret_key.public_key().to_string()
+                ]
+            );
+            list_keys
+                .process(key_store, crate::common::OutputFormat::Json)
+                .await
+                .unwrap();
+
+            self::export_key::ExportKey {
+                network: network.clone(),
+                account_id: Some("alice.testnet".to_string()),
+                public_key: Some(first_secret_key.public_key()),
+            }
+            .process(key_store, crate::common::OutputFormat::Json)
+            .await
+            .unwrap();
+
+            // `keys encrypt` converts the entries in place
+            self::encrypt_keys::EncryptKeys {
+                networks: vec![network.clone()],
+                account_id: Some("alice.testnet".to_string()),
+            }
+            .encrypt(key_store, || Ok("passphrase".to_string()))
+            .unwrap();
+            let stored_keys = key_store.find(&network, "alice.testnet").unwrap();
+            assert_eq!(stored_keys.len(), 2);
+            for (_, stored_key) in stored_keys {
+                assert!(stored_key.is_encrypted());
+                assert!(stored_key.private_key.is_none());
+                let decrypted_key = stored_key.decrypt("passphrase").unwrap();
+                assert!(
+                    decrypted_key.private_key == Some(first_secret_key.clone())
+                        || decrypted_key.private_key == Some(second_secret_key.clone())
+                );
+            }
+
+            // `--yes` removes the key without a confirmation prompt
+            crate::common::set_assume_yes(true);
+            self::remove_key::RemoveKey {
+                network: network.clone(),
+                account_id: Some("alice.testnet".to_string()),
+                public_key: Some(second_secret_key.public_key()),
+            }
+            .process(key_store)
+            .await
+            .unwrap();
+            crate::common::set_assume_yes(false);
+            let stored_keys = key_store.find(&network, "alice.testnet").unwrap();
+            assert_eq!(stored_keys.len(), 1);
+            assert_eq!(stored_keys[0].1.public_key, first_secret_key.public_key());
+        });
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,80 @@
+use dialoguer::{theme::ColorfulTheme, Confirm};
+use std::convert::TryFrom;
+
+/// Remove a stored key pair
+#[derive(Debug, Default, clap::Clap)]
+pub struct CliRemoveKey {
+    /// The keychain network (testnet, mainnet, betanet, localnet, default or a network of the configuration file)
+    #[clap(long)]
+    network: Option<crate::key_store::KeyChainNetwork>,
+    /// The account whose key should be removed
+    #[clap(long)]
+    account_id: Option<String>,
+    /// The public key of the key to be removed, when the account has several keys
+    #[clap(long)]
+    public_key: Option<near_crypto::PublicKey>,
+}
+
+#[derive(Debug)]
+pub struct RemoveKey {
+    pub network: crate::key_store::KeyChainNetwork,
+    pub account_id: Option<String>,
+    pub public_key: Option<near_crypto::PublicKey>,
+}
+
+impl TryFrom<CliRemoveKey> for RemoveKey {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliRemoveKey) -> color_eyre::eyre::Result<Self> {
+        let network = match item.network {
+            Some(network) => network,
+            None => crate::key_store::KeyChainNetwork::choose_network()?,
+        };
+        Ok(Self {
+            network,
+            account_id: item.account_id,
+            public_key: item.public_key,
+        })
+    }
+}
+
+impl RemoveKey {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec!["--network".to_owned(), self.network.name().to_owned()];
+        if let Some(account_id) = &self.account_id {
+            args.push("--account-id".to_owned());
+            args.push(account_id.clone());
+        }
+        if let Some(public_key) = &self.public_key {
+            args.push("--public-key".to_owned());
+            args.push(public_key.to_string());
+        }
+        args
+    }
+
+    pub async fn process(self, key_store: &dyn crate::key_store::KeyStore) -> crate::CliResult {
+        let (location, stored_key) = super::select_stored_key(
+            key_store,
+            &self.network,
+            self.account_id,
+            self.public_key.as_ref(),
+        )?;
+        if !crate::common::is_assume_yes() {
+            crate::common::ensure_interactive("the confirmation to remove the key (`--yes`)")?;
+            let confirmed = Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!(
+                    "Remove the key {} of <{}> ({})? It cannot be restored unless you have a backup.",
+                    stored_key.public_key, stored_key.account_id, location
+                ))
+                .default(false)
+                .interact()?;
+            if !confirmed {
+                println!("The key was not removed");
+                return Ok(());
+            }
+        }
+        let location = key_store.remove(&self.network, &stored_key)?;
+        println!("The key {} was removed", location);
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::TryFrom;
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 pub mod server;
@@ -13,6 +14,8 @@ pub enum CliSelectServer {
     Betanet(self::server::CliServer),
     /// предоставление данных для сервера, указанного вручную
     Custom(self::server::CliCustomServer),
+    /// A network of the configuration file (~/.config/near-cli/config.toml) selected by its name
+    Named(self::server::CliNamedServer),
 }
 
 #[derive(Debug, EnumDiscriminants)]
@@ -26,31 +29,74 @@ pub enum SelectServer {
     Betanet(self::server::Server),
     #[strum_discriminants(strum(message = "Custom"))]
     Custom(self::server::Server),
+    #[strum_discriminants(strum(message = "A network of the configuration file"))]
+    Named(self::server::Server),
 }
 
-impl From<CliSelectServer> for SelectServer {
-    fn from(item: CliSelectServer) -> Self {
-        match item {
+impl TryFrom<CliSelectServer> for SelectServer {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSelectServer) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliSelectServer::Testnet(cli_server) => {
-                Self::Testnet(cli_server.into_server(crate::common::ConnectionConfig::Testnet))
+                Self::Testnet(cli_server.into_server(crate::common::ConnectionConfig::Testnet)?)
             }
             CliSelectServer::Mainnet(cli_server) => {
-                Self::Mainnet(cli_server.into_server(crate::common::ConnectionConfig::Mainnet))
+                Self::Mainnet(cli_server.into_server(crate::common::ConnectionConfig::Mainnet)?)
             }
             CliSelectServer::Betanet(cli_server) => {
-                Self::Betanet(cli_server.into_server(crate::common::ConnectionConfig::Betanet))
+                Self::Betanet(cli_server.into_server(crate::common::ConnectionConfig::Betanet)?)
             }
             CliSelectServer::Custom(cli_custom_server) => {
-                Self::Custom(cli_custom_server.into_server())
+                Self::Custom(cli_custom_server.into_server()?)
             }
-        }
+            CliSelectServer::Named(cli_named_server) => {
+                Self::Named(cli_named_server.into_server()?)
+            }
+        })
     }
 }
 
 impl SelectServer {
-    pub fn choose_server() -> Self {
+    /// The server of the network of `--network` (or, without it, of the default network of the
+    /// configuration file) with the options and subcommands of a server subcommand
+    pub fn from_network(
+        network: Option<crate::common::ConnectionConfig>,
+        server: self::server::CliServer,
+    ) -> color_eyre::eyre::Result<Self> {
+        let network = match network {
+            Some(network) => Some(network),
+            None => crate::config::default_network(),
+        };
+        Ok(Self::Named(
+            self::server::CliNamedServer { network, server }.into_server()?,
+        ))
+    }
+
+    /// `--network` takes the place of a server subcommand
+    pub fn from_cli(
+        network: Option<crate::common::ConnectionConfig>,
+        cli_select_server: Option<CliSelectServer>,
+    ) -> color_eyre::eyre::Result<Self> {
+        match (network, cli_select_server) {
+            (None, Some(cli_select_server)) => Self::try_from(cli_select_server),
+            (None, None) => Self::choose_server(),
+            (Some(network), None) => Self::from_network(Some(network), Default::default()),
+            (Some(_), Some(_)) => Err(color_eyre::Report::msg(
+                "`--network` selects the server, so it cannot be combined with testnet, mainnet, betanet, localnet, custom or named",
+            )),
+        }
+    }
+
+    pub fn choose_server() -> color_eyre::eyre::Result<Self> {
+        if let Some(connection_config) = crate::config::default_network() {
+            let mut cli_named_server = self::server::CliNamedServer::default();
+            cli_named_server.network = Some(connection_config);
+            return Self::try_from(CliSelectServer::Named(cli_named_server));
+        }
         println!();
         let variants = SelectServerDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let servers = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -66,8 +112,25 @@ impl SelectServer {
             SelectServerDiscriminants::Mainnet => CliSelectServer::Mainnet(Default::default()),
             SelectServerDiscriminants::Betanet => CliSelectServer::Betanet(Default::default()),
             SelectServerDiscriminants::Custom => CliSelectServer::Custom(Default::default()),
+            SelectServerDiscriminants::Named => CliSelectServer::Named(Default::default()),
+        };
+        Self::try_from(cli_select_server)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (server_name, server) = match self {
+            Self::Testnet(server) => ("testnet", server),
+            Self::Mainnet(server) => ("mainnet", server),
+            Self::Betanet(server) => ("betanet", server),
+            Self::Custom(server) => ("custom", server),
+            Self::Named(server) => ("named", server),
         };
-        Self::from(cli_select_server)
+        let mut args = vec![server_name.to_owned()];
+        if let Self::Custom(_) | Self::Named(_) = self {
+            args.extend(server.connection_config.to_cli_args());
+        }
+        args.extend(server.to_cli_args());
+        args
     }
 
     pub async fn process(self) -> crate::CliResult {
@@ -84,6 +147,9 @@ impl SelectServer {
             SelectServer::Custom(server) => {
                 server.process().await?;
             }
+            SelectServer::Named(server) => {
+                server.process().await?;
+            }
         })
     }
 }
@@ -1,47 +1,115 @@
-use std::io::Write;
 use std::str::FromStr;
 
 use dialoguer::Input;
 use url_open::UrlOpen;
 
+use crate::wallet_callback::DEFAULT_CALLBACK_TIMEOUT_SECS;
+
 /// предустановленный RPC-сервер
 #[derive(Debug, Default, clap::Clap)]
-pub struct CliServer {}
+pub struct CliServer {
+    /// Where the keys are kept: file (default) or secret-service
+    #[clap(long)]
+    pub key_store: Option<crate::key_store::KeyStoreKind>,
+    /// How long to wait for the wallet to redirect back to near-cli before asking for the
+    /// account ID (300 seconds by default; 0 asks right away)
+    #[clap(long)]
+    pub callback_timeout_secs: Option<u64>,
+}
 
 /// данные для custom server
 #[derive(Debug, Default, clap::Clap)]
 pub struct CliCustomServer {
     #[clap(long)]
     pub url: Option<url::Url>,
+    /// Where the keys are kept: file (default) or secret-service
+    #[clap(long)]
+    pub key_store: Option<crate::key_store::KeyStoreKind>,
+    /// How long to wait for the wallet to redirect back to near-cli before asking for the
+    /// account ID (300 seconds by default; 0 asks right away)
+    #[clap(long)]
+    pub callback_timeout_secs: Option<u64>,
+}
+
+/// A network of the configuration file or a built-in one, selected by its name
+#[derive(Debug, Default, clap::Clap)]
+pub struct CliNamedServer {
+    /// The name of the network (~/.config/near-cli/config.toml)
+    #[clap(long)]
+    pub network: Option<crate::common::ConnectionConfig>,
+    #[clap(flatten)]
+    pub server: CliServer,
 }
 
 #[derive(Debug)]
 pub struct Server {
     pub connection_config: crate::common::ConnectionConfig,
+    pub key_store: crate::key_store::KeyStoreKind,
+    pub callback_timeout_secs: u64,
 }
 
 impl CliServer {
-    pub fn into_server(self, connection_config: crate::common::ConnectionConfig) -> Server {
-        Server { connection_config }
+    pub fn into_server(
+        self,
+        connection_config: crate::common::ConnectionConfig,
+    ) -> color_eyre::eyre::Result<Server> {
+        Ok(Server {
+            connection_config,
+            key_store: self.key_store.unwrap_or_default(),
+            callback_timeout_secs: self
+                .callback_timeout_secs
+                .unwrap_or(DEFAULT_CALLBACK_TIMEOUT_SECS),
+        })
     }
 }
 
 impl CliCustomServer {
-    pub fn into_server(self) -> Server {
+    pub fn into_server(self) -> color_eyre::eyre::Result<Server> {
         let url: url::Url = match self.url {
             Some(url) => url,
-            None => Input::new()
-                .with_prompt("What is the wallet url?")
-                .interact_text()
-                .unwrap(),
+            None => {
+                crate::common::ensure_interactive("`--url <URL>` of `custom`")?;
+                Input::new()
+                    .with_prompt("What is the wallet url?")
+                    .interact_text()
+                    .unwrap()
+            }
         };
-        Server {
-            connection_config: crate::common::ConnectionConfig::Custom { url },
-        }
+        let connection_config = crate::common::ConnectionConfig::Custom { url };
+        Ok(Server {
+            connection_config,
+            key_store: self.key_store.unwrap_or_default(),
+            callback_timeout_secs: self
+                .callback_timeout_secs
+                .unwrap_or(DEFAULT_CALLBACK_TIMEOUT_SECS),
+        })
+    }
+}
+
+impl CliNamedServer {
+    pub fn into_server(self) -> color_eyre::eyre::Result<Server> {
+        let connection_config = match self.network {
+            Some(connection_config) => connection_config,
+            None => crate::config::choose_network()?,
+        };
+        self.server.into_server(connection_config)
     }
 }
 
 impl Server {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if self.key_store != crate::key_store::KeyStoreKind::default() {
+            args.push("--key-store".to_owned());
+            args.push(<&str>::from(self.key_store).to_owned());
+        }
+        if self.callback_timeout_secs != DEFAULT_CALLBACK_TIMEOUT_SECS {
+            args.push("--callback-timeout-secs".to_owned());
+            args.push(self.callback_timeout_secs.to_string());
+        }
+        args
+    }
+
     pub async fn process(self) -> crate::CliResult {
         let generate_keypair: crate::commands::utils_command::generate_keypair_subcommand::CliGenerateKeypair =
             crate::commands::utils_command::generate_keypair_subcommand::CliGenerateKeypair::default();
@@ -54,12 +122,37 @@ impl Server {
             )
             .await?;
 
-        let mut url: url::Url = self.connection_config.wallet_url().join("login/")?;
+        let mut url: url::Url = self
+            .connection_config
+            .wallet_url()
+            .ok_or_else(|| {
+                color_eyre::Report::msg(
+                    "There is no wallet for this network, use `keys import` to add a key instead",
+                )
+            })?
+            .join("login/")?;
         url.query_pairs_mut()
             .append_pair("title", "NEAR CLI")
             .append_pair("public_key", &key_pair_properties.public_key_str);
-        // Use `success_url` once capture mode is implemented
-        //.append_pair("success_url", "http://127.0.0.1:8080");
+        let callback_listener = if self.callback_timeout_secs > 0 {
+            match crate::wallet_callback::CallbackListener::start() {
+                Ok(callback_listener) => {
+                    url.query_pairs_mut()
+                        .append_pair("success_url", callback_listener.success_url().as_str())
+                        .append_pair("failure_url", callback_listener.failure_url().as_str());
+                    Some(callback_listener)
+                }
+                Err(err) => {
+                    eprintln!(
+                        "Failed to listen for the wallet redirect ({}), the account ID will be asked for",
+                        err
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
         println!(
             "If your browser doesn't automatically open, please visit this URL:\n {}\n",
             &url.as_str()
@@ -69,9 +162,36 @@ impl Server {
         let public_key: near_crypto::PublicKey =
             near_crypto::PublicKey::from_str(&key_pair_properties.public_key_str)?;
 
-        let account_id = get_account_from_cli(public_key, self.connection_config.clone()).await?;
+        let captured_account_id = match callback_listener {
+            Some(callback_listener) => redirected_account_id(
+                callback_listener
+                    .wait(std::time::Duration::from_secs(self.callback_timeout_secs))
+                    .await,
+                &key_pair_properties.public_key_str,
+            )?,
+            None => None,
+        };
+        let account_id = match captured_account_id {
+            Some(account_id) => {
+                verify_account_id(
+                    account_id.clone(),
+                    public_key,
+                    self.connection_config.clone(),
+                )
+                .await
+                .map_err(|err| color_eyre::Report::msg(format!("Failed account ID: {:?}", err)))?;
+                account_id
+            }
+            None => get_account_from_cli(public_key, self.connection_config.clone()).await?,
+        };
         if !account_id.is_empty() {
-            save_account(&account_id, key_pair_properties, self.connection_config).await?
+            save_account(
+                &account_id,
+                key_pair_properties,
+                self.connection_config,
+                self.key_store,
+            )
+            .await?
         };
         Ok(())
     }
@@ -81,22 +201,50 @@ async fn get_account_from_cli(
     public_key: near_crypto::PublicKey,
     network_connection_config: crate::common::ConnectionConfig,
 ) -> color_eyre::eyre::Result<String> {
-    let account_id = input_account_id();
+    let account_id = input_account_id()?;
     verify_account_id(account_id.clone(), public_key, network_connection_config)
         .await
         .map_err(|err| color_eyre::Report::msg(format!("Failed account ID: {:?}", err)))?;
     Ok(account_id)
 }
 
-fn input_account_id() -> String {
-    Input::new()
+fn input_account_id() -> color_eyre::eyre::Result<String> {
+    crate::common::ensure_interactive("the account ID authorized in the wallet")?;
+    Ok(Input::new()
         .with_prompt("Enter account ID")
         .interact_text()
-        .unwrap()
+        .unwrap())
 }
 
-fn rpc_client(selected_server_url: &str) -> near_jsonrpc_client::JsonRpcClient {
-    near_jsonrpc_client::new_client(&selected_server_url)
+/// The account ID of an approved redirect with the key `public_key` added; `None` makes the
+/// account ID be asked for (no redirect in time or no account ID in it)
+fn redirected_account_id(
+    redirect: Option<crate::wallet_callback::WalletRedirect>,
+    public_key: &str,
+) -> color_eyre::eyre::Result<Option<String>> {
+    match redirect {
+        Some(crate::wallet_callback::WalletRedirect::Approved(query)) => {
+            match (query.get("account_id"), query.get("public_key")) {
+                (Some(account_id), Some(redirected_public_key)) => {
+                    if redirected_public_key != public_key {
+                        return Err(color_eyre::Report::msg(format!(
+                            "The wallet added the key {} instead of {}",
+                            redirected_public_key, public_key
+                        )));
+                    }
+                    Ok(Some(account_id.clone()))
+                }
+                _ => {
+                    eprintln!("The wallet did not pass `account_id` and `public_key`");
+                    Ok(None)
+                }
+            }
+        }
+        Some(crate::wallet_callback::WalletRedirect::Rejected(reason)) => Err(
+            color_eyre::Report::msg(format!("The wallet did not authorize the key: {}", reason)),
+        ),
+        None => Ok(None),
+    }
 }
 
 async fn verify_account_id(
@@ -104,7 +252,8 @@ async fn verify_account_id(
     public_key: near_crypto::PublicKey,
     network_connection_config: crate::common::ConnectionConfig,
 ) -> crate::CliResult {
-    rpc_client(network_connection_config.rpc_url().as_str())
+    network_connection_config
+        .rpc_client()
         .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
             block_reference: near_primitives::types::Finality::Final.into(),
             request: near_primitives::views::QueryRequest::ViewAccessKey {
@@ -126,32 +275,91 @@ async fn save_account(
     account_id: &str,
     key_pair_properties: crate::common::KeyPairProperties,
     network_connection_config: crate::common::ConnectionConfig,
+    key_store: crate::key_store::KeyStoreKind,
 ) -> crate::CliResult {
-    let buf = format!(
-        "{}",
-        serde_json::json!({
-        "account_id": account_id,
-        "public_key": key_pair_properties.public_key_str.clone(),
-        "private_key": key_pair_properties.secret_keypair_str.clone(),
-        })
-    );
-    let home_dir = dirs::home_dir().expect("Impossible to get your home dir!");
-    let file_name: std::path::PathBuf = format!("{}.json", &account_id).into();
-    let mut path = std::path::PathBuf::from(&home_dir);
-    path.push(network_connection_config.dir_name());
-    std::fs::create_dir_all(&path)?;
-    path.push(file_name);
-    std::fs::File::create(&path)
-        .map_err(|err| color_eyre::Report::msg(format!("Failed to create file: {:?}", err)))?
-        .write(buf.as_bytes())
-        .map_err(|err| color_eyre::Report::msg(format!("Failed to write to file: {:?}", err)))?;
-    println!(
-        "\n\n\nThe data for the access key is saved in a file {}",
-        &path.display()
-    );
+    let stored_key = crate::key_store::StoredKey {
+        account_id: account_id.to_string(),
+        public_key: near_crypto::PublicKey::from_str(&key_pair_properties.public_key_str)?,
+        private_key: Some(near_crypto::SecretKey::from_str(
+            &key_pair_properties.secret_keypair_str,
+        )?),
+        master_seed_phrase: None,
+        seed_phrase_hd_path: None,
+        encrypted_secrets: None,
+    };
+    let location = key_store.open()?.save(
+        &crate::key_store::KeyChainNetwork::from_connection_config(&network_connection_config),
+        &stored_key,
+    )?;
+    println!("\n\n\nThe data for the access key is saved in {}", location);
     println!(
         "Logged in as [ {} ] with public key [ {} ] successfully",
         account_id, key_pair_properties.public_key_str
     );
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approved(query: &[(&str, &str)]) -> Option<crate::wallet_callback::WalletRedirect> {
+        Some(crate::wallet_callback::WalletRedirect::Approved(
+            query
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn account_id_of_the_redirect_is_used_for_the_same_key() {
+        assert_eq!(
+            redirected_account_id(
+                approved(&[
+                    ("account_id", "volodymyr.testnet"),
+                    ("public_key", "ed25519:Abc")
+                ]),
+                "ed25519:Abc"
+            )
+            .unwrap(),
+            Some("volodymyr.testnet".to_string())
+        );
+    }
+
+    #[test]
+    fn redirect_with_another_key_is_an_error() {
+        let err = redirected_account_id(
+            approved(&[
+                ("account_id", "volodymyr.testnet"),
+                ("public_key", "ed25519:Def"),
+            ]),
+            "ed25519:Abc",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The wallet added the key ed25519:Def instead of ed25519:Abc"
+        );
+    }
+
+    #[test]
+    fn account_id_is_asked_for_without_a_complete_redirect() {
+        assert_eq!(redirected_account_id(None, "ed25519:Abc").unwrap(), None);
+        assert_eq!(
+            redirected_account_id(
+                approved(&[("account_id", "volodymyr.testnet")]),
+                "ed25519:Abc"
+            )
+            .unwrap(),
+            None
+        );
+        assert!(redirected_account_id(
+            Some(crate::wallet_callback::WalletRedirect::Rejected(
+                "userRejected".to_string()
+            )),
+            "ed25519:Abc"
+        )
+        .is_err());
+    }
+}
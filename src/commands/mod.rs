@@ -1,11 +1,14 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::{TryFrom, TryInto};
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 pub mod add_command;
+pub mod batch_command;
 pub mod construct_transaction_command;
 pub mod delete_command;
 pub mod execute_command;
 pub mod generate_shell_completions_command;
+pub mod keys_command;
 pub mod login;
 pub mod transfer_command;
 pub mod utils_command;
@@ -15,6 +18,8 @@ pub mod view_command;
 pub enum CliTopLevelCommand {
     /// Use these to add access key, contract code, stake proposal, sub-account, implicit-account
     Add(self::add_command::CliAddAction),
+    /// Sign and submit the transactions listed in a manifest file (JSON, YAML or CSV)
+    Batch(self::batch_command::CliBatch),
     /// Prepare and, optionally, submit a new transaction
     ConstructTransaction(self::construct_transaction_command::operation_mode::CliOperationMode),
     /// Use these to delete access key, sub-account
@@ -23,6 +28,8 @@ pub enum CliTopLevelCommand {
     Execute(self::execute_command::CliOptionMethod),
     /// Use these to generate static shell completions
     GenerateShellCompletions(self::generate_shell_completions_command::CliGenerateShellCompletions),
+    /// Use these to list, import, export and remove the keys stored in the keychain
+    Keys(self::keys_command::CliKeys),
     /// Use these to login with wallet authorization
     Login(self::login::operation_mode::CliOperationMode),
     /// Use these to transfer tokens
@@ -38,6 +45,8 @@ pub enum CliTopLevelCommand {
 pub enum TopLevelCommand {
     #[strum_discriminants(strum(message = "Login with wallet authorization"))]
     Login(self::login::operation_mode::OperationMode),
+    #[strum_discriminants(strum(message = "Manage the keys stored in the keychain"))]
+    Keys(self::keys_command::Keys),
     #[strum_discriminants(strum(
         message = "View account, contract code, contract state, transaction, nonce"
     ))]
@@ -54,44 +63,53 @@ pub enum TopLevelCommand {
     Delete(self::delete_command::DeleteAction),
     #[strum_discriminants(strum(message = "Construct a new transaction"))]
     ConstructTransaction(self::construct_transaction_command::operation_mode::OperationMode),
+    #[strum_discriminants(strum(message = "Submit the transactions of a manifest file"))]
+    Batch(self::batch_command::Batch),
     #[strum_discriminants(strum(message = "Helpers"))]
     Utils(self::utils_command::Utils),
 }
 
-impl From<CliTopLevelCommand> for TopLevelCommand {
-    fn from(cli_top_level_command: CliTopLevelCommand) -> Self {
-        match cli_top_level_command {
-            CliTopLevelCommand::Add(cli_add_action) => TopLevelCommand::Add(cli_add_action.into()),
+impl TryFrom<CliTopLevelCommand> for TopLevelCommand {
+    type Error = color_eyre::Report;
+
+    fn try_from(cli_top_level_command: CliTopLevelCommand) -> color_eyre::eyre::Result<Self> {
+        Ok(match cli_top_level_command {
+            CliTopLevelCommand::Add(cli_add_action) => {
+                TopLevelCommand::Add(cli_add_action.try_into()?)
+            }
+            CliTopLevelCommand::Batch(cli_batch) => TopLevelCommand::Batch(cli_batch.try_into()?),
             CliTopLevelCommand::ConstructTransaction(cli_operation_mode) => {
-                TopLevelCommand::ConstructTransaction(cli_operation_mode.into())
+                TopLevelCommand::ConstructTransaction(cli_operation_mode.try_into()?)
             }
             CliTopLevelCommand::Delete(cli_delete_action) => {
-                TopLevelCommand::Delete(cli_delete_action.into())
+                TopLevelCommand::Delete(cli_delete_action.try_into()?)
             }
             CliTopLevelCommand::Execute(cli_option_method) => {
-                TopLevelCommand::Execute(cli_option_method.into())
+                TopLevelCommand::Execute(cli_option_method.try_into()?)
             }
             CliTopLevelCommand::GenerateShellCompletions(_) => {
                 unreachable!("This variant is handled in the main function")
             }
+            CliTopLevelCommand::Keys(cli_keys) => TopLevelCommand::Keys(cli_keys.try_into()?),
             CliTopLevelCommand::Login(cli_option_method) => {
-                TopLevelCommand::Login(cli_option_method.into())
+                TopLevelCommand::Login(cli_option_method.try_into()?)
             }
             CliTopLevelCommand::Transfer(cli_currency) => {
-                TopLevelCommand::Transfer(cli_currency.into())
+                TopLevelCommand::Transfer(cli_currency.try_into()?)
             }
-            CliTopLevelCommand::Utils(cli_util) => TopLevelCommand::Utils(cli_util.into()),
+            CliTopLevelCommand::Utils(cli_util) => TopLevelCommand::Utils(cli_util.try_into()?),
             CliTopLevelCommand::View(cli_view_query_request) => {
-                TopLevelCommand::View(cli_view_query_request.into())
+                TopLevelCommand::View(cli_view_query_request.try_into()?)
             }
-        }
+        })
     }
 }
 
 impl TopLevelCommand {
-    pub fn choose_command() -> Self {
+    pub fn choose_command() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = TopLevelCommandDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let commands = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -104,6 +122,7 @@ impl TopLevelCommand {
             .unwrap();
         let cli_top_level_command = match variants[selection] {
             TopLevelCommandDiscriminants::Add => CliTopLevelCommand::Add(Default::default()),
+            TopLevelCommandDiscriminants::Batch => CliTopLevelCommand::Batch(Default::default()),
             TopLevelCommandDiscriminants::ConstructTransaction => {
                 CliTopLevelCommand::ConstructTransaction(Default::default())
             }
@@ -111,6 +130,7 @@ impl TopLevelCommand {
             TopLevelCommandDiscriminants::Execute => {
                 CliTopLevelCommand::Execute(Default::default())
             }
+            TopLevelCommandDiscriminants::Keys => CliTopLevelCommand::Keys(Default::default()),
             TopLevelCommandDiscriminants::Login => CliTopLevelCommand::Login(Default::default()),
             TopLevelCommandDiscriminants::Transfer => {
                 CliTopLevelCommand::Transfer(Default::default())
@@ -118,10 +138,28 @@ impl TopLevelCommand {
             TopLevelCommandDiscriminants::Utils => CliTopLevelCommand::Utils(Default::default()),
             TopLevelCommandDiscriminants::View => CliTopLevelCommand::View(Default::default()),
         };
-        Self::from(cli_top_level_command)
+        Self::try_from(cli_top_level_command)
+    }
+
+    /// `None` when what was chosen in the prompts cannot be passed as arguments
+    pub fn to_cli_args(&self) -> Option<Vec<String>> {
+        let (command, mut args) = match self {
+            Self::Add(add_action) => ("add", add_action.to_cli_args()),
+            Self::Batch(batch) => ("batch", batch.to_cli_args()),
+            Self::ConstructTransaction(mode) => ("construct-transaction", mode.to_cli_args()?),
+            Self::Delete(delete_action) => ("delete", delete_action.to_cli_args()),
+            Self::Execute(option_method) => ("execute", option_method.to_cli_args()),
+            Self::Keys(keys) => ("keys", keys.to_cli_args()),
+            Self::Login(mode) => ("login", mode.to_cli_args()),
+            Self::Transfer(currency) => ("transfer", currency.to_cli_args()),
+            Self::Utils(util_type) => ("utils", util_type.to_cli_args()),
+            Self::View(view_query_request) => ("view", view_query_request.to_cli_args()),
+        };
+        args.insert(0, command.to_owned());
+        Some(args)
     }
 
-    pub async fn process(self) -> crate::CliResult {
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::CliResult {
         let unsigned_transaction = near_primitives::transaction::Transaction {
             signer_id: "".to_string(),
             public_key: near_crypto::PublicKey::empty(near_crypto::KeyType::ED25519),
@@ -131,14 +169,30 @@ impl TopLevelCommand {
             actions: vec![],
         };
         match self {
-            Self::Add(add_action) => add_action.process(unsigned_transaction).await,
-            Self::ConstructTransaction(mode) => mode.process(unsigned_transaction).await,
-            Self::Delete(delete_action) => delete_action.process(unsigned_transaction).await,
-            Self::Execute(option_method) => option_method.process(unsigned_transaction).await,
+            Self::Add(add_action) => {
+                add_action
+                    .process(unsigned_transaction, output_format)
+                    .await
+            }
+            Self::Batch(batch) => batch.process(output_format).await,
+            Self::ConstructTransaction(mode) => {
+                mode.process(unsigned_transaction, output_format).await
+            }
+            Self::Delete(delete_action) => {
+                delete_action
+                    .process(unsigned_transaction, output_format)
+                    .await
+            }
+            Self::Execute(option_method) => {
+                option_method
+                    .process(unsigned_transaction, output_format)
+                    .await
+            }
+            Self::Keys(keys) => keys.process(output_format).await,
             Self::Login(mode) => mode.process().await,
-            Self::Transfer(currency) => currency.process(unsigned_transaction).await,
-            Self::Utils(util_type) => util_type.process().await,
-            Self::View(view_query_request) => view_query_request.process().await,
+            Self::Transfer(currency) => currency.process(unsigned_transaction, output_format).await,
+            Self::Utils(util_type) => util_type.process(output_format).await,
+            Self::View(view_query_request) => view_query_request.process(output_format).await,
         }
     }
 }
@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::{TryFrom, TryInto};
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 pub mod operation_mode;
@@ -18,23 +19,30 @@ pub struct Currency {
     currency_selection: CurrencySelection,
 }
 
-impl From<CliCurrency> for Currency {
-    fn from(item: CliCurrency) -> Self {
+impl TryFrom<CliCurrency> for Currency {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliCurrency) -> color_eyre::eyre::Result<Self> {
         let currency_selection = match item.currency_selection {
-            Some(cli_currency_selection) => CurrencySelection::from(cli_currency_selection),
-            None => CurrencySelection::choose_currency(),
+            Some(cli_currency_selection) => CurrencySelection::try_from(cli_currency_selection)?,
+            None => CurrencySelection::choose_currency()?,
         };
-        Self { currency_selection }
+        Ok(Self { currency_selection })
     }
 }
 
 impl Currency {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.currency_selection.to_cli_args()
+    }
+
     pub async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         self.currency_selection
-            .process(prepopulated_unsigned_transaction)
+            .process(prepopulated_unsigned_transaction, output_format)
             .await
     }
 }
@@ -52,18 +60,23 @@ enum CurrencySelection {
     NEAR(self::operation_mode::OperationMode),
 }
 
-impl From<CliCurrencySelection> for CurrencySelection {
-    fn from(item: CliCurrencySelection) -> Self {
-        match item {
-            CliCurrencySelection::NEAR(cli_operation_mode) => Self::NEAR(cli_operation_mode.into()),
-        }
+impl TryFrom<CliCurrencySelection> for CurrencySelection {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliCurrencySelection) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
+            CliCurrencySelection::NEAR(cli_operation_mode) => {
+                Self::NEAR(cli_operation_mode.try_into()?)
+            }
+        })
     }
 }
 
 impl CurrencySelection {
-    fn choose_currency() -> Self {
+    fn choose_currency() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = CurrencySelectionDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let currencies = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -77,17 +90,26 @@ impl CurrencySelection {
         let cli_currency = match variants[selected_currency] {
             CurrencySelectionDiscriminants::NEAR => CliCurrencySelection::NEAR(Default::default()),
         };
-        Self::from(cli_currency)
+        Self::try_from(cli_currency)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (currency, mut args) = match self {
+            Self::NEAR(operation_mode) => ("near", operation_mode.to_cli_args()),
+        };
+        args.insert(0, currency.to_owned());
+        args
     }
 
     async fn process(
         self,
         prepopulated_unsigned_transaction: near_primitives::transaction::Transaction,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             Self::NEAR(operation_mode) => {
                 operation_mode
-                    .process(prepopulated_unsigned_transaction)
+                    .process(prepopulated_unsigned_transaction, output_format)
                     .await
             }
         }
@@ -1,19 +1,90 @@
 use dialoguer::Input;
-use near_primitives::borsh::BorshSerialize;
+use near_primitives::borsh::{BorshDeserialize, BorshSerialize};
+use std::convert::TryFrom;
 
 /// утилита, соединяющая подготовленную неподписанную транзакцию с синатурой
 #[derive(Debug, Default, clap::Clap)]
 pub struct CliCombineTransactionSignature {
     #[clap(long)]
     signature: Option<near_crypto::Signature>,
+    /// Take the signature from a file with the scanned QR codes of the signer, one per line
+    /// (the signature itself or the base64 signed transaction)
+    #[clap(long, conflicts_with = "signature")]
+    qr_payload: Option<SignatureQrPayload>,
     #[clap(long)]
     unsigned_transaction: Option<crate::common::TransactionAsBase64>,
+    /// Read the unsigned transaction from a file written by `sign-manually --output`
+    #[clap(long, conflicts_with = "unsigned-transaction")]
+    input: Option<crate::transaction_file::UnsignedTransactionFile>,
+    /// Save the signed transaction to this file (JSON envelope for `.json`, raw borsh otherwise)
+    #[clap(long)]
+    output: Option<std::path::PathBuf>,
+    /// The network the transaction is for; it has to be the network recorded in `--input`
+    /// (unless `--yes` is given) and is recorded in `--output`
+    #[clap(long)]
+    network: Option<crate::common::ConnectionConfig>,
+}
+
+/// A signature scanned from the QR codes shown by the signer
+#[derive(Debug, Clone)]
+pub struct SignatureQrPayload {
+    path: std::path::PathBuf,
+    signature: near_crypto::Signature,
+    /// The hash of the transaction when the payload is a whole signed transaction
+    transaction_hash: Option<near_primitives::hash::CryptoHash>,
+}
+
+impl std::str::FromStr for SignatureQrPayload {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let path = std::path::PathBuf::from(s);
+        let scanned = std::fs::read_to_string(&path)
+            .map_err(|err| format!("Failed to read {}: {}", path.display(), err))?;
+        let payload = crate::common::join_qr_payload(&scanned)?;
+        if let Ok(signature) = payload.parse::<near_crypto::Signature>() {
+            return Ok(Self {
+                path,
+                signature,
+                transaction_hash: None,
+            });
+        }
+        let signed_transaction = near_primitives::serialize::from_base64(&payload)
+            .ok()
+            .and_then(|bytes| {
+                near_primitives::transaction::SignedTransaction::try_from_slice(&bytes).ok()
+            })
+            .ok_or_else(|| {
+                format!(
+                    "{} contains neither a signature nor a signed transaction",
+                    path.display()
+                )
+            })?;
+        Ok(Self {
+            path,
+            signature: signed_transaction.signature.clone(),
+            transaction_hash: Some(signed_transaction.get_hash()),
+        })
+    }
+}
+
+impl std::fmt::Display for SignatureQrPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path.display())
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct CombineTransactionSignature {
     signature: near_crypto::Signature,
+    /// The QR payload file the signature was taken from
+    qr_payload: Option<SignatureQrPayload>,
     unsigned_transaction: near_primitives::transaction::Transaction,
+    /// The file the unsigned transaction was read from
+    input: Option<std::path::PathBuf>,
+    /// The network recorded in the input file, carried over to the output file
+    network: Option<String>,
+    output: Option<std::path::PathBuf>,
+    network_connection_config: Option<crate::common::ConnectionConfig>,
 }
 
 impl std::fmt::Display for CombineTransactionSignature {
@@ -22,41 +93,120 @@ impl std::fmt::Display for CombineTransactionSignature {
     }
 }
 
-impl From<CliCombineTransactionSignature> for CombineTransactionSignature {
-    fn from(item: CliCombineTransactionSignature) -> Self {
-        let signature: near_crypto::Signature = match item.signature {
-            Some(cli_signature) => cli_signature,
-            None => CombineTransactionSignature::input_signature(),
+impl TryFrom<CliCombineTransactionSignature> for CombineTransactionSignature {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliCombineTransactionSignature) -> color_eyre::eyre::Result<Self> {
+        let signature: near_crypto::Signature = match (&item.qr_payload, item.signature) {
+            (Some(qr_payload), _) => qr_payload.signature.clone(),
+            (None, Some(cli_signature)) => cli_signature,
+            (None, None) => CombineTransactionSignature::input_signature()?,
+        };
+        let (unsigned_transaction, input, network) = match (item.input, item.unsigned_transaction) {
+            (Some(input), _) => (input.inner, Some(input.path), input.network),
+            (None, Some(cli_unsigned_transaction)) => (cli_unsigned_transaction.inner, None, None),
+            (None, None) => (
+                CombineTransactionSignature::input_unsigned_transaction()?,
+                None,
+                None,
+            ),
         };
-        let unsigned_transaction: near_primitives::transaction::Transaction =
-            match item.unsigned_transaction {
-                Some(cli_unsigned_transaction) => cli_unsigned_transaction.inner,
-                None => CombineTransactionSignature::input_unsigned_transaction(),
-            };
-        Self {
+        Ok(Self {
             signature,
+            qr_payload: item.qr_payload,
             unsigned_transaction,
-        }
+            input,
+            network,
+            output: item.output,
+            network_connection_config: item.network,
+        })
     }
 }
 
 impl CombineTransactionSignature {
-    pub fn input_signature() -> near_crypto::Signature {
-        Input::new()
+    pub fn input_signature() -> color_eyre::eyre::Result<near_crypto::Signature> {
+        crate::common::ensure_interactive("`--signature <SIGNATURE>` or `--qr-payload <PATH>`")?;
+        Ok(Input::new()
             .with_prompt("Enter the signature")
             .interact_text()
-            .unwrap()
+            .unwrap())
     }
 
-    pub fn input_unsigned_transaction() -> near_primitives::transaction::Transaction {
+    pub fn input_unsigned_transaction(
+    ) -> color_eyre::eyre::Result<near_primitives::transaction::Transaction> {
+        crate::common::ensure_interactive(
+            "`--unsigned-transaction <UNSIGNED_TRANSACTION>` or `--input <PATH>`",
+        )?;
         let input: crate::common::TransactionAsBase64 = Input::new()
             .with_prompt("Enter an unsigned transaction")
             .interact_text()
             .unwrap();
-        input.inner
+        Ok(input.inner)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = match &self.qr_payload {
+            Some(qr_payload) => vec![
+                "--qr-payload".to_owned(),
+                qr_payload.path.display().to_string(),
+            ],
+            None => vec!["--signature".to_owned(), self.signature.to_string()],
+        };
+        match &self.input {
+            Some(input) => args.extend(vec!["--input".to_owned(), input.display().to_string()]),
+            None => args.extend(vec![
+                "--unsigned-transaction".to_owned(),
+                near_primitives::serialize::to_base64(
+                    self.unsigned_transaction
+                        .try_to_vec()
+                        .expect("Transaction is not expected to fail on serialization"),
+                ),
+            ]),
+        }
+        if let Some(output) = &self.output {
+            args.extend(vec!["--output".to_owned(), output.display().to_string()]);
+        }
+        if let Some(network_connection_config) = &self.network_connection_config {
+            args.extend(network_connection_config.to_cli_args());
+        }
+        args
     }
 
     pub async fn process(self) -> crate::CliResult {
+        let network = match &self.network_connection_config {
+            Some(network_connection_config) => {
+                if let Some(input) = &self.input {
+                    crate::transaction_file::check_network(
+                        input,
+                        self.network.as_deref(),
+                        network_connection_config,
+                    )?;
+                }
+                Some(network_connection_config.name())
+            }
+            None => self.network,
+        };
+        if let Some(qr_payload) = &self.qr_payload {
+            let unsigned_transaction_hash = self.unsigned_transaction.get_hash_and_size().0;
+            if let Some(transaction_hash) = qr_payload.transaction_hash {
+                if transaction_hash != unsigned_transaction_hash {
+                    return Err(color_eyre::Report::msg(format!(
+                        "The signed transaction in {} is not the given unsigned transaction",
+                        qr_payload.path.display()
+                    )));
+                }
+            }
+            if !self.signature.verify(
+                unsigned_transaction_hash.as_ref(),
+                &self.unsigned_transaction.public_key,
+            ) {
+                return Err(color_eyre::Report::msg(format!(
+                    "The signature in {} is not made with the key {} of the transaction",
+                    qr_payload.path.display(),
+                    self.unsigned_transaction.public_key
+                )));
+            }
+        }
         let signed_transaction = near_primitives::transaction::SignedTransaction::new(
             self.signature,
             self.unsigned_transaction,
@@ -71,6 +221,69 @@ impl CombineTransactionSignature {
             signed_transaction
         );
         println!("Base64-encoded signed transaction: {}", serialize_to_base64);
+        if let Some(output) = &self.output {
+            crate::transaction_file::save_signed_transaction(output, network, &signed_transaction)?;
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combine(
+        signature: near_crypto::Signature,
+        unsigned_transaction: near_primitives::transaction::Transaction,
+    ) -> CombineTransactionSignature {
+        CombineTransactionSignature {
+            signature: signature.clone(),
+            qr_payload: Some(SignatureQrPayload {
+                path: std::path::PathBuf::from("scanned.txt"),
+                signature,
+                transaction_hash: None,
+            }),
+            unsigned_transaction,
+            input: None,
+            network: None,
+            output: None,
+            network_connection_config: None,
+        }
+    }
+
+    #[test]
+    fn qr_payload_signature_is_verified() {
+        let secret_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519);
+        let other_secret_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519);
+        let unsigned_transaction = near_primitives::transaction::Transaction {
+            signer_id: "volodymyr.testnet".to_string(),
+            public_key: secret_key.public_key(),
+            nonce: 1,
+            receiver_id: "21.testnet".to_string(),
+            block_hash: Default::default(),
+            actions: vec![near_primitives::transaction::Action::Transfer(
+                near_primitives::transaction::TransferAction { deposit: 1 },
+            )],
+        };
+        let transaction_hash = unsigned_transaction.get_hash_and_size().0;
+        actix::System::new().block_on(async {
+            assert!(combine(
+                secret_key.sign(transaction_hash.as_ref()),
+                unsigned_transaction.clone()
+            )
+            .process()
+            .await
+            .is_ok());
+            let err = combine(
+                other_secret_key.sign(transaction_hash.as_ref()),
+                unsigned_transaction.clone(),
+            )
+            .process()
+            .await
+            .unwrap_err();
+            assert!(err
+                .to_string()
+                .starts_with("The signature in scanned.txt is not made with the key"));
+        });
+    }
+}
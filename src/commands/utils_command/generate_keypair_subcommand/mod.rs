@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-fn bip32path_to_string(bip32path: &slip10::BIP32Path) -> String {
+pub fn bip32path_to_string(bip32path: &slip10::BIP32Path) -> String {
     const HARDEND: u32 = 1 << 31;
 
     format!(
@@ -29,8 +29,6 @@ pub struct CliGenerateKeypair {
     pub new_master_seed_phrase_words_count: usize,
     #[clap(long, default_value = "m/44'/397'/0'")]
     pub seed_phrase_hd_path: slip10::BIP32Path,
-    #[clap(long, default_value = "plaintext")]
-    pub format: crate::common::OutputFormat,
 }
 
 impl Default for CliGenerateKeypair {
@@ -39,13 +37,31 @@ impl Default for CliGenerateKeypair {
             master_seed_phrase: None,
             new_master_seed_phrase_words_count: 12,
             seed_phrase_hd_path: slip10::BIP32Path::from_str("m/44'/397'/0'").unwrap(),
-            format: crate::common::OutputFormat::Json,
         }
     }
 }
 
 impl CliGenerateKeypair {
-    pub async fn process(self) -> crate::CliResult {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let default = Self::default();
+        let mut args = vec![];
+        if let Some(master_seed_phrase) = &self.master_seed_phrase {
+            args.push("--master-seed-phrase".to_owned());
+            args.push(master_seed_phrase.clone());
+        }
+        if self.new_master_seed_phrase_words_count != default.new_master_seed_phrase_words_count {
+            args.push("--new-master-seed-phrase-words-count".to_owned());
+            args.push(self.new_master_seed_phrase_words_count.to_string());
+        }
+        let seed_phrase_hd_path = bip32path_to_string(&self.seed_phrase_hd_path);
+        if seed_phrase_hd_path != bip32path_to_string(&default.seed_phrase_hd_path) {
+            args.push("--seed-phrase-hd-path".to_owned());
+            args.push(seed_phrase_hd_path);
+        }
+        args
+    }
+
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::CliResult {
         let key_pair_properties = crate::common::generate_keypair(
             self.master_seed_phrase.as_deref(),
             self.new_master_seed_phrase_words_count,
@@ -53,7 +69,7 @@ impl CliGenerateKeypair {
         )
         .await?;
 
-        match self.format {
+        match output_format {
             crate::common::OutputFormat::Plaintext => {
                 println!(
                     "Master Seed Phrase: {}\nSeed Phrase HD Path: {}\nImplicit Account ID: {}\nPublic Key: {}\nSECRET KEYPAIR: {}",
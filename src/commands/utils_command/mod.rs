@@ -1,9 +1,13 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::TryFrom;
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 mod combine_transaction_subcommand_with_signature;
 pub mod generate_keypair_subcommand;
+mod sign_message_subcommand;
 mod sign_transaction_subcommand_with_secret_key;
+mod verify_message_subcommand;
+mod verify_signature_subcommand;
 mod view_serialized_transaction;
 
 /// набор утилит-помощников
@@ -18,19 +22,25 @@ pub struct Utils {
     pub util: Util,
 }
 
-impl From<CliUtils> for Utils {
-    fn from(item: CliUtils) -> Self {
+impl TryFrom<CliUtils> for Utils {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliUtils) -> color_eyre::eyre::Result<Self> {
         let util = match item.util {
-            Some(cli_util) => Util::from(cli_util),
-            None => Util::choose_util(),
+            Some(cli_util) => Util::try_from(cli_util)?,
+            None => Util::choose_util()?,
         };
-        Self { util }
+        Ok(Self { util })
     }
 }
 
 impl Utils {
-    pub async fn process(self) -> crate::CliResult {
-        self.util.process().await
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.util.to_cli_args()
+    }
+
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::CliResult {
+        self.util.process(output_format).await
     }
 }
 
@@ -48,6 +58,12 @@ enum CliUtil {
     ),
     /// Using this module, you can view the contents of a serialized transaction (whether signed or not).
     ViewSerializedTransaction(self::view_serialized_transaction::CliViewSerializedTransaction),
+    /// Check the signature of a transaction and, optionally, the access key on the network
+    VerifySignature(self::verify_signature_subcommand::CliVerifySignature),
+    /// Sign an arbitrary message with a secret key or a key from the keychain
+    SignMessage(self::sign_message_subcommand::CliSignMessage),
+    /// Check the signature of an arbitrary message and, optionally, the owner of the key
+    VerifyMessage(self::verify_message_subcommand::CliVerifyMessage),
 }
 
 #[derive(Debug, EnumDiscriminants)]
@@ -65,37 +81,55 @@ pub enum Util {
     ),
     #[strum_discriminants(strum(message = "Deserializing the bytes from base64"))]
     ViewSerializedTransaction(self::view_serialized_transaction::ViewSerializedTransaction),
+    #[strum_discriminants(strum(message = "Verify the signature of a transaction"))]
+    VerifySignature(self::verify_signature_subcommand::VerifySignature),
+    #[strum_discriminants(strum(message = "Sign a message"))]
+    SignMessage(self::sign_message_subcommand::SignMessage),
+    #[strum_discriminants(strum(message = "Verify the signature of a message"))]
+    VerifyMessage(self::verify_message_subcommand::VerifyMessage),
 }
 
-impl From<CliUtil> for Util {
-    fn from(item: CliUtil) -> Self {
-        match item {
+impl TryFrom<CliUtil> for Util {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliUtil) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliUtil::GenerateKeypair(generate_keypair) => Util::GenerateKeypair(generate_keypair),
             CliUtil::SignTransactionSecretKey(cli_sign_transaction) => {
                 let sign_transaction =
-                    self::sign_transaction_subcommand_with_secret_key::SignTransactionSecretKey::from(cli_sign_transaction);
+                    self::sign_transaction_subcommand_with_secret_key::SignTransactionSecretKey::try_from(cli_sign_transaction)?;
                 Util::SignTransactionSecretKey(sign_transaction)
             }
             CliUtil::CombineTransactionSignature(cli_combine_transaction) => {
                 let combine_transaction =
-                    self::combine_transaction_subcommand_with_signature::CombineTransactionSignature::from(cli_combine_transaction);
+                    self::combine_transaction_subcommand_with_signature::CombineTransactionSignature::try_from(cli_combine_transaction)?;
                 Util::CombineTransactionSignature(combine_transaction)
             }
             CliUtil::ViewSerializedTransaction(cli_view_serialized_transaction) => {
                 let view_serialized_transaction =
-                    self::view_serialized_transaction::ViewSerializedTransaction::from(
+                    self::view_serialized_transaction::ViewSerializedTransaction::try_from(
                         cli_view_serialized_transaction,
-                    );
+                    )?;
                 Util::ViewSerializedTransaction(view_serialized_transaction)
             }
-        }
+            CliUtil::VerifySignature(cli_verify_signature) => Util::VerifySignature(
+                self::verify_signature_subcommand::VerifySignature::try_from(cli_verify_signature)?,
+            ),
+            CliUtil::SignMessage(cli_sign_message) => Util::SignMessage(
+                self::sign_message_subcommand::SignMessage::try_from(cli_sign_message)?,
+            ),
+            CliUtil::VerifyMessage(cli_verify_message) => Util::VerifyMessage(
+                self::verify_message_subcommand::VerifyMessage::try_from(cli_verify_message)?,
+            ),
+        })
     }
 }
 
 impl Util {
-    fn choose_util() -> Self {
+    fn choose_util() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = UtilDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let utils = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -119,20 +153,57 @@ impl Util {
             UtilDiscriminants::ViewSerializedTransaction => {
                 CliUtil::ViewSerializedTransaction(Default::default())
             }
+            UtilDiscriminants::VerifySignature => CliUtil::VerifySignature(Default::default()),
+            UtilDiscriminants::SignMessage => CliUtil::SignMessage(Default::default()),
+            UtilDiscriminants::VerifyMessage => CliUtil::VerifyMessage(Default::default()),
+        };
+        Self::try_from(cli_util)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (command, mut args) = match self {
+            Self::GenerateKeypair(generate_keypair) => {
+                ("generate-keypair", generate_keypair.to_cli_args())
+            }
+            Self::SignTransactionSecretKey(sign_transaction) => (
+                "sign-transaction-secret-key",
+                sign_transaction.to_cli_args(),
+            ),
+            Self::CombineTransactionSignature(combine_transaction) => (
+                "combine-transaction-signature",
+                combine_transaction.to_cli_args(),
+            ),
+            Self::ViewSerializedTransaction(view_serialized_transaction) => (
+                "view-serialized-transaction",
+                view_serialized_transaction.to_cli_args(),
+            ),
+            Self::VerifySignature(verify_signature) => {
+                ("verify-signature", verify_signature.to_cli_args())
+            }
+            Self::SignMessage(sign_message) => ("sign-message", sign_message.to_cli_args()),
+            Self::VerifyMessage(verify_message) => ("verify-message", verify_message.to_cli_args()),
         };
-        Self::from(cli_util)
+        args.insert(0, command.to_owned());
+        args
     }
 
-    pub async fn process(self) -> crate::CliResult {
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::CliResult {
         match self {
-            Self::GenerateKeypair(generate_keypair) => generate_keypair.process().await,
+            Self::GenerateKeypair(generate_keypair) => {
+                generate_keypair.process(output_format).await
+            }
             Self::SignTransactionSecretKey(sign_transaction) => sign_transaction.process().await,
             Self::CombineTransactionSignature(combine_transaction) => {
                 combine_transaction.process().await
             }
             Self::ViewSerializedTransaction(view_serialized_transaction) => {
-                view_serialized_transaction.process().await
+                view_serialized_transaction.process(output_format).await
+            }
+            Self::VerifySignature(verify_signature) => {
+                verify_signature.process(output_format).await
             }
+            Self::SignMessage(sign_message) => sign_message.process(output_format).await,
+            Self::VerifyMessage(verify_message) => verify_message.process(output_format).await,
         }
     }
 }
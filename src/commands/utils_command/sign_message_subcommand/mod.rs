@@ -0,0 +1,296 @@
+use dialoguer::{theme::ColorfulTheme, Input, Select};
+use std::convert::TryFrom;
+
+use crate::commands::construct_transaction_command::sign_transaction::sign_with_keychain::SignKeychain;
+
+/// The tag that goes before the message bytes when they are hashed for signing: the NEP-413 tag
+/// (2^31 + 413), as 4 little-endian bytes. A borsh-serialized transaction starts with the length
+/// of its `signer_id`, which can never be that large, so a signed message is never a signature
+/// of a transaction.
+pub const MESSAGE_TAG: u32 = (1 << 31) + 413;
+
+/// The message to sign or verify; the signature is made over the SHA-256 hash of `MESSAGE_TAG`
+/// followed by the message bytes
+#[derive(Debug, Default, Clone, clap::Clap)]
+pub struct CliMessage {
+    /// A UTF-8 message
+    #[clap(long, conflicts_with_all = &["message-base64", "message-file"])]
+    message: Option<String>,
+    /// A binary message encoded in base64
+    #[clap(long, conflicts_with = "message-file")]
+    message_base64: Option<String>,
+    /// A file with the message
+    #[clap(long)]
+    message_file: Option<std::path::PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Text(String),
+    Base64(String),
+    File(std::path::PathBuf),
+}
+
+impl TryFrom<CliMessage> for Message {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliMessage) -> color_eyre::eyre::Result<Self> {
+        Ok(
+            match (item.message, item.message_base64, item.message_file) {
+                (Some(message), _, _) => Self::Text(message),
+                (None, Some(message_base64), _) => Self::Base64(message_base64),
+                (None, None, Some(message_file)) => Self::File(message_file),
+                (None, None, None) => Self::Text(Self::input_message()?),
+            },
+        )
+    }
+}
+
+impl Message {
+    fn input_message() -> color_eyre::eyre::Result<String> {
+        crate::common::ensure_interactive(
+            "`--message <MESSAGE>`, `--message-base64 <MESSAGE_BASE64>` or `--message-file <PATH>`",
+        )?;
+        Ok(Input::new()
+            .with_prompt("Enter the message")
+            .interact_text()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        match self {
+            Self::Text(message) => vec!["--message".to_owned(), message.clone()],
+            Self::Base64(message_base64) => {
+                vec!["--message-base64".to_owned(), message_base64.clone()]
+            }
+            Self::File(path) => vec!["--message-file".to_owned(), path.display().to_string()],
+        }
+    }
+
+    pub fn bytes(&self) -> color_eyre::eyre::Result<Vec<u8>> {
+        match self {
+            Self::Text(message) => Ok(message.as_bytes().to_vec()),
+            Self::Base64(message_base64) => base64::decode(message_base64).map_err(|err| {
+                color_eyre::Report::msg(format!("The base64 message is invalid: {}", err))
+            }),
+            Self::File(path) => std::fs::read(path).map_err(|err| {
+                color_eyre::Report::msg(format!("Failed to read {}: {:?}", path.display(), err))
+            }),
+        }
+    }
+
+    /// What is actually signed
+    pub fn hash(&self) -> color_eyre::eyre::Result<near_primitives::hash::CryptoHash> {
+        let mut payload = MESSAGE_TAG.to_le_bytes().to_vec();
+        payload.extend(self.bytes()?);
+        Ok(near_primitives::hash::hash(&payload))
+    }
+}
+
+/// утилита, позволяющая подписать произвольное сообщение ключом аккаунта
+#[derive(Debug, Default, clap::Clap)]
+pub struct CliSignMessage {
+    #[clap(flatten)]
+    message: CliMessage,
+    #[clap(long, conflicts_with = "signer-id")]
+    signer_secret_key: Option<near_crypto::SecretKey>,
+    /// Read the secret key from a file (`-` for stdin) instead, so that it stays out of the
+    /// shell history; `NEAR_SIGNER_SECRET_KEY` is used when none of the keys is given
+    #[clap(long, conflicts_with_all = &["signer-secret-key", "signer-id"])]
+    signer_secret_key_file: Option<crate::common::SecretKeyFile>,
+    /// Sign with the key of this account from the keychain
+    #[clap(long)]
+    signer_id: Option<near_primitives::types::AccountId>,
+    /// The keychain of this network is searched first, and only a key that is an access key of
+    /// the account on this network is used
+    #[clap(long)]
+    network: Option<crate::common::ConnectionConfig>,
+    /// Where the keys are kept: file (default) or secret-service
+    #[clap(long)]
+    key_store: Option<crate::key_store::KeyStoreKind>,
+}
+
+#[derive(Debug)]
+pub enum MessageSigner {
+    SecretKey(near_crypto::SecretKey, crate::common::SecretKeySource),
+    Keychain {
+        signer_id: near_primitives::types::AccountId,
+        network_connection_config: Option<crate::common::ConnectionConfig>,
+        key_store: crate::key_store::KeyStoreKind,
+    },
+}
+
+#[derive(Debug)]
+pub struct SignMessage {
+    message: Message,
+    signer: MessageSigner,
+}
+
+impl TryFrom<CliSignMessage> for SignMessage {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSignMessage) -> color_eyre::eyre::Result<Self> {
+        let message = Message::try_from(item.message)?;
+        let signer = match item.signer_id {
+            Some(signer_id) => MessageSigner::Keychain {
+                signer_id,
+                network_connection_config: item.network,
+                key_store: item.key_store.unwrap_or_default(),
+            },
+            None if item.signer_secret_key.is_some()
+                || item.signer_secret_key_file.is_some()
+                || std::env::var_os(crate::common::SIGNER_SECRET_KEY_ENV).is_some() =>
+            {
+                let (signer_secret_key, secret_key_source) = crate::common::signer_secret_key(
+                    item.signer_secret_key,
+                    item.signer_secret_key_file,
+                    "`--signer-secret-key <SIGNER_SECRET_KEY>`",
+                )?;
+                MessageSigner::SecretKey(signer_secret_key, secret_key_source)
+            }
+            None => SignMessage::choose_signer(item.network, item.key_store)?,
+        };
+        Ok(Self { message, signer })
+    }
+}
+
+impl SignMessage {
+    fn choose_signer(
+        network_connection_config: Option<crate::common::ConnectionConfig>,
+        key_store: Option<crate::key_store::KeyStoreKind>,
+    ) -> color_eyre::eyre::Result<MessageSigner> {
+        crate::common::ensure_interactive(
+            "`--signer-secret-key <SIGNER_SECRET_KEY>`, `--signer-secret-key-file <PATH>`, `NEAR_SIGNER_SECRET_KEY` or `--signer-id <SIGNER_ID>`",
+        )?;
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("How do you want to sign the message?")
+            .items(&[
+                "With the key of an account from the keychain",
+                "With a secret key",
+            ])
+            .default(0)
+            .interact()
+            .unwrap();
+        Ok(if selection == 0 {
+            MessageSigner::Keychain {
+                signer_id: Input::new()
+                    .with_prompt("What is the account ID of the signer?")
+                    .interact_text()
+                    .unwrap(),
+                network_connection_config,
+                key_store: key_store.unwrap_or_default(),
+            }
+        } else {
+            MessageSigner::SecretKey(
+                crate::common::input_secret_key("Enter the secret key (it is not shown)"),
+                crate::common::SecretKeySource::Argument,
+            )
+        })
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = self.message.to_cli_args();
+        match &self.signer {
+            MessageSigner::SecretKey(signer_secret_key, secret_key_source) => {
+                args.extend(secret_key_source.to_cli_args(signer_secret_key));
+            }
+            MessageSigner::Keychain {
+                signer_id,
+                network_connection_config,
+                key_store,
+            } => {
+                args.extend(vec!["--signer-id".to_owned(), signer_id.to_string()]);
+                if let Some(network_connection_config) = network_connection_config {
+                    args.extend(network_connection_config.to_cli_args());
+                }
+                if *key_store != crate::key_store::KeyStoreKind::default() {
+                    args.push("--key-store".to_owned());
+                    args.push(<&str>::from(*key_store).to_owned());
+                }
+            }
+        }
+        args
+    }
+
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::CliResult {
+        let message_hash = self.message.hash()?;
+        let secret_key = match self.signer {
+            MessageSigner::SecretKey(signer_secret_key, _) => signer_secret_key,
+            MessageSigner::Keychain {
+                signer_id,
+                network_connection_config,
+                key_store,
+            } => {
+                let key_store = key_store.open()?;
+                SignKeychain::find_signer_key(
+                    &*key_store,
+                    &signer_id,
+                    &network_connection_config,
+                    None,
+                )
+                .await?
+                .secret_key()?
+            }
+        };
+        let signature = secret_key.sign(message_hash.as_ref());
+        match output_format {
+            crate::common::OutputFormat::Plaintext => {
+                println!("\nPublic key:   {}", secret_key.public_key());
+                println!("Message hash: {}", message_hash);
+                println!("Signature:    {}", signature);
+            }
+            crate::common::OutputFormat::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "public_key": secret_key.public_key(),
+                        "message_hash": message_hash,
+                        "signature": signature,
+                    }))?
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signing_transaction_bytes_does_not_sign_the_transaction() {
+        let secret_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519);
+        let transaction = near_primitives::transaction::Transaction {
+            signer_id: "volodymyr.testnet".to_string(),
+            public_key: secret_key.public_key(),
+            nonce: 1,
+            receiver_id: "fro_volod.testnet".to_string(),
+            block_hash: Default::default(),
+            actions: vec![near_primitives::transaction::Action::Transfer(
+                near_primitives::transaction::TransferAction {
+                    deposit: 1_000_000_000_000_000_000_000_000,
+                },
+            )],
+        };
+        let transaction_hash = transaction.get_hash_and_size().0;
+        let transaction_bytes =
+            near_primitives::borsh::BorshSerialize::try_to_vec(&transaction).unwrap();
+        let message_hash = Message::Base64(base64::encode(&transaction_bytes))
+            .hash()
+            .unwrap();
+        assert_ne!(message_hash, transaction_hash);
+        assert_eq!(
+            message_hash,
+            near_primitives::hash::hash(
+                &[&MESSAGE_TAG.to_le_bytes()[..], &transaction_bytes[..]].concat()
+            )
+        );
+        let message_signature = secret_key.sign(message_hash.as_ref());
+        assert!(!message_signature.verify(transaction_hash.as_ref(), &secret_key.public_key()));
+        assert_ne!(
+            message_signature,
+            secret_key.sign(transaction_hash.as_ref())
+        );
+    }
+}
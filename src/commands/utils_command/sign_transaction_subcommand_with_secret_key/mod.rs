@@ -1,56 +1,115 @@
 use dialoguer::Input;
 use near_primitives::borsh::BorshSerialize;
+use std::convert::TryFrom;
 
 /// утилита, позволяющая подписать транзакцию личным ключом
 #[derive(Debug, Default, clap::Clap)]
 pub struct CliSignTransactionSecretKey {
     #[clap(long)]
     signer_secret_key: Option<near_crypto::SecretKey>,
+    /// Read the secret key from a file (`-` for stdin) instead, so that it stays out of the
+    /// shell history; `NEAR_SIGNER_SECRET_KEY` is used when neither is given
+    #[clap(long, conflicts_with = "signer-secret-key")]
+    signer_secret_key_file: Option<crate::common::SecretKeyFile>,
     #[clap(long)]
     unsigned_transaction: Option<crate::common::TransactionAsBase64>,
+    /// Read the unsigned transaction from a file written by `sign-manually --output`
+    #[clap(long, conflicts_with = "unsigned-transaction")]
+    input: Option<crate::transaction_file::UnsignedTransactionFile>,
+    /// Save the signed transaction to this file (JSON envelope for `.json`, raw borsh otherwise)
+    #[clap(long)]
+    output: Option<std::path::PathBuf>,
+    /// Also show the signed transaction as a QR code (animated parts for large transactions)
+    #[clap(long)]
+    qr: bool,
 }
 
 #[derive(Debug)]
 pub struct SignTransactionSecretKey {
     pub signer_secret_key: near_crypto::SecretKey,
+    pub secret_key_source: crate::common::SecretKeySource,
     pub unsigned_transaction: near_primitives::transaction::Transaction,
+    /// The file the unsigned transaction was read from
+    pub input: Option<std::path::PathBuf>,
+    /// The network recorded in the input file, carried over to the output file
+    pub network: Option<String>,
+    pub output: Option<std::path::PathBuf>,
+    pub qr: bool,
 }
 
-impl From<CliSignTransactionSecretKey> for SignTransactionSecretKey {
-    fn from(item: CliSignTransactionSecretKey) -> Self {
-        let signer_secret_key: near_crypto::SecretKey = match item.signer_secret_key {
-            Some(cli_signer_secret_key) => cli_signer_secret_key,
-            None => SignTransactionSecretKey::input_signer_secret_key(),
+impl TryFrom<CliSignTransactionSecretKey> for SignTransactionSecretKey {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSignTransactionSecretKey) -> color_eyre::eyre::Result<Self> {
+        let (signer_secret_key, secret_key_source) = crate::common::signer_secret_key(
+            item.signer_secret_key,
+            item.signer_secret_key_file,
+            "`--signer-secret-key <SIGNER_SECRET_KEY>`, `--signer-secret-key-file <PATH>` or `NEAR_SIGNER_SECRET_KEY`",
+        )?;
+        let (unsigned_transaction, input, network) = match (item.input, item.unsigned_transaction) {
+            (Some(input), _) => (input.inner, Some(input.path), input.network),
+            (None, Some(cli_unsigned_transaction)) => (cli_unsigned_transaction.inner, None, None),
+            (None, None) => (
+                SignTransactionSecretKey::input_unsigned_transaction()?,
+                None,
+                None,
+            ),
         };
-        let unsigned_transaction: near_primitives::transaction::Transaction =
-            match item.unsigned_transaction {
-                Some(cli_unsigned_transaction) => cli_unsigned_transaction.inner,
-                None => SignTransactionSecretKey::input_unsigned_transaction(),
-            };
-        SignTransactionSecretKey {
+        Ok(SignTransactionSecretKey {
             signer_secret_key,
+            secret_key_source,
             unsigned_transaction,
-        }
+            input,
+            network,
+            output: item.output,
+            qr: item.qr,
+        })
     }
 }
 
 impl SignTransactionSecretKey {
-    pub fn input_signer_secret_key() -> near_crypto::SecretKey {
-        Input::new()
-            .with_prompt("Enter the secret key")
-            .interact_text()
-            .unwrap()
-    }
-
-    pub fn input_unsigned_transaction() -> near_primitives::transaction::Transaction {
+    pub fn input_unsigned_transaction(
+    ) -> color_eyre::eyre::Result<near_primitives::transaction::Transaction> {
+        crate::common::ensure_interactive(
+            "`--unsigned-transaction <UNSIGNED_TRANSACTION>` or `--input <PATH>`",
+        )?;
         let input: crate::common::TransactionAsBase64 = Input::new()
             .with_prompt("Enter an unsigned transaction")
             .interact_text()
             .unwrap();
-        input.inner
+        Ok(input.inner)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = self.secret_key_source.to_cli_args(&self.signer_secret_key);
+        match &self.input {
+            Some(input) => args.extend(vec!["--input".to_owned(), input.display().to_string()]),
+            None => args.extend(vec![
+                "--unsigned-transaction".to_owned(),
+                near_primitives::serialize::to_base64(
+                    self.unsigned_transaction
+                        .try_to_vec()
+                        .expect("Transaction is not expected to fail on serialization"),
+                ),
+            ]),
+        }
+        if let Some(output) = &self.output {
+            args.extend(vec!["--output".to_owned(), output.display().to_string()]);
+        }
+        if self.qr {
+            args.push("--qr".to_owned());
+        }
+        args
     }
 
     pub async fn process(self) -> crate::CliResult {
+        if self.signer_secret_key.public_key() != self.unsigned_transaction.public_key {
+            return Err(color_eyre::Report::msg(format!(
+                "The secret key is the key of {}, but the transaction is to be signed with {}",
+                self.signer_secret_key.public_key(),
+                self.unsigned_transaction.public_key
+            )));
+        }
         let signature = self
             .signer_secret_key
             .sign(&self.unsigned_transaction.get_hash_and_size().0.as_ref());
@@ -71,6 +130,16 @@ impl SignTransactionSecretKey {
             signed_transaction
         );
         println!("Base64-encoded signed transaction: {}", serialize_to_base64);
+        if let Some(output) = &self.output {
+            crate::transaction_file::save_signed_transaction(
+                output,
+                self.network,
+                &signed_transaction,
+            )?;
+        }
+        if self.qr {
+            crate::common::print_qr_code(&serialize_to_base64)?;
+        }
         Ok(())
     }
 }
@@ -0,0 +1,193 @@
+use dialoguer::Input;
+use std::convert::TryFrom;
+
+use super::sign_message_subcommand::{CliMessage, Message};
+
+/// утилита, позволяющая проверить подпись произвольного сообщения
+#[derive(Debug, Default, clap::Clap)]
+pub struct CliVerifyMessage {
+    #[clap(flatten)]
+    message: CliMessage,
+    #[clap(long)]
+    signature: Option<near_crypto::Signature>,
+    #[clap(long)]
+    public_key: Option<near_crypto::PublicKey>,
+    /// Also check that the public key is an access key of this account on `--network`
+    #[clap(long, requires = "network")]
+    account_id: Option<near_primitives::types::AccountId>,
+    #[clap(long)]
+    network: Option<crate::common::ConnectionConfig>,
+}
+
+#[derive(Debug)]
+pub struct VerifyMessage {
+    message: Message,
+    signature: near_crypto::Signature,
+    public_key: near_crypto::PublicKey,
+    account: Option<(
+        near_primitives::types::AccountId,
+        crate::common::ConnectionConfig,
+    )>,
+}
+
+impl TryFrom<CliVerifyMessage> for VerifyMessage {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliVerifyMessage) -> color_eyre::eyre::Result<Self> {
+        let message = Message::try_from(item.message)?;
+        let signature = match item.signature {
+            Some(signature) => signature,
+            None => VerifyMessage::input_signature()?,
+        };
+        let public_key = match item.public_key {
+            Some(public_key) => public_key,
+            None => VerifyMessage::input_public_key()?,
+        };
+        let account = match (item.account_id, item.network) {
+            (Some(account_id), Some(network_connection_config)) => {
+                Some((account_id, network_connection_config))
+            }
+            _ => None,
+        };
+        Ok(Self {
+            message,
+            signature,
+            public_key,
+            account,
+        })
+    }
+}
+
+impl VerifyMessage {
+    fn input_signature() -> color_eyre::eyre::Result<near_crypto::Signature> {
+        crate::common::ensure_interactive("`--signature <SIGNATURE>`")?;
+        Ok(Input::new()
+            .with_prompt("Enter the signature")
+            .interact_text()
+            .unwrap())
+    }
+
+    fn input_public_key() -> color_eyre::eyre::Result<near_crypto::PublicKey> {
+        crate::common::ensure_interactive("`--public-key <PUBLIC_KEY>`")?;
+        Ok(Input::new()
+            .with_prompt("Enter the public key")
+            .interact_text()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = self.message.to_cli_args();
+        args.extend(vec![
+            "--signature".to_owned(),
+            self.signature.to_string(),
+            "--public-key".to_owned(),
+            self.public_key.to_string(),
+        ]);
+        if let Some((account_id, network_connection_config)) = &self.account {
+            args.extend(vec!["--account-id".to_owned(), account_id.to_string()]);
+            args.extend(network_connection_config.to_cli_args());
+        }
+        args
+    }
+
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::CliResult {
+        let message_hash = self.message.hash()?;
+        let signature_valid = self
+            .signature
+            .verify(message_hash.as_ref(), &self.public_key);
+        let access_key = match &self.account {
+            Some((account_id, network_connection_config)) => Some(match network_connection_config
+                .rpc_client()
+                .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
+                    block_reference: near_primitives::types::Finality::Final.into(),
+                    request: near_primitives::views::QueryRequest::ViewAccessKey {
+                        account_id: account_id.clone(),
+                        public_key: self.public_key.clone(),
+                    },
+                })
+                .await
+            {
+                Ok(near_jsonrpc_primitives::types::query::RpcQueryResponse {
+                    kind:
+                        near_jsonrpc_primitives::types::query::QueryResponseKind::AccessKey(
+                            access_key_view,
+                        ),
+                    ..
+                }) => Some(access_key_view),
+                Ok(_) => {
+                    return Err(color_eyre::Report::msg(
+                        "Unexpected response to the access key query",
+                    ))
+                }
+                Err(err)
+                    if super::verify_signature_subcommand::is_unknown_access_key_error(&err) =>
+                {
+                    None
+                }
+                Err(err) => {
+                    return Err(color_eyre::Report::msg(format!(
+                        "Failed to fetch the access key {} of {}: {:?}",
+                        self.public_key, account_id, err
+                    )))
+                }
+            }),
+            None => None,
+        };
+        match output_format {
+            crate::common::OutputFormat::Plaintext => {
+                println!("\nMessage hash: {}", message_hash);
+                if signature_valid {
+                    println!("Signature:    valid for {}", self.public_key);
+                } else {
+                    println!("Signature:    INVALID for {}", self.public_key);
+                }
+                if let Some((account_id, _)) = &self.account {
+                    match &access_key {
+                        Some(Some(access_key_view)) => {
+                            let permission = match &access_key_view.permission {
+                                near_primitives::views::AccessKeyPermissionView::FullAccess => {
+                                    "full access".to_string()
+                                }
+                                near_primitives::views::AccessKeyPermissionView::FunctionCall {
+                                    receiver_id,
+                                    ..
+                                } => format!("function-call access to {}", receiver_id),
+                            };
+                            println!(
+                                "Access key:   {} belongs to {} ({})",
+                                self.public_key, account_id, permission
+                            )
+                        }
+                        _ => println!(
+                            "Access key:   {} is NOT an access key of {}",
+                            self.public_key, account_id
+                        ),
+                    }
+                }
+            }
+            crate::common::OutputFormat::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "message_hash": message_hash,
+                        "public_key": self.public_key,
+                        "signature_valid": signature_valid,
+                        "account_id": self.account.as_ref().map(|(account_id, _)| account_id),
+                        "access_key": access_key,
+                    }))?
+                );
+            }
+        }
+        if !signature_valid {
+            return Err(color_eyre::Report::msg(
+                "The signature is not valid for the message",
+            ));
+        }
+        if let Some(None) = access_key {
+            return Err(color_eyre::Report::msg(
+                "The public key is not an access key of the account",
+            ));
+        }
+        Ok(())
+    }
+}
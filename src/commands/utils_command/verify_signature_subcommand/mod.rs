@@ -0,0 +1,378 @@
+use dialoguer::Input;
+use near_primitives::borsh::{BorshDeserialize, BorshSerialize};
+use std::convert::TryFrom;
+
+/// Checks the signature of a transaction before it is broadcast
+#[derive(Debug, Default, clap::Clap)]
+pub struct CliVerifySignature {
+    /// The base64 signed transaction
+    #[clap(long, conflicts_with_all = &["unsigned-transaction", "input"])]
+    signed_transaction: Option<crate::common::SignedTransactionAsBase64>,
+    /// The base64 unsigned transaction, checked together with `--signature`
+    #[clap(long, conflicts_with = "input")]
+    unsigned_transaction: Option<crate::common::TransactionAsBase64>,
+    /// A transaction file (signed, or unsigned together with `--signature`)
+    #[clap(long)]
+    input: Option<crate::transaction_file::TransactionFile>,
+    #[clap(long)]
+    signature: Option<near_crypto::Signature>,
+    /// The key to check the signature with (the public key of the transaction by default)
+    #[clap(long)]
+    public_key: Option<near_crypto::PublicKey>,
+    /// Also check on this network that the key is an access key of the signer that allows
+    /// every action of the transaction
+    #[clap(long)]
+    network: Option<crate::common::ConnectionConfig>,
+}
+
+#[derive(Debug)]
+enum TransactionSource {
+    Signed(near_primitives::transaction::SignedTransaction),
+    Unsigned {
+        transaction: near_primitives::transaction::Transaction,
+        signature: near_crypto::Signature,
+    },
+    File {
+        file: crate::transaction_file::TransactionFile,
+        signature: Option<near_crypto::Signature>,
+    },
+}
+
+#[derive(Debug)]
+pub struct VerifySignature {
+    source: TransactionSource,
+    public_key: Option<near_crypto::PublicKey>,
+    network_connection_config: Option<crate::common::ConnectionConfig>,
+}
+
+impl TryFrom<CliVerifySignature> for VerifySignature {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliVerifySignature) -> color_eyre::eyre::Result<Self> {
+        let source = match (
+            item.input,
+            item.signed_transaction,
+            item.unsigned_transaction,
+        ) {
+            (Some(file), _, _) => {
+                let signature = match (item.signature, file.kind) {
+                    (Some(signature), _) => Some(signature),
+                    (None, Some(crate::transaction_file::TransactionKind::UnsignedTransaction)) => {
+                        Some(VerifySignature::input_signature()?)
+                    }
+                    (None, _) => None,
+                };
+                TransactionSource::File { file, signature }
+            }
+            (None, Some(signed_transaction), _) => {
+                TransactionSource::Signed(signed_transaction.inner)
+            }
+            (None, None, Some(unsigned_transaction)) => TransactionSource::Unsigned {
+                transaction: unsigned_transaction.inner,
+                signature: match item.signature {
+                    Some(signature) => signature,
+                    None => VerifySignature::input_signature()?,
+                },
+            },
+            (None, None, None) => {
+                TransactionSource::Signed(VerifySignature::input_signed_transaction()?)
+            }
+        };
+        Ok(Self {
+            source,
+            public_key: item.public_key,
+            network_connection_config: item.network,
+        })
+    }
+}
+
+impl VerifySignature {
+    fn input_signed_transaction(
+    ) -> color_eyre::eyre::Result<near_primitives::transaction::SignedTransaction> {
+        crate::common::ensure_interactive(
+            "`--signed-transaction <SIGNED_TRANSACTION>`, `--unsigned-transaction <UNSIGNED_TRANSACTION>` or `--input <PATH>`",
+        )?;
+        let input: crate::common::SignedTransactionAsBase64 = Input::new()
+            .with_prompt("Enter a signed transaction")
+            .interact_text()
+            .unwrap();
+        Ok(input.inner)
+    }
+
+    fn input_signature() -> color_eyre::eyre::Result<near_crypto::Signature> {
+        crate::common::ensure_interactive("`--signature <SIGNATURE>`")?;
+        Ok(Input::new()
+            .with_prompt("Enter the signature")
+            .interact_text()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = match &self.source {
+            TransactionSource::Signed(signed_transaction) => vec![
+                "--signed-transaction".to_owned(),
+                near_primitives::serialize::to_base64(
+                    signed_transaction
+                        .try_to_vec()
+                        .expect("Transaction is not expected to fail on serialization"),
+                ),
+            ],
+            TransactionSource::Unsigned {
+                transaction,
+                signature,
+            } => vec![
+                "--unsigned-transaction".to_owned(),
+                near_primitives::serialize::to_base64(
+                    transaction
+                        .try_to_vec()
+                        .expect("Transaction is not expected to fail on serialization"),
+                ),
+                "--signature".to_owned(),
+                signature.to_string(),
+            ],
+            TransactionSource::File { file, signature } => {
+                let mut args = vec!["--input".to_owned(), file.path.display().to_string()];
+                if let Some(signature) = signature {
+                    args.extend(vec!["--signature".to_owned(), signature.to_string()]);
+                }
+                args
+            }
+        };
+        if let Some(public_key) = &self.public_key {
+            args.extend(vec!["--public-key".to_owned(), public_key.to_string()]);
+        }
+        if let Some(network_connection_config) = &self.network_connection_config {
+            args.extend(network_connection_config.to_cli_args());
+        }
+        args
+    }
+
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::CliResult {
+        let (transaction, signature) = match self.source {
+            TransactionSource::Signed(signed_transaction) => {
+                (signed_transaction.transaction, signed_transaction.signature)
+            }
+            TransactionSource::Unsigned {
+                transaction,
+                signature,
+            } => (transaction, signature),
+            TransactionSource::File { file, signature } => {
+                if let Some(network_connection_config) = &self.network_connection_config {
+                    crate::transaction_file::check_network(
+                        &file.path,
+                        file.network.as_deref(),
+                        network_connection_config,
+                    )?;
+                }
+                decode_file(file, signature)?
+            }
+        };
+        let transaction_hash = transaction.get_hash_and_size().0;
+        let public_key = self
+            .public_key
+            .unwrap_or_else(|| transaction.public_key.clone());
+        let signature_valid = signature.verify(transaction_hash.as_ref(), &public_key);
+        let access_key_problems = match &self.network_connection_config {
+            Some(network_connection_config) => {
+                Some(check_access_key(network_connection_config, &transaction, &public_key).await?)
+            }
+            None => None,
+        };
+        match output_format {
+            crate::common::OutputFormat::Plaintext => {
+                println!("\nTransaction hash: {}", transaction_hash);
+                println!("Public key:       {}", public_key);
+                if signature_valid {
+                    println!("Signature:        {} (valid)", signature);
+                } else {
+                    println!("Signature:        {} (INVALID)", signature);
+                }
+                if public_key != transaction.public_key {
+                    println!(
+                        "Note: the network checks the signature with the public key of the transaction, {}",
+                        transaction.public_key
+                    );
+                }
+                match &access_key_problems {
+                    Some(problems) if problems.is_empty() => println!(
+                        "Access key:       {} allows every action of the transaction",
+                        transaction.signer_id
+                    ),
+                    Some(problems) => {
+                        println!("Access key:       the transaction would be rejected:");
+                        for problem in problems {
+                            println!("  - {}", problem);
+                        }
+                    }
+                    None => {}
+                }
+            }
+            crate::common::OutputFormat::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "transaction_hash": transaction_hash,
+                        "public_key": public_key,
+                        "signature": signature,
+                        "signature_valid": signature_valid,
+                        "access_key_problems": access_key_problems,
+                    }))?
+                );
+            }
+        }
+        match access_key_problems {
+            _ if !signature_valid => Err(color_eyre::Report::msg(
+                "The signature is not valid for the transaction",
+            )),
+            Some(problems) if !problems.is_empty() => Err(color_eyre::Report::msg(
+                "The access key does not allow the transaction",
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn decode_file(
+    file: crate::transaction_file::TransactionFile,
+    signature: Option<near_crypto::Signature>,
+) -> color_eyre::eyre::Result<(
+    near_primitives::transaction::Transaction,
+    near_crypto::Signature,
+)> {
+    if file.kind != Some(crate::transaction_file::TransactionKind::SignedTransaction) {
+        if let Ok(transaction) =
+            near_primitives::transaction::Transaction::try_from_slice(&file.bytes)
+        {
+            return match signature {
+                Some(signature) => Ok((transaction, signature)),
+                None => Err(color_eyre::Report::msg(format!(
+                    "{} contains an unsigned transaction, `--signature` is required",
+                    file.path.display()
+                ))),
+            };
+        }
+    }
+    let signed_transaction = near_primitives::transaction::SignedTransaction::try_from_slice(
+        &file.bytes,
+    )
+    .map_err(|err| {
+        color_eyre::Report::msg(format!(
+            "The transaction of {} could not be parsed: {}",
+            file.path.display(),
+            err
+        ))
+    })?;
+    Ok((
+        signed_transaction.transaction,
+        signature.unwrap_or(signed_transaction.signature),
+    ))
+}
+
+/// An RPC error that means the key is not an access key of the account
+pub(super) fn is_unknown_access_key_error(err: &near_jsonrpc_primitives::errors::RpcError) -> bool {
+    match &err.error_struct {
+        Some(near_jsonrpc_primitives::errors::RpcErrorKind::HandlerError(cause)) => {
+            cause.get("name").and_then(|name| name.as_str()) == Some("UNKNOWN_ACCESS_KEY")
+        }
+        _ => false,
+    }
+}
+
+/// The reasons why the network would reject the transaction signed with the key
+async fn check_access_key(
+    network_connection_config: &crate::common::ConnectionConfig,
+    transaction: &near_primitives::transaction::Transaction,
+    public_key: &near_crypto::PublicKey,
+) -> color_eyre::eyre::Result<Vec<String>> {
+    let access_key_view = match network_connection_config
+        .rpc_client()
+        .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
+            block_reference: near_primitives::types::Finality::Final.into(),
+            request: near_primitives::views::QueryRequest::ViewAccessKey {
+                account_id: transaction.signer_id.clone(),
+                public_key: public_key.clone(),
+            },
+        })
+        .await
+    {
+        Ok(near_jsonrpc_primitives::types::query::RpcQueryResponse {
+            kind:
+                near_jsonrpc_primitives::types::query::QueryResponseKind::AccessKey(access_key_view),
+            ..
+        }) => access_key_view,
+        Ok(_) => {
+            return Err(color_eyre::Report::msg(
+                "Unexpected response to the access key query",
+            ))
+        }
+        Err(err) if is_unknown_access_key_error(&err) => {
+            return Ok(vec![format!(
+                "{} is not an access key of {} on {}",
+                public_key,
+                transaction.signer_id,
+                network_connection_config.name()
+            )])
+        }
+        Err(err) => {
+            return Err(color_eyre::Report::msg(format!(
+                "Failed to fetch the access key {} of {}: {:?}",
+                public_key, transaction.signer_id, err
+            )))
+        }
+    };
+    let mut problems = vec![];
+    if transaction.nonce <= access_key_view.nonce {
+        problems.push(format!(
+            "the nonce {} is already used, the next one is {}",
+            transaction.nonce,
+            access_key_view.nonce + 1
+        ));
+    }
+    // The allowance is only checked against the gas cost of the transaction when it is limited
+    let max_gas_cost = match access_key_view.permission {
+        near_primitives::views::AccessKeyPermissionView::FunctionCall {
+            allowance: Some(_),
+            ..
+        } => crate::commands::construct_transaction_command::sign_transaction::cost_estimate::max_gas_cost(
+            network_connection_config,
+            transaction,
+        )
+        .await?,
+        _ => 0,
+    };
+    if !crate::common::access_key_permits(&access_key_view.permission, transaction, 0) {
+        if let near_primitives::views::AccessKeyPermissionView::FunctionCall {
+            allowance: _,
+            receiver_id,
+            method_names,
+        } = access_key_view.permission
+        {
+            problems.push(format!(
+                "the function-call key only permits a single call of {} on {} without a deposit",
+                if method_names.is_empty() {
+                    "any method".to_string()
+                } else {
+                    method_names
+                        .iter()
+                        .map(|method_name| format!("`{}`", method_name))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                },
+                receiver_id
+            ));
+        }
+    } else if let near_primitives::views::AccessKeyPermissionView::FunctionCall {
+        allowance: Some(allowance),
+        ..
+    } = access_key_view.permission
+    {
+        if allowance < max_gas_cost {
+            problems.push(format!(
+                "the allowance of the function-call key ({}) does not cover the gas cost of the transaction ({})",
+                crate::common::NearBalance::from_yoctonear(allowance).to_cli_arg(),
+                crate::common::NearBalance::from_yoctonear(max_gas_cost).to_cli_arg()
+            ));
+        }
+    }
+    Ok(problems)
+}
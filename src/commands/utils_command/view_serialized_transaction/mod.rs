@@ -1,51 +1,213 @@
 use dialoguer::Input;
 use near_primitives::borsh::BorshDeserialize;
+use std::convert::TryFrom;
 
 /// Using this utility, you can view the contents of a serialized transaction (signed or not).
 #[derive(Debug, Default, clap::Clap)]
 pub struct CliViewSerializedTransaction {
     transaction: Option<String>,
+    /// Read the transaction from a file instead (JSON envelope for `.json`, raw borsh otherwise)
+    #[clap(long, conflicts_with = "transaction")]
+    input: Option<crate::transaction_file::TransactionFile>,
 }
 
 #[derive(Debug)]
-pub struct ViewSerializedTransaction {
-    transaction: String,
+pub enum ViewSerializedTransaction {
+    Base64(String),
+    File(crate::transaction_file::TransactionFile),
 }
 
-impl From<CliViewSerializedTransaction> for ViewSerializedTransaction {
-    fn from(item: CliViewSerializedTransaction) -> Self {
-        let transaction: String = match item.transaction {
-            Some(transaction) => transaction,
-            None => ViewSerializedTransaction::input_transaction(),
-        };
-        Self { transaction }
+impl TryFrom<CliViewSerializedTransaction> for ViewSerializedTransaction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliViewSerializedTransaction) -> color_eyre::eyre::Result<Self> {
+        Ok(match (item.input, item.transaction) {
+            (Some(input), _) => Self::File(input),
+            (None, Some(transaction)) => Self::Base64(transaction),
+            (None, None) => Self::Base64(ViewSerializedTransaction::input_transaction()?),
+        })
     }
 }
 
 impl ViewSerializedTransaction {
-    fn input_transaction() -> String {
-        Input::new()
-            .with_prompt("Enter the hash of the transaction")
+    fn input_transaction() -> color_eyre::eyre::Result<String> {
+        crate::common::ensure_interactive(
+            "`<TRANSACTION>` or `--input <PATH>` of `view-serialized-transaction`",
+        )?;
+        Ok(Input::new()
+            .with_prompt("Enter the borsh-serialized transaction (signed or not) in base64")
             .interact_text()
-            .unwrap()
-    }
-
-    pub async fn process(self) -> crate::CliResult {
-        let serialize_from_base64 =
-            near_primitives::serialize::from_base64(&self.transaction).unwrap();
-        match near_primitives::transaction::Transaction::try_from_slice(&serialize_from_base64) {
-            Ok(transaction) => println!("\n{:#?}", &transaction),
-            Err(_) => {
-                match near_primitives::transaction::SignedTransaction::try_from_slice(
-                    &serialize_from_base64,
-                ) {
-                    Ok(signed_transaction) => println!("\n{:#?}", signed_transaction),
-                    Err(err) => {
-                        println!("\nError: Base64 transaction sequence is invalid: {}", err)
-                    }
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        match self {
+            Self::Base64(transaction) => vec![transaction.clone()],
+            Self::File(input) => vec!["--input".to_owned(), input.path.display().to_string()],
+        }
+    }
+
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::CliResult {
+        let (serialize_from_base64, kind, network) = match self {
+            Self::Base64(transaction) => (
+                near_primitives::serialize::from_base64(&transaction).map_err(|err| {
+                    color_eyre::Report::msg(format!(
+                        "Base64 transaction sequence is invalid: {}",
+                        err
+                    ))
+                })?,
+                None,
+                None,
+            ),
+            Self::File(input) => (input.bytes, input.kind, input.network),
+        };
+        let (transaction, signature) = parse_transaction(&serialize_from_base64, kind)?;
+        print_transaction(&transaction, signature.as_ref(), network, output_format)
+    }
+}
+
+/// An unsigned transaction, unless the bytes only parse as a signed one (or the file says it is
+/// signed)
+fn parse_transaction(
+    bytes: &[u8],
+    kind: Option<crate::transaction_file::TransactionKind>,
+) -> color_eyre::eyre::Result<(
+    near_primitives::transaction::Transaction,
+    Option<near_crypto::Signature>,
+)> {
+    if kind != Some(crate::transaction_file::TransactionKind::SignedTransaction) {
+        if let Ok(transaction) = near_primitives::transaction::Transaction::try_from_slice(bytes) {
+            return Ok((transaction, None));
+        }
+    }
+    let signed_transaction = near_primitives::transaction::SignedTransaction::try_from_slice(bytes)
+        .map_err(|err| {
+            color_eyre::Report::msg(format!("Base64 transaction sequence is invalid: {}", err))
+        })?;
+    Ok((
+        signed_transaction.transaction,
+        Some(signed_transaction.signature),
+    ))
+}
+
+fn print_transaction(
+    transaction: &near_primitives::transaction::Transaction,
+    signature: Option<&near_crypto::Signature>,
+    network: Option<String>,
+    output_format: crate::common::OutputFormat,
+) -> crate::CliResult {
+    let transaction_hash = transaction.get_hash_and_size().0;
+    let signature_valid = signature
+        .map(|signature| signature.verify(transaction_hash.as_ref(), &transaction.public_key));
+    match output_format {
+        crate::common::OutputFormat::Plaintext => {
+            println!("\nTransaction hash: {}", transaction_hash);
+            if let Some(network) = &network {
+                println!("Network:          {}", network);
+            }
+            println!("Signer:           {}", transaction.signer_id);
+            println!("Public key:       {}", transaction.public_key);
+            println!("Nonce:            {}", transaction.nonce);
+            println!("Receiver:         {}", transaction.receiver_id);
+            println!("Block hash:       {}", transaction.block_hash);
+            println!("Actions:");
+            for (index, action) in transaction.actions.iter().enumerate() {
+                println!(
+                    "  {}. {}",
+                    index + 1,
+                    crate::common::describe_action(action)
+                );
+            }
+            match (signature, signature_valid) {
+                (Some(signature), Some(true)) => {
+                    println!("Signature:        {} (valid)", signature)
                 }
+                (Some(signature), _) => println!(
+                    "Signature:        {} (INVALID: it is not made with the key {})",
+                    signature, transaction.public_key
+                ),
+                (None, _) => println!("Signature:        none (the transaction is not signed)"),
             }
+        }
+        crate::common::OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "transaction_hash": transaction_hash,
+                    "network": network,
+                    "signer_id": transaction.signer_id,
+                    "public_key": transaction.public_key,
+                    "nonce": transaction.nonce,
+                    "receiver_id": transaction.receiver_id,
+                    "block_hash": transaction.block_hash,
+                    "actions": transaction
+                        .actions
+                        .iter()
+                        .map(crate::common::describe_action)
+                        .collect::<Vec<_>>(),
+                    "signature": signature,
+                    "signature_valid": signature_valid,
+                }))?
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_primitives::borsh::BorshSerialize;
+
+    fn signed_transaction() -> near_primitives::transaction::SignedTransaction {
+        let secret_key = near_crypto::SecretKey::from_seed(
+            near_crypto::KeyType::ED25519,
+            "view_serialized_transaction",
+        );
+        let transaction = near_primitives::transaction::Transaction {
+            signer_id: "volodymyr.testnet".to_string(),
+            public_key: secret_key.public_key(),
+            nonce: 2,
+            receiver_id: "alice.testnet".to_string(),
+            block_hash: Default::default(),
+            actions: vec![near_primitives::transaction::Action::Transfer(
+                near_primitives::transaction::TransferAction { deposit: 1 },
+            )],
         };
-        Ok(())
+        let signature = secret_key.sign(transaction.get_hash_and_size().0.as_ref());
+        near_primitives::transaction::SignedTransaction::new(signature, transaction)
+    }
+
+    #[test]
+    fn unsigned_transaction_has_no_signature() {
+        let signed_transaction = signed_transaction();
+        let bytes = signed_transaction.transaction.try_to_vec().unwrap();
+        let (transaction, signature) = parse_transaction(&bytes, None).unwrap();
+        assert_eq!(transaction, signed_transaction.transaction);
+        assert_eq!(signature, None);
+        assert!(parse_transaction(
+            &bytes,
+            Some(crate::transaction_file::TransactionKind::SignedTransaction)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn signed_transaction_has_its_signature() {
+        let signed_transaction = signed_transaction();
+        let bytes = signed_transaction.try_to_vec().unwrap();
+        for kind in vec![
+            None,
+            Some(crate::transaction_file::TransactionKind::SignedTransaction),
+        ] {
+            let (transaction, signature) = parse_transaction(&bytes, kind).unwrap();
+            assert_eq!(transaction, signed_transaction.transaction);
+            assert_eq!(signature, Some(signed_transaction.signature.clone()));
+        }
+    }
+
+    #[test]
+    fn garbage_is_not_a_transaction() {
+        assert!(parse_transaction(b"not a transaction", None).is_err());
     }
 }
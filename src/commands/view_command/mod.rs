@@ -1,11 +1,12 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::{TryFrom, TryInto};
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 mod view_account;
 mod view_contract_code;
 mod view_contract_state;
 mod view_nonce;
-mod view_transaction_status;
+pub mod view_transaction_status;
 
 /// инструмент выбора to view
 #[derive(Debug, Default, clap::Clap)]
@@ -19,19 +20,25 @@ pub struct ViewQueryRequest {
     pub query: QueryRequest,
 }
 
-impl From<CliViewQueryRequest> for ViewQueryRequest {
-    fn from(item: CliViewQueryRequest) -> Self {
+impl TryFrom<CliViewQueryRequest> for ViewQueryRequest {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliViewQueryRequest) -> color_eyre::eyre::Result<Self> {
         let query = match item.query {
-            Some(cli_query_request) => QueryRequest::from(cli_query_request),
-            None => QueryRequest::choose_query_request(),
+            Some(cli_query_request) => QueryRequest::try_from(cli_query_request)?,
+            None => QueryRequest::choose_query_request()?,
         };
-        ViewQueryRequest { query }
+        Ok(ViewQueryRequest { query })
     }
 }
 
 impl ViewQueryRequest {
-    pub async fn process(self) -> crate::CliResult {
-        self.query.process().await
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.query.to_cli_args()
+    }
+
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::CliResult {
+        self.query.process(output_format).await
     }
 }
 
@@ -64,32 +71,35 @@ pub enum QueryRequest {
     Nonce(self::view_nonce::operation_mode::OperationMode),
 }
 
-impl From<CliQueryRequest> for QueryRequest {
-    fn from(item: CliQueryRequest) -> Self {
-        match item {
+impl TryFrom<CliQueryRequest> for QueryRequest {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliQueryRequest) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliQueryRequest::AccountSummary(cli_operation_mode) => {
-                QueryRequest::AccountSummary(cli_operation_mode.into())
+                QueryRequest::AccountSummary(cli_operation_mode.try_into()?)
             }
             CliQueryRequest::ContractCode(cli_operation_mode) => {
-                QueryRequest::ContractCode(cli_operation_mode.into())
+                QueryRequest::ContractCode(cli_operation_mode.try_into()?)
             }
             CliQueryRequest::ContractState(cli_operation_mode) => {
-                QueryRequest::ContractState(cli_operation_mode.into())
+                QueryRequest::ContractState(cli_operation_mode.try_into()?)
             }
             CliQueryRequest::Transaction(cli_operation_mode) => {
-                QueryRequest::Transaction(cli_operation_mode.into())
+                QueryRequest::Transaction(cli_operation_mode.try_into()?)
             }
             CliQueryRequest::Nonce(cli_operation_mode) => {
-                QueryRequest::Nonce(cli_operation_mode.into())
+                QueryRequest::Nonce(cli_operation_mode.try_into()?)
             }
-        }
+        })
     }
 }
 
 impl QueryRequest {
-    fn choose_query_request() -> Self {
+    fn choose_query_request() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = QueryRequestDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let requests = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -115,16 +125,38 @@ impl QueryRequest {
             }
             QueryRequestDiscriminants::Nonce => CliQueryRequest::Nonce(Default::default()),
         };
-        Self::from(cli_request)
+        Self::try_from(cli_request)
     }
 
-    pub async fn process(self) -> crate::CliResult {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (command, mut args) = match self {
+            Self::AccountSummary(operation_mode) => {
+                ("account-summary", operation_mode.to_cli_args())
+            }
+            Self::ContractCode(operation_mode) => ("contract-code", operation_mode.to_cli_args()),
+            Self::ContractState(operation_mode) => ("contract-state", operation_mode.to_cli_args()),
+            Self::Transaction(operation_mode) => ("transaction", operation_mode.to_cli_args()),
+            Self::Nonce(operation_mode) => ("nonce", operation_mode.to_cli_args()),
+        };
+        args.insert(0, command.to_owned());
+        args
+    }
+
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::CliResult {
         match self {
-            QueryRequest::AccountSummary(operation_mode) => operation_mode.process().await,
-            QueryRequest::ContractCode(operation_mode) => operation_mode.process().await,
-            QueryRequest::ContractState(operation_mode) => operation_mode.process().await,
-            QueryRequest::Transaction(operation_mode) => operation_mode.process().await,
-            QueryRequest::Nonce(operation_mode) => operation_mode.process().await,
+            QueryRequest::AccountSummary(operation_mode) => {
+                operation_mode.process(output_format).await
+            }
+            QueryRequest::ContractCode(operation_mode) => {
+                operation_mode.process(output_format).await
+            }
+            QueryRequest::ContractState(operation_mode) => {
+                operation_mode.process(output_format).await
+            }
+            QueryRequest::Transaction(operation_mode) => {
+                operation_mode.process(output_format).await
+            }
+            QueryRequest::Nonce(operation_mode) => operation_mode.process(output_format).await,
         }
     }
 }
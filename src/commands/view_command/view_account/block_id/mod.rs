@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::{TryFrom, TryInto};
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 mod block_id_hash;
@@ -25,24 +26,27 @@ pub enum BlockId {
     AtBlockHash(self::block_id_hash::BlockIdHash),
 }
 
-impl From<CliBlockId> for BlockId {
-    fn from(item: CliBlockId) -> Self {
-        match item {
+impl TryFrom<CliBlockId> for BlockId {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliBlockId) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliBlockId::AtFinalBlock => Self::AtFinalBlock,
             CliBlockId::AtBlockHeight(cli_block_id_height) => {
-                Self::AtBlockHeight(cli_block_id_height.into())
+                Self::AtBlockHeight(cli_block_id_height.try_into()?)
             }
             CliBlockId::AtBlockHash(cli_block_id_hash) => {
-                Self::AtBlockHash(cli_block_id_hash.into())
+                Self::AtBlockHash(cli_block_id_hash.try_into()?)
             }
-        }
+        })
     }
 }
 
 impl BlockId {
-    pub fn choose_block_id() -> Self {
+    pub fn choose_block_id() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = BlockIdDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let blocks = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -58,153 +62,184 @@ impl BlockId {
             BlockIdDiscriminants::AtBlockHeight => CliBlockId::AtBlockHeight(Default::default()),
             BlockIdDiscriminants::AtBlockHash => CliBlockId::AtBlockHash(Default::default()),
         };
-        Self::from(cli_block_id)
+        Self::try_from(cli_block_id)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (block_id, mut args) = match self {
+            Self::AtFinalBlock => ("at-final-block", vec![]),
+            Self::AtBlockHeight(block_id_height) => {
+                ("at-block-height", block_id_height.to_cli_args())
+            }
+            Self::AtBlockHash(block_id_hash) => ("at-block-hash", block_id_hash.to_cli_args()),
+        };
+        args.insert(0, block_id.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         sender_account_id: String,
         network_connection_config: crate::common::ConnectionConfig,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         println!();
         match self {
             Self::AtBlockHeight(block_id_height) => {
                 block_id_height
-                    .process(sender_account_id, network_connection_config)
+                    .process(sender_account_id, network_connection_config, output_format)
                     .await
             }
             Self::AtBlockHash(block_id_hash) => {
                 block_id_hash
-                    .process(sender_account_id, network_connection_config)
+                    .process(sender_account_id, network_connection_config, output_format)
                     .await
             }
             Self::AtFinalBlock => {
-                self.display_account_info(sender_account_id.clone(), &network_connection_config)
-                    .await?;
-                self.display_access_key_list(sender_account_id.clone(), &network_connection_config)
-                    .await?;
-                Ok(())
+                display_account_summary(
+                    sender_account_id,
+                    network_connection_config.rpc_client(),
+                    near_primitives::types::Finality::Final.into(),
+                    output_format,
+                )
+                .await
             }
         }
     }
+}
 
-    fn rpc_client(&self, selected_server_url: &str) -> near_jsonrpc_client::JsonRpcClient {
-        near_jsonrpc_client::new_client(&selected_server_url)
-    }
-
-    async fn display_account_info(
-        &self,
-        account_id: String,
-        network_connection_config: &crate::common::ConnectionConfig,
-    ) -> crate::CliResult {
-        let query_view_method_response = self
-            .rpc_client(network_connection_config.rpc_url().as_str())
-            .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
-                block_reference: near_primitives::types::Finality::Final.into(),
-                request: near_primitives::views::QueryRequest::ViewAccount {
-                    account_id: account_id.clone(),
-                },
-            })
-            .await
-            .map_err(|err| {
-                color_eyre::Report::msg(format!(
-                    "Failed to fetch query for view account: {:?}",
-                    err
-                ))
-            })?;
-        let account_view =
-            if let near_jsonrpc_primitives::types::query::QueryResponseKind::ViewAccount(result) =
-                query_view_method_response.kind
-            {
-                result
-            } else {
-                return Err(color_eyre::Report::msg(format!("Error call result")));
-            };
+pub async fn display_account_summary(
+    account_id: String,
+    rpc_client: near_jsonrpc_client::JsonRpcClient,
+    block_reference: near_primitives::types::BlockReference,
+    output_format: crate::common::OutputFormat,
+) -> crate::CliResult {
+    let query_view_method_response = rpc_client
+        .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
+            block_reference: block_reference.clone(),
+            request: near_primitives::views::QueryRequest::ViewAccount {
+                account_id: account_id.clone(),
+            },
+        })
+        .await
+        .map_err(|err| {
+            color_eyre::Report::msg(format!("Failed to fetch query for view account: {:?}", err))
+        })?;
+    let account_view =
+        if let near_jsonrpc_primitives::types::query::QueryResponseKind::ViewAccount(result) =
+            query_view_method_response.kind
+        {
+            result
+        } else {
+            return Err(color_eyre::Report::msg(format!("Error call result")));
+        };
 
-        println!(
-            "Account details for '{}' at block #{} ({})\n\
-            Native account balance: {}\n\
-            Validator stake: {}\n\
-            Storage used by the account: {} bytes",
-            account_id,
-            query_view_method_response.block_height,
-            query_view_method_response.block_hash,
-            crate::common::NearBalance::from_yoctonear(account_view.amount),
-            crate::common::NearBalance::from_yoctonear(account_view.locked),
-            account_view.storage_usage
-        );
-        if account_view.code_hash == near_primitives::hash::CryptoHash::default() {
-            println!("Contract code is not deployed to this account.");
+    let query_access_key_list_response = rpc_client
+        .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
+            block_reference,
+            request: near_primitives::views::QueryRequest::ViewAccessKeyList {
+                account_id: account_id.clone(),
+            },
+        })
+        .await
+        .map_err(|err| {
+            color_eyre::Report::msg(format!(
+                "Failed to fetch query for view key list: {:?}",
+                err
+            ))
+        })?;
+    let access_key_view =
+        if let near_jsonrpc_primitives::types::query::QueryResponseKind::AccessKeyList(result) =
+            query_access_key_list_response.kind
+        {
+            result
         } else {
-            println!(
-                "Contract code SHA-256 checksum (hex): {}",
-                hex::encode(account_view.code_hash.as_ref())
+            return Err(color_eyre::Report::msg(format!("Error call result")));
+        };
+
+    match output_format {
+        crate::common::OutputFormat::Plaintext => {
+            display_account_info(
+                &account_id,
+                query_view_method_response.block_height,
+                &query_view_method_response.block_hash,
+                &account_view,
             );
+            display_access_key_list(&access_key_view);
         }
-        Ok(())
-    }
-
-    async fn display_access_key_list(
-        &self,
-        account_id: String,
-        network_connection_config: &crate::common::ConnectionConfig,
-    ) -> crate::CliResult {
-        let query_view_method_response = self
-            .rpc_client(network_connection_config.rpc_url().as_str())
-            .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
-                block_reference: near_primitives::types::Finality::Final.into(),
-                request: near_primitives::views::QueryRequest::ViewAccessKeyList {
-                    account_id: account_id.clone(),
-                },
-            })
-            .await
-            .map_err(|err| {
-                color_eyre::Report::msg(format!(
-                    "Failed to fetch query for view key list: {:?}",
-                    err
-                ))
-            })?;
-        let access_key_view =
-            if let near_jsonrpc_primitives::types::query::QueryResponseKind::AccessKeyList(result) =
-                query_view_method_response.kind
-            {
-                result
-            } else {
-                return Err(color_eyre::Report::msg(format!("Error call result")));
-            };
-
-        println!("Number of access keys: {}", access_key_view.keys.len());
-        for (index, access_key) in access_key_view.keys.iter().enumerate() {
-            let permissions_message = match &access_key.access_key.permission {
-                near_primitives::views::AccessKeyPermissionView::FullAccess => {
-                    "full access".to_owned()
-                }
-                near_primitives::views::AccessKeyPermissionView::FunctionCall {
-                    allowance,
-                    receiver_id,
-                    method_names,
-                } => {
-                    let allowance_message = match allowance {
-                        Some(amount) => format!(
-                            "with an allowance of {}",
-                            crate::common::NearBalance::from_yoctonear(*amount)
-                        ),
-                        None => format!("with no limit"),
-                    };
-                    format!(
-                        "only do {:?} function calls on {} {}",
-                        method_names, receiver_id, allowance_message
-                    )
-                }
-            };
+        crate::common::OutputFormat::Json => {
             println!(
-                "{: >4}. {} (nonce: {}) is granted to {}",
-                index + 1,
-                access_key.public_key,
-                access_key.access_key.nonce,
-                permissions_message
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "account_id": account_id,
+                    "block_height": query_view_method_response.block_height,
+                    "block_hash": query_view_method_response.block_hash,
+                    "account": account_view,
+                    "access_keys": access_key_view.keys,
+                }))?
             );
         }
-        Ok(())
+    };
+    Ok(())
+}
+
+fn display_account_info(
+    account_id: &str,
+    block_height: near_primitives::types::BlockHeight,
+    block_hash: &near_primitives::hash::CryptoHash,
+    account_view: &near_primitives::views::AccountView,
+) {
+    println!(
+        "Account details for '{}' at block #{} ({})\n\
+        Native account balance: {}\n\
+        Validator stake: {}\n\
+        Storage used by the account: {} bytes",
+        account_id,
+        block_height,
+        block_hash,
+        crate::common::NearBalance::from_yoctonear(account_view.amount),
+        crate::common::NearBalance::from_yoctonear(account_view.locked),
+        account_view.storage_usage
+    );
+    if account_view.code_hash == near_primitives::hash::CryptoHash::default() {
+        println!("Contract code is not deployed to this account.");
+    } else {
+        println!(
+            "Contract code SHA-256 checksum (hex): {}",
+            hex::encode(account_view.code_hash.as_ref())
+        );
+    }
+}
+
+fn display_access_key_list(access_key_view: &near_primitives::views::AccessKeyList) {
+    println!("Number of access keys: {}", access_key_view.keys.len());
+    for (index, access_key) in access_key_view.keys.iter().enumerate() {
+        let permissions_message = match &access_key.access_key.permission {
+            near_primitives::views::AccessKeyPermissionView::FullAccess => "full access".to_owned(),
+            near_primitives::views::AccessKeyPermissionView::FunctionCall {
+                allowance,
+                receiver_id,
+                method_names,
+            } => {
+                let allowance_message = match allowance {
+                    Some(amount) => format!(
+                        "with an allowance of {}",
+                        crate::common::NearBalance::from_yoctonear(*amount)
+                    ),
+                    None => format!("with no limit"),
+                };
+                format!(
+                    "only do {:?} function calls on {} {}",
+                    method_names, receiver_id, allowance_message
+                )
+            }
+        };
+        println!(
+            "{: >4}. {} (nonce: {}) is granted to {}",
+            index + 1,
+            access_key.public_key,
+            access_key.access_key.nonce,
+            permissions_message
+        );
     }
 }
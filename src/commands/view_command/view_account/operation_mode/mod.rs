@@ -1,3 +1,4 @@
+use std::convert::{TryFrom, TryInto};
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
 pub mod online_mode;
@@ -14,19 +15,25 @@ pub struct OperationMode {
     pub mode: Mode,
 }
 
-impl From<CliOperationMode> for OperationMode {
-    fn from(item: CliOperationMode) -> Self {
+impl TryFrom<CliOperationMode> for OperationMode {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliOperationMode) -> color_eyre::eyre::Result<Self> {
         let mode = match item.mode {
-            Some(cli_mode) => Mode::from(cli_mode),
-            None => Mode::choose_mode(),
+            Some(cli_mode) => Mode::try_from(cli_mode)?,
+            None => Mode::choose_mode()?,
         };
-        Self { mode }
+        Ok(Self { mode })
     }
 }
 
 impl OperationMode {
-    pub async fn process(self) -> crate::CliResult {
-        self.mode.process().await
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.mode.to_cli_args()
+    }
+
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::CliResult {
+        self.mode.process(output_format).await
     }
 }
 
@@ -43,22 +50,32 @@ pub enum Mode {
     Network(self::online_mode::NetworkArgs),
 }
 
-impl From<CliMode> for Mode {
-    fn from(item: CliMode) -> Self {
-        match item {
-            CliMode::Network(cli_network_args) => Self::Network(cli_network_args.into()),
-        }
+impl TryFrom<CliMode> for Mode {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliMode) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
+            CliMode::Network(cli_network_args) => Self::Network(cli_network_args.try_into()?),
+        })
     }
 }
 
 impl Mode {
-    pub fn choose_mode() -> Self {
-        Self::from(CliMode::Network(Default::default()))
+    pub fn choose_mode() -> color_eyre::eyre::Result<Self> {
+        Self::try_from(CliMode::Network(Default::default()))
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (mode, mut args) = match self {
+            Self::Network(network_args) => ("network", network_args.to_cli_args()),
+        };
+        args.insert(0, mode.to_owned());
+        args
     }
 
-    pub async fn process(self) -> crate::CliResult {
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::CliResult {
         match self {
-            Self::Network(network_args) => network_args.process().await,
+            Self::Network(network_args) => network_args.process(output_format).await,
         }
     }
 }
@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::{TryFrom, TryInto};
 
 #[derive(Debug, clap::Clap)]
 pub enum CliSendTo {
@@ -11,28 +12,43 @@ pub enum SendTo {
     Account(Sender),
 }
 
-impl From<CliSendTo> for SendTo {
-    fn from(item: CliSendTo) -> Self {
-        match item {
+impl TryFrom<CliSendTo> for SendTo {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSendTo) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliSendTo::Account(cli_sender) => {
-                let sender = Sender::from(cli_sender);
+                let sender = Sender::try_from(cli_sender)?;
                 Self::Account(sender)
             }
-        }
+        })
     }
 }
 
 impl SendTo {
-    pub fn send_to() -> Self {
-        Self::from(CliSendTo::Account(Default::default()))
+    pub fn send_to() -> color_eyre::eyre::Result<Self> {
+        Self::try_from(CliSendTo::Account(Default::default()))
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (command, mut args) = match self {
+            Self::Account(sender) => ("account", sender.to_cli_args()),
+        };
+        args.insert(0, command.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         network_connection_config: crate::common::ConnectionConfig,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
-            SendTo::Account(sender) => sender.process(network_connection_config).await,
+            SendTo::Account(sender) => {
+                sender
+                    .process(network_connection_config, output_format)
+                    .await
+            }
         }
     }
 }
@@ -51,38 +67,52 @@ pub struct Sender {
     selected_block_id: super::block_id::BlockId,
 }
 
-impl From<CliSender> for Sender {
-    fn from(item: CliSender) -> Self {
+impl TryFrom<CliSender> for Sender {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSender) -> color_eyre::eyre::Result<Self> {
         let sender_account_id: String = match item.sender_account_id {
             Some(cli_sender_account_id) => cli_sender_account_id,
-            None => Sender::input_sender_account_id(),
+            None => Sender::input_sender_account_id()?,
         };
         let selected_block_id: super::block_id::BlockId = match item.selected_block_id {
-            Some(cli_block_id) => cli_block_id.into(),
-            None => super::block_id::BlockId::choose_block_id(),
+            Some(cli_block_id) => cli_block_id.try_into()?,
+            None => super::block_id::BlockId::choose_block_id()?,
         };
-        Self {
+        Ok(Self {
             sender_account_id,
             selected_block_id,
-        }
+        })
     }
 }
 
 impl Sender {
-    pub fn input_sender_account_id() -> String {
+    pub fn input_sender_account_id() -> color_eyre::eyre::Result<String> {
+        crate::common::ensure_interactive("`<SENDER_ACCOUNT_ID>` of `account`")?;
         println!();
-        Input::new()
+        Ok(Input::new()
             .with_prompt("What Account ID do you need to view?")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![self.sender_account_id.clone()];
+        args.extend(self.selected_block_id.to_cli_args());
+        args
     }
 
     pub async fn process(
         self,
         network_connection_config: crate::common::ConnectionConfig,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         self.selected_block_id
-            .process(self.sender_account_id, network_connection_config)
+            .process(
+                self.sender_account_id,
+                network_connection_config,
+                output_format,
+            )
             .await
     }
 }
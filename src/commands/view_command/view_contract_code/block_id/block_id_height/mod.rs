@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryFrom;
 use std::io::Write;
 
 /// Specify the block_id height for this contract to view
@@ -12,26 +13,30 @@ pub struct BlockIdHeight {
     block_id_height: near_primitives::types::BlockHeight,
 }
 
-impl From<CliBlockIdHeight> for BlockIdHeight {
-    fn from(item: CliBlockIdHeight) -> Self {
+impl TryFrom<CliBlockIdHeight> for BlockIdHeight {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliBlockIdHeight) -> color_eyre::eyre::Result<Self> {
         let block_id_height: near_primitives::types::BlockHeight = match item.block_id_height {
             Some(cli_block_id_hash) => cli_block_id_hash,
-            None => BlockIdHeight::input_block_id_height(),
+            None => BlockIdHeight::input_block_id_height()?,
         };
-        Self { block_id_height }
+        Ok(Self { block_id_height })
     }
 }
 
 impl BlockIdHeight {
-    pub fn input_block_id_height() -> near_primitives::types::BlockHeight {
-        Input::new()
+    pub fn input_block_id_height() -> color_eyre::eyre::Result<near_primitives::types::BlockHeight>
+    {
+        crate::common::ensure_interactive("`<BLOCK_ID_HEIGHT>` of `at-block-height`")?;
+        Ok(Input::new()
             .with_prompt("Type the block ID height for this contract")
             .interact_text()
-            .unwrap()
+            .unwrap())
     }
 
-    fn rpc_client(&self, selected_server_url: &str) -> near_jsonrpc_client::JsonRpcClient {
-        near_jsonrpc_client::new_client(&selected_server_url)
+    pub fn to_cli_args(&self) -> Vec<String> {
+        vec![self.block_id_height.to_string()]
     }
 
     pub async fn process(
@@ -39,15 +44,16 @@ impl BlockIdHeight {
         contract_id: String,
         network_connection_config: crate::common::ConnectionConfig,
         file_path: Option<std::path::PathBuf>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
-        let query_view_method_response = self
-            .rpc_client(network_connection_config.archival_rpc_url().as_str())
+        let query_view_method_response = network_connection_config
+            .archival_rpc_client()
             .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
                 block_reference: near_primitives::types::BlockReference::BlockId(
                     near_primitives::types::BlockId::Height(self.block_id_height.clone()),
                 ),
                 request: near_primitives::views::QueryRequest::ViewCode {
-                    account_id: contract_id,
+                    account_id: contract_id.clone(),
                 },
             })
             .await
@@ -65,24 +71,41 @@ impl BlockIdHeight {
             } else {
                 return Err(color_eyre::Report::msg(format!("Error call result")));
             };
-        match &file_path {
-            Some(file_path) => {
-                let dir_name = &file_path.parent().unwrap();
-                std::fs::create_dir_all(&dir_name)?;
-                std::fs::File::create(file_path)
-                    .map_err(|err| {
-                        color_eyre::Report::msg(format!("Failed to create file: {:?}", err))
-                    })?
-                    .write(&call_access_view.code)
-                    .map_err(|err| {
-                        color_eyre::Report::msg(format!("Failed to write to file: {:?}", err))
-                    })?;
-                println!("\nThe file {:?} was downloaded successfully", file_path);
-            }
-            None => {
-                println!("\nHash of the contract: {}", &call_access_view.hash)
-            }
+        if let Some(file_path) = &file_path {
+            let dir_name = &file_path.parent().unwrap();
+            std::fs::create_dir_all(&dir_name)?;
+            std::fs::File::create(file_path)
+                .map_err(|err| {
+                    color_eyre::Report::msg(format!("Failed to create file: {:?}", err))
+                })?
+                .write(&call_access_view.code)
+                .map_err(|err| {
+                    color_eyre::Report::msg(format!("Failed to write to file: {:?}", err))
+                })?;
         }
+        match output_format {
+            crate::common::OutputFormat::Plaintext => match &file_path {
+                Some(file_path) => {
+                    println!("\nThe file {:?} was downloaded successfully", file_path);
+                }
+                None => {
+                    println!("\nHash of the contract: {}", &call_access_view.hash)
+                }
+            },
+            crate::common::OutputFormat::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "contract_id": contract_id,
+                        "block_height": query_view_method_response.block_height,
+                        "block_hash": query_view_method_response.block_hash,
+                        "code_hash": call_access_view.hash,
+                        "code_size": call_access_view.code.len(),
+                        "file_path": file_path,
+                    }))?
+                );
+            }
+        };
         Ok(())
     }
 }
@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::{TryFrom, TryInto};
 use std::io::Write;
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
@@ -26,24 +27,27 @@ pub enum BlockId {
     AtBlockHash(self::block_id_hash::BlockIdHash),
 }
 
-impl From<CliBlockId> for BlockId {
-    fn from(item: CliBlockId) -> Self {
-        match item {
+impl TryFrom<CliBlockId> for BlockId {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliBlockId) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliBlockId::AtFinalBlock => Self::AtFinalBlock,
             CliBlockId::AtBlockHeight(cli_block_id_height) => {
-                Self::AtBlockHeight(cli_block_id_height.into())
+                Self::AtBlockHeight(cli_block_id_height.try_into()?)
             }
             CliBlockId::AtBlockHash(cli_block_id_hash) => {
-                Self::AtBlockHash(cli_block_id_hash.into())
+                Self::AtBlockHash(cli_block_id_hash.try_into()?)
             }
-        }
+        })
     }
 }
 
 impl BlockId {
-    pub fn choose_block_id() -> Self {
+    pub fn choose_block_id() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = BlockIdDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let blocks = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -59,7 +63,19 @@ impl BlockId {
             BlockIdDiscriminants::AtBlockHeight => CliBlockId::AtBlockHeight(Default::default()),
             BlockIdDiscriminants::AtBlockHash => CliBlockId::AtBlockHash(Default::default()),
         };
-        Self::from(cli_block_id)
+        Self::try_from(cli_block_id)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (block_id, mut args) = match self {
+            Self::AtFinalBlock => ("at-final-block", vec![]),
+            Self::AtBlockHeight(block_id_height) => {
+                ("at-block-height", block_id_height.to_cli_args())
+            }
+            Self::AtBlockHash(block_id_hash) => ("at-block-hash", block_id_hash.to_cli_args()),
+        };
+        args.insert(0, block_id.to_owned());
+        args
     }
 
     pub async fn process(
@@ -67,42 +83,55 @@ impl BlockId {
         contract_id: String,
         network_connection_config: crate::common::ConnectionConfig,
         file_path: Option<std::path::PathBuf>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         println!();
         match self {
             Self::AtBlockHeight(block_id_height) => {
                 block_id_height
-                    .process(contract_id, network_connection_config, file_path)
+                    .process(
+                        contract_id,
+                        network_connection_config,
+                        file_path,
+                        output_format,
+                    )
                     .await
             }
             Self::AtBlockHash(block_id_hash) => {
                 block_id_hash
-                    .process(contract_id, network_connection_config, file_path)
+                    .process(
+                        contract_id,
+                        network_connection_config,
+                        file_path,
+                        output_format,
+                    )
                     .await
             }
             Self::AtFinalBlock => {
-                self.at_final_block(contract_id, network_connection_config, file_path)
-                    .await
+                self.at_final_block(
+                    contract_id,
+                    network_connection_config,
+                    file_path,
+                    output_format,
+                )
+                .await
             }
         }
     }
 
-    fn rpc_client(&self, selected_server_url: &str) -> near_jsonrpc_client::JsonRpcClient {
-        near_jsonrpc_client::new_client(&selected_server_url)
-    }
-
     async fn at_final_block(
         self,
         contract_id: String,
         network_connection_config: crate::common::ConnectionConfig,
         file_path: Option<std::path::PathBuf>,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
-        let query_view_method_response = self
-            .rpc_client(network_connection_config.rpc_url().as_str())
+        let query_view_method_response = network_connection_config
+            .rpc_client()
             .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
                 block_reference: near_primitives::types::Finality::Final.into(),
                 request: near_primitives::views::QueryRequest::ViewCode {
-                    account_id: contract_id,
+                    account_id: contract_id.clone(),
                 },
             })
             .await
@@ -120,24 +149,41 @@ impl BlockId {
             } else {
                 return Err(color_eyre::Report::msg(format!("Error call result")));
             };
-        match &file_path {
-            Some(file_path) => {
-                let dir_name = &file_path.parent().unwrap();
-                std::fs::create_dir_all(&dir_name)?;
-                std::fs::File::create(file_path)
-                    .map_err(|err| {
-                        color_eyre::Report::msg(format!("Failed to create file: {:?}", err))
-                    })?
-                    .write(&call_access_view.code)
-                    .map_err(|err| {
-                        color_eyre::Report::msg(format!("Failed to write to file: {:?}", err))
-                    })?;
-                println!("\nThe file {:?} was downloaded successfully", file_path);
-            }
-            None => {
-                println!("\nHash of the contract: {}", &call_access_view.hash)
-            }
+        if let Some(file_path) = &file_path {
+            let dir_name = &file_path.parent().unwrap();
+            std::fs::create_dir_all(&dir_name)?;
+            std::fs::File::create(file_path)
+                .map_err(|err| {
+                    color_eyre::Report::msg(format!("Failed to create file: {:?}", err))
+                })?
+                .write(&call_access_view.code)
+                .map_err(|err| {
+                    color_eyre::Report::msg(format!("Failed to write to file: {:?}", err))
+                })?;
         }
+        match output_format {
+            crate::common::OutputFormat::Plaintext => match &file_path {
+                Some(file_path) => {
+                    println!("\nThe file {:?} was downloaded successfully", file_path);
+                }
+                None => {
+                    println!("\nHash of the contract: {}", &call_access_view.hash)
+                }
+            },
+            crate::common::OutputFormat::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "contract_id": contract_id,
+                        "block_height": query_view_method_response.block_height,
+                        "block_hash": query_view_method_response.block_hash,
+                        "code_hash": call_access_view.hash,
+                        "code_size": call_access_view.code.len(),
+                        "file_path": file_path,
+                    }))?
+                );
+            }
+        };
         Ok(())
     }
 }
@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryInto;
 
 // download contract file
 #[derive(Debug, Default, clap::Clap)]
@@ -15,41 +16,57 @@ pub struct ContractFile {
 }
 
 impl ContractFile {
-    pub fn from(item: CliContractFile, contract_id: &str) -> Self {
+    pub fn from(item: CliContractFile, contract_id: &str) -> color_eyre::eyre::Result<Self> {
         let file_path = match item.file_path {
             Some(cli_file_path) => Some(cli_file_path),
-            None => ContractFile::input_file_path(contract_id),
+            None => ContractFile::input_file_path(contract_id)?,
         };
         let selected_block_id: super::super::super::block_id::BlockId = match item.selected_block_id
         {
-            Some(cli_block_id) => cli_block_id.into(),
-            None => super::super::super::block_id::BlockId::choose_block_id(),
+            Some(cli_block_id) => cli_block_id.try_into()?,
+            None => super::super::super::block_id::BlockId::choose_block_id()?,
         };
-        ContractFile {
+        Ok(ContractFile {
             file_path,
             selected_block_id,
+        })
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(file_path) = &self.file_path {
+            args.push(file_path.display().to_string());
         }
+        args.extend(self.selected_block_id.to_cli_args());
+        args
     }
 }
 
 impl ContractFile {
-    fn input_file_path(contract_id: &str) -> Option<std::path::PathBuf> {
+    fn input_file_path(contract_id: &str) -> color_eyre::eyre::Result<Option<std::path::PathBuf>> {
+        crate::common::ensure_interactive("`<FILE_PATH>` of `download`")?;
         println!();
         let input_file_path: String = Input::new()
             .with_prompt("Where to download the contract file?")
             .with_initial_text(format!("{}.wasm", contract_id))
             .interact_text()
             .unwrap();
-        Some(input_file_path.into())
+        Ok(Some(input_file_path.into()))
     }
 
     pub async fn process(
         self,
         contract_id: String,
         network_connection_config: crate::common::ConnectionConfig,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         self.selected_block_id
-            .process(contract_id, network_connection_config, self.file_path)
+            .process(
+                contract_id,
+                network_connection_config,
+                self.file_path,
+                output_format,
+            )
             .await
     }
 }
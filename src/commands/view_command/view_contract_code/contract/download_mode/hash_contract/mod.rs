@@ -1,3 +1,5 @@
+use std::convert::TryInto;
+
 // view a contract hash
 #[derive(Debug, Default, clap::Clap)]
 pub struct CliContractHash {
@@ -11,13 +13,17 @@ pub struct ContractHash {
 }
 
 impl ContractHash {
-    pub fn from(item: CliContractHash) -> Self {
+    pub fn from(item: CliContractHash) -> color_eyre::eyre::Result<Self> {
         let selected_block_id: super::super::super::block_id::BlockId = match item.selected_block_id
         {
-            Some(cli_block_id) => cli_block_id.into(),
-            None => super::super::super::block_id::BlockId::choose_block_id(),
+            Some(cli_block_id) => cli_block_id.try_into()?,
+            None => super::super::super::block_id::BlockId::choose_block_id()?,
         };
-        ContractHash { selected_block_id }
+        Ok(ContractHash { selected_block_id })
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.selected_block_id.to_cli_args()
     }
 }
 
@@ -26,9 +32,10 @@ impl ContractHash {
         self,
         contract_id: String,
         network_connection_config: crate::common::ConnectionConfig,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         self.selected_block_id
-            .process(contract_id, network_connection_config, None)
+            .process(contract_id, network_connection_config, None, output_format)
             .await
     }
 }
@@ -22,22 +22,32 @@ pub enum DownloadMode {
 }
 
 impl DownloadMode {
-    pub fn from(item: CliDownloadMode, contract_id: &str) -> Self {
-        match item {
+    pub fn from(item: CliDownloadMode, contract_id: &str) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliDownloadMode::Download(cli_contract_file) => DownloadMode::Download(
-                self::download_contract::ContractFile::from(cli_contract_file, contract_id),
+                self::download_contract::ContractFile::from(cli_contract_file, contract_id)?,
             ),
             CliDownloadMode::Hash(cli_contract_hash) => {
-                DownloadMode::Hash(self::hash_contract::ContractHash::from(cli_contract_hash))
+                DownloadMode::Hash(self::hash_contract::ContractHash::from(cli_contract_hash)?)
             }
-        }
+        })
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (command, mut args) = match self {
+            Self::Download(contract_file) => ("download", contract_file.to_cli_args()),
+            Self::Hash(contract_hash) => ("hash", contract_hash.to_cli_args()),
+        };
+        args.insert(0, command.to_owned());
+        args
     }
 }
 
 impl DownloadMode {
-    pub fn choose_download_mode(contract_id: &str) -> Self {
+    pub fn choose_download_mode(contract_id: &str) -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = DownloadModeDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let modes = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -59,16 +69,17 @@ impl DownloadMode {
         self,
         contract_id: String,
         network_connection_config: crate::common::ConnectionConfig,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             DownloadMode::Download(contract_file) => {
                 contract_file
-                    .process(contract_id, network_connection_config)
+                    .process(contract_id, network_connection_config, output_format)
                     .await
             }
             DownloadMode::Hash(contract_hash) => {
                 contract_hash
-                    .process(contract_id, network_connection_config)
+                    .process(contract_id, network_connection_config, output_format)
                     .await
             }
         }
@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 mod download_mode;
 
@@ -13,28 +14,43 @@ pub enum SendTo {
     Contract(Contract),
 }
 
-impl From<CliSendTo> for SendTo {
-    fn from(item: CliSendTo) -> Self {
-        match item {
+impl TryFrom<CliSendTo> for SendTo {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSendTo) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliSendTo::Contract(cli_sender) => {
-                let sender = Contract::from(cli_sender);
+                let sender = Contract::try_from(cli_sender)?;
                 Self::Contract(sender)
             }
-        }
+        })
     }
 }
 
 impl SendTo {
-    pub fn send_to() -> Self {
-        Self::from(CliSendTo::Contract(Default::default()))
+    pub fn send_to() -> color_eyre::eyre::Result<Self> {
+        Self::try_from(CliSendTo::Contract(Default::default()))
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (command, mut args) = match self {
+            Self::Contract(contract) => ("contract", contract.to_cli_args()),
+        };
+        args.insert(0, command.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         network_connection_config: crate::common::ConnectionConfig,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
-            SendTo::Contract(sender) => sender.process(network_connection_config).await,
+            SendTo::Contract(sender) => {
+                sender
+                    .process(network_connection_config, output_format)
+                    .await
+            }
         }
     }
 }
@@ -53,40 +69,50 @@ pub struct Contract {
     pub download_mode: self::download_mode::DownloadMode,
 }
 
-impl From<CliContract> for Contract {
-    fn from(item: CliContract) -> Self {
+impl TryFrom<CliContract> for Contract {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliContract) -> color_eyre::eyre::Result<Self> {
         let contract_id: String = match item.contract_id {
             Some(cli_contract_id) => cli_contract_id,
-            None => Contract::input_contract_id(),
+            None => Contract::input_contract_id()?,
         };
         let download_mode = match item.download_mode {
             Some(cli_download_mode) => {
-                self::download_mode::DownloadMode::from(cli_download_mode, &contract_id)
+                self::download_mode::DownloadMode::from(cli_download_mode, &contract_id)?
             }
-            None => self::download_mode::DownloadMode::choose_download_mode(&contract_id),
+            None => self::download_mode::DownloadMode::choose_download_mode(&contract_id)?,
         };
-        Self {
+        Ok(Self {
             contract_id,
             download_mode,
-        }
+        })
     }
 }
 
 impl Contract {
-    pub fn input_contract_id() -> String {
+    pub fn input_contract_id() -> color_eyre::eyre::Result<String> {
+        crate::common::ensure_interactive("`<CONTRACT_ID>` of `contract`")?;
         println!();
-        Input::new()
+        Ok(Input::new()
             .with_prompt("What contract do you need to view?")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![self.contract_id.clone()];
+        args.extend(self.download_mode.to_cli_args());
+        args
     }
 
     pub async fn process(
         self,
         network_connection_config: crate::common::ConnectionConfig,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         self.download_mode
-            .process(self.contract_id, network_connection_config)
+            .process(self.contract_id, network_connection_config, output_format)
             .await
     }
 }
@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 /// Specify the block_id hash for this contract to view
 #[derive(Debug, Default, clap::Clap)]
@@ -11,41 +12,45 @@ pub struct BlockIdHash {
     block_id_hash: near_primitives::hash::CryptoHash,
 }
 
-impl From<CliBlockIdHash> for BlockIdHash {
-    fn from(item: CliBlockIdHash) -> Self {
+impl TryFrom<CliBlockIdHash> for BlockIdHash {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliBlockIdHash) -> color_eyre::eyre::Result<Self> {
         let block_id_hash: near_primitives::hash::CryptoHash = match item.block_id_hash {
             Some(cli_block_id_hash) => cli_block_id_hash,
-            None => BlockIdHash::input_block_id_hash(),
+            None => BlockIdHash::input_block_id_hash()?,
         };
-        Self { block_id_hash }
+        Ok(Self { block_id_hash })
     }
 }
 
 impl BlockIdHash {
-    pub fn input_block_id_hash() -> near_primitives::hash::CryptoHash {
-        Input::new()
+    pub fn input_block_id_hash() -> color_eyre::eyre::Result<near_primitives::hash::CryptoHash> {
+        crate::common::ensure_interactive("`<BLOCK_ID_HASH>` of `at-block-hash`")?;
+        Ok(Input::new()
             .with_prompt("Type the block ID hash for this contract")
             .interact_text()
-            .unwrap()
+            .unwrap())
     }
 
-    fn rpc_client(&self, selected_server_url: &str) -> near_jsonrpc_client::JsonRpcClient {
-        near_jsonrpc_client::new_client(&selected_server_url)
+    pub fn to_cli_args(&self) -> Vec<String> {
+        vec![self.block_id_hash.to_string()]
     }
 
     pub async fn process(
         self,
         sender_account_id: String,
         network_connection_config: crate::common::ConnectionConfig,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
-        let query_view_method_response = self
-            .rpc_client(network_connection_config.archival_rpc_url().as_str())
+        let query_view_method_response = network_connection_config
+            .archival_rpc_client()
             .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
                 block_reference: near_primitives::types::BlockReference::BlockId(
                     near_primitives::types::BlockId::Hash(self.block_id_hash.clone()),
                 ),
                 request: near_primitives::views::QueryRequest::ViewState {
-                    account_id: sender_account_id,
+                    account_id: sender_account_id.clone(),
                     prefix: near_primitives::types::StoreKey::from(vec![]),
                 },
             })
@@ -64,14 +69,30 @@ impl BlockIdHash {
             } else {
                 return Err(color_eyre::Report::msg(format!("Error call result")));
             };
-        println!(
-            "\nContract state (values):\n{:#?}\n",
-            &call_access_view.values
-        );
-        println!(
-            "\nContract state (proof):\n{:#?}\n",
-            &call_access_view.proof
-        );
+        match output_format {
+            crate::common::OutputFormat::Plaintext => {
+                println!(
+                    "\nContract state (values):\n{:#?}\n",
+                    &call_access_view.values
+                );
+                println!(
+                    "\nContract state (proof):\n{:#?}\n",
+                    &call_access_view.proof
+                );
+            }
+            crate::common::OutputFormat::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "account_id": sender_account_id,
+                        "block_height": query_view_method_response.block_height,
+                        "block_hash": query_view_method_response.block_hash,
+                        "values": call_access_view.values,
+                        "proof": call_access_view.proof,
+                    }))?
+                );
+            }
+        };
         Ok(())
     }
 }
@@ -1,4 +1,5 @@
 use dialoguer::{theme::ColorfulTheme, Select};
+use std::convert::{TryFrom, TryInto};
 use strum::{EnumDiscriminants, EnumIter, EnumMessage, IntoEnumIterator};
 
 mod block_id_hash;
@@ -25,24 +26,27 @@ pub enum BlockId {
     AtBlockHash(self::block_id_hash::BlockIdHash),
 }
 
-impl From<CliBlockId> for BlockId {
-    fn from(item: CliBlockId) -> Self {
-        match item {
+impl TryFrom<CliBlockId> for BlockId {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliBlockId) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliBlockId::AtFinalBlock => Self::AtFinalBlock,
             CliBlockId::AtBlockHeight(cli_block_id_height) => {
-                Self::AtBlockHeight(cli_block_id_height.into())
+                Self::AtBlockHeight(cli_block_id_height.try_into()?)
             }
             CliBlockId::AtBlockHash(cli_block_id_hash) => {
-                Self::AtBlockHash(cli_block_id_hash.into())
+                Self::AtBlockHash(cli_block_id_hash.try_into()?)
             }
-        }
+        })
     }
 }
 
 impl BlockId {
-    pub fn choose_block_id() -> Self {
+    pub fn choose_block_id() -> color_eyre::eyre::Result<Self> {
         println!();
         let variants = BlockIdDiscriminants::iter().collect::<Vec<_>>();
+        crate::common::ensure_interactive_choice(&variants)?;
         let blocks = variants
             .iter()
             .map(|p| p.get_message().unwrap().to_owned())
@@ -58,48 +62,58 @@ impl BlockId {
             BlockIdDiscriminants::AtBlockHeight => CliBlockId::AtBlockHeight(Default::default()),
             BlockIdDiscriminants::AtBlockHash => CliBlockId::AtBlockHash(Default::default()),
         };
-        Self::from(cli_block_id)
+        Self::try_from(cli_block_id)
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (block_id, mut args) = match self {
+            Self::AtFinalBlock => ("at-final-block", vec![]),
+            Self::AtBlockHeight(block_id_height) => {
+                ("at-block-height", block_id_height.to_cli_args())
+            }
+            Self::AtBlockHash(block_id_hash) => ("at-block-hash", block_id_hash.to_cli_args()),
+        };
+        args.insert(0, block_id.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         sender_account_id: String,
         network_connection_config: crate::common::ConnectionConfig,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         println!();
         match self {
             Self::AtBlockHeight(block_id_height) => {
                 block_id_height
-                    .process(sender_account_id, network_connection_config)
+                    .process(sender_account_id, network_connection_config, output_format)
                     .await
             }
             Self::AtBlockHash(block_id_hash) => {
                 block_id_hash
-                    .process(sender_account_id, network_connection_config)
+                    .process(sender_account_id, network_connection_config, output_format)
                     .await
             }
             Self::AtFinalBlock => {
-                self.at_final_block(sender_account_id, network_connection_config)
+                self.at_final_block(sender_account_id, network_connection_config, output_format)
                     .await
             }
         }
     }
 
-    fn rpc_client(&self, selected_server_url: &str) -> near_jsonrpc_client::JsonRpcClient {
-        near_jsonrpc_client::new_client(&selected_server_url)
-    }
-
     async fn at_final_block(
         self,
         sender_account_id: String,
         network_connection_config: crate::common::ConnectionConfig,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
-        let query_view_method_response = self
-            .rpc_client(network_connection_config.rpc_url().as_str())
+        let query_view_method_response = network_connection_config
+            .rpc_client()
             .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
                 block_reference: near_primitives::types::Finality::Final.into(),
                 request: near_primitives::views::QueryRequest::ViewState {
-                    account_id: sender_account_id,
+                    account_id: sender_account_id.clone(),
                     prefix: near_primitives::types::StoreKey::from(vec![]),
                 },
             })
@@ -118,14 +132,30 @@ impl BlockId {
             } else {
                 return Err(color_eyre::Report::msg(format!("Error call result")));
             };
-        println!(
-            "\nContract state (values):\n{:#?}\n",
-            &call_access_view.values
-        );
-        println!(
-            "\nContract state (proof):\n{:#?}\n",
-            &call_access_view.proof
-        );
+        match output_format {
+            crate::common::OutputFormat::Plaintext => {
+                println!(
+                    "\nContract state (values):\n{:#?}\n",
+                    &call_access_view.values
+                );
+                println!(
+                    "\nContract state (proof):\n{:#?}\n",
+                    &call_access_view.proof
+                );
+            }
+            crate::common::OutputFormat::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "account_id": sender_account_id,
+                        "block_height": query_view_method_response.block_height,
+                        "block_hash": query_view_method_response.block_hash,
+                        "values": call_access_view.values,
+                        "proof": call_access_view.proof,
+                    }))?
+                );
+            }
+        };
         Ok(())
     }
 }
@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 #[derive(Debug, clap::Clap)]
 pub enum CliSendTo {
@@ -11,28 +12,43 @@ pub enum SendTo {
     Account(Account),
 }
 
-impl From<CliSendTo> for SendTo {
-    fn from(item: CliSendTo) -> Self {
-        match item {
+impl TryFrom<CliSendTo> for SendTo {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSendTo) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliSendTo::Account(cli_account) => {
-                let account = Account::from(cli_account);
+                let account = Account::try_from(cli_account)?;
                 Self::Account(account)
             }
-        }
+        })
     }
 }
 
 impl SendTo {
-    pub fn send_to() -> Self {
-        Self::from(CliSendTo::Account(Default::default()))
+    pub fn send_to() -> color_eyre::eyre::Result<Self> {
+        Self::try_from(CliSendTo::Account(Default::default()))
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (command, mut args) = match self {
+            Self::Account(account) => ("account", account.to_cli_args()),
+        };
+        args.insert(0, command.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         network_connection_config: crate::common::ConnectionConfig,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
-            SendTo::Account(account) => account.process(network_connection_config).await,
+            SendTo::Account(account) => {
+                account
+                    .process(network_connection_config, output_format)
+                    .await
+            }
         }
     }
 }
@@ -51,38 +67,48 @@ pub struct Account {
     pub public_key: super::public_key::AccessKey,
 }
 
-impl From<CliAccount> for Account {
-    fn from(item: CliAccount) -> Self {
+impl TryFrom<CliAccount> for Account {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliAccount) -> color_eyre::eyre::Result<Self> {
         let account_id: String = match item.account_id {
             Some(cli_account_id) => cli_account_id,
-            None => Account::input_account_id(),
+            None => Account::input_account_id()?,
         };
         let public_key = match item.public_key {
-            Some(cli_public_key) => super::public_key::AccessKey::from(cli_public_key),
-            None => super::public_key::AccessKey::choose_key(),
+            Some(cli_public_key) => super::public_key::AccessKey::try_from(cli_public_key)?,
+            None => super::public_key::AccessKey::choose_key()?,
         };
-        Self {
+        Ok(Self {
             account_id,
             public_key,
-        }
+        })
     }
 }
 
 impl Account {
-    fn input_account_id() -> String {
+    fn input_account_id() -> color_eyre::eyre::Result<String> {
+        crate::common::ensure_interactive("`<ACCOUNT_ID>` of `account`")?;
         println!();
-        Input::new()
+        Ok(Input::new()
             .with_prompt("Enter your account ID")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![self.account_id.clone()];
+        args.extend(self.public_key.to_cli_args());
+        args
     }
 
     pub async fn process(
         self,
         network_connection_config: crate::common::ConnectionConfig,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         self.public_key
-            .process(self.account_id, network_connection_config)
+            .process(self.account_id, network_connection_config, output_format)
             .await
     }
 }
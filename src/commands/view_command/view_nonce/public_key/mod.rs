@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::{TryFrom, TryInto};
 
 #[derive(Debug, clap::Clap)]
 pub enum CliAccessKey {
@@ -11,30 +12,41 @@ pub enum AccessKey {
     PublicKey(AccessKeyType),
 }
 
-impl From<CliAccessKey> for AccessKey {
-    fn from(item: CliAccessKey) -> Self {
-        match item {
+impl TryFrom<CliAccessKey> for AccessKey {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliAccessKey) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliAccessKey::PublicKey(cli_delete_access_key_type) => {
-                Self::PublicKey(cli_delete_access_key_type.into())
+                Self::PublicKey(cli_delete_access_key_type.try_into()?)
             }
-        }
+        })
     }
 }
 
 impl AccessKey {
-    pub fn choose_key() -> Self {
-        Self::from(CliAccessKey::PublicKey(Default::default()))
+    pub fn choose_key() -> color_eyre::eyre::Result<Self> {
+        Self::try_from(CliAccessKey::PublicKey(Default::default()))
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (command, mut args) = match self {
+            Self::PublicKey(access_key_type) => ("public-key", access_key_type.to_cli_args()),
+        };
+        args.insert(0, command.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         account_id: String,
         network_connection_config: crate::common::ConnectionConfig,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             AccessKey::PublicKey(access_key_type) => {
                 access_key_type
-                    .process(account_id, network_connection_config)
+                    .process(account_id, network_connection_config, output_format)
                     .await
             }
         }
@@ -52,40 +64,44 @@ pub struct AccessKeyType {
     pub public_key: near_crypto::PublicKey,
 }
 
-impl From<CliAccessKeyType> for AccessKeyType {
-    fn from(item: CliAccessKeyType) -> Self {
+impl TryFrom<CliAccessKeyType> for AccessKeyType {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliAccessKeyType) -> color_eyre::eyre::Result<Self> {
         let public_key: near_crypto::PublicKey = match item.public_key {
             Some(cli_public_key) => cli_public_key,
-            None => AccessKeyType::input_public_key(),
+            None => AccessKeyType::input_public_key()?,
         };
-        Self { public_key }
+        Ok(Self { public_key })
     }
 }
 
 impl AccessKeyType {
-    pub fn input_public_key() -> near_crypto::PublicKey {
-        Input::new()
+    pub fn input_public_key() -> color_eyre::eyre::Result<near_crypto::PublicKey> {
+        crate::common::ensure_interactive("`<PUBLIC_KEY>` of `public-key`")?;
+        Ok(Input::new()
             .with_prompt("Enter a public key for this access key")
             .interact_text()
-            .unwrap()
+            .unwrap())
     }
 
-    fn rpc_client(self, selected_server_url: &str) -> near_jsonrpc_client::JsonRpcClient {
-        near_jsonrpc_client::new_client(&selected_server_url)
+    pub fn to_cli_args(&self) -> Vec<String> {
+        vec![self.public_key.to_string()]
     }
 
     pub async fn process(
         self,
         account_id: String,
         network_connection_config: crate::common::ConnectionConfig,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let public_key = self.public_key.clone();
-        let online_signer_access_key_response = self
-            .rpc_client(network_connection_config.rpc_url().as_str())
+        let online_signer_access_key_response = network_connection_config
+            .rpc_client()
             .query(near_jsonrpc_primitives::types::query::RpcQueryRequest {
                 block_reference: near_primitives::types::Finality::Final.into(),
                 request: near_primitives::views::QueryRequest::ViewAccessKey {
-                    account_id,
+                    account_id: account_id.clone(),
                     public_key: public_key.clone(),
                 },
             })
@@ -105,10 +121,26 @@ impl AccessKeyType {
             } else {
                 return Err(color_eyre::Report::msg(format!("Error current_nonce")));
             };
-        println!(
-            "\ncurrent nonce: {}  for a public key: {}",
-            current_nonce, public_key
-        );
+        match output_format {
+            crate::common::OutputFormat::Plaintext => {
+                println!(
+                    "\ncurrent nonce: {}  for a public key: {}",
+                    current_nonce, public_key
+                );
+            }
+            crate::common::OutputFormat::Json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "account_id": account_id,
+                        "public_key": public_key,
+                        "nonce": current_nonce,
+                        "block_height": online_signer_access_key_response.block_height,
+                        "block_hash": online_signer_access_key_response.block_hash,
+                    }))?
+                );
+            }
+        };
         Ok(())
     }
 }
@@ -1,3 +1,3 @@
 pub mod operation_mode;
-mod signer;
+pub mod signer;
 mod transaction;
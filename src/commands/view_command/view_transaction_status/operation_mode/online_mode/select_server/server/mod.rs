@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 /// предустановленный RPC-сервер
 #[derive(Debug, Default, clap::Clap)]
@@ -16,6 +17,16 @@ pub struct CliCustomServer {
     transaction_status: Option<super::super::super::super::transaction::CliTransaction>,
 }
 
+/// A network of the configuration file or a built-in one, selected by its name
+#[derive(Debug, Default, clap::Clap)]
+pub struct CliNamedServer {
+    /// The name of the network (~/.config/near-cli/config.toml)
+    #[clap(long)]
+    pub network: Option<crate::common::ConnectionConfig>,
+    #[clap(flatten)]
+    pub server: CliServer,
+}
+
 #[derive(Debug)]
 pub struct Server {
     pub connection_config: crate::common::ConnectionConfig,
@@ -23,46 +34,71 @@ pub struct Server {
 }
 
 impl CliServer {
-    pub fn into_server(self, connection_config: crate::common::ConnectionConfig) -> Server {
+    pub fn into_server(
+        self,
+        connection_config: crate::common::ConnectionConfig,
+    ) -> color_eyre::eyre::Result<Server> {
         let transaction_status = match self.transaction_status {
             Some(cli_transaction_status) => {
-                super::super::super::super::transaction::Transaction::from(cli_transaction_status)
+                super::super::super::super::transaction::Transaction::try_from(
+                    cli_transaction_status,
+                )?
             }
-            None => super::super::super::super::transaction::Transaction::transaction(),
+            None => super::super::super::super::transaction::Transaction::transaction()?,
         };
-        Server {
+        Ok(Server {
             connection_config,
             transaction_status,
-        }
+        })
     }
 }
 
 impl CliCustomServer {
-    pub fn into_server(self) -> Server {
+    pub fn into_server(self) -> color_eyre::eyre::Result<Server> {
         let url: crate::common::AvailableRpcServerUrl = match self.url {
             Some(url) => url,
-            None => Input::new()
-                .with_prompt("What is the RPC endpoint?")
-                .interact_text()
-                .unwrap(),
+            None => {
+                crate::common::ensure_interactive("`--url <URL>` of `custom`")?;
+                Input::new()
+                    .with_prompt("What is the RPC endpoint?")
+                    .interact_text()
+                    .unwrap()
+            }
         };
+        let connection_config = crate::common::ConnectionConfig::Custom { url: url.inner };
         let transaction_status = match self.transaction_status {
             Some(cli_transaction_status) => {
-                super::super::super::super::transaction::Transaction::from(cli_transaction_status)
+                super::super::super::super::transaction::Transaction::try_from(
+                    cli_transaction_status,
+                )?
             }
-            None => super::super::super::super::transaction::Transaction::transaction(),
+            None => super::super::super::super::transaction::Transaction::transaction()?,
         };
-        Server {
-            connection_config: crate::common::ConnectionConfig::Custom { url: url.inner },
+        Ok(Server {
+            connection_config,
             transaction_status,
-        }
+        })
+    }
+}
+
+impl CliNamedServer {
+    pub fn into_server(self) -> color_eyre::eyre::Result<Server> {
+        let connection_config = match self.network {
+            Some(connection_config) => connection_config,
+            None => crate::config::choose_network()?,
+        };
+        self.server.into_server(connection_config)
     }
 }
 
 impl Server {
-    pub async fn process(self) -> crate::CliResult {
+    pub fn to_cli_args(&self) -> Vec<String> {
+        self.transaction_status.to_cli_args()
+    }
+
+    pub async fn process(self, output_format: crate::common::OutputFormat) -> crate::CliResult {
         self.transaction_status
-            .process(self.connection_config)
+            .process(self.connection_config, output_format)
             .await
     }
 }
@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::TryFrom;
 
 #[derive(Debug, clap::Clap)]
 pub enum CliSendFrom {
@@ -11,31 +12,42 @@ pub enum SendFrom {
     Signer(Sender),
 }
 
-impl From<CliSendFrom> for SendFrom {
-    fn from(item: CliSendFrom) -> Self {
-        match item {
+impl TryFrom<CliSendFrom> for SendFrom {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSendFrom) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliSendFrom::Signer(cli_sender) => {
-                let sender = Sender::from(cli_sender);
+                let sender = Sender::try_from(cli_sender)?;
                 Self::Signer(sender)
             }
-        }
+        })
     }
 }
 
 impl SendFrom {
-    pub fn send_from() -> Self {
-        Self::from(CliSendFrom::Signer(Default::default()))
+    pub fn send_from() -> color_eyre::eyre::Result<Self> {
+        Self::try_from(CliSendFrom::Signer(Default::default()))
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (command, mut args) = match self {
+            Self::Signer(sender) => ("signer", sender.to_cli_args()),
+        };
+        args.insert(0, command.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         network_connection_config: crate::common::ConnectionConfig,
         transaction_hash: String,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             SendFrom::Signer(sender) => {
                 sender
-                    .process(network_connection_config, transaction_hash)
+                    .process(network_connection_config, transaction_hash, output_format)
                     .await
             }
         }
@@ -53,46 +65,78 @@ pub struct Sender {
     pub account_id: String,
 }
 
-impl From<CliSender> for Sender {
-    fn from(item: CliSender) -> Self {
+impl TryFrom<CliSender> for Sender {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliSender) -> color_eyre::eyre::Result<Self> {
         let account_id: String = match item.account_id {
             Some(cli_account_id) => cli_account_id,
-            None => Sender::input_sender_account_id(),
+            None => Sender::input_sender_account_id()?,
         };
-        Self { account_id }
+        Ok(Self { account_id })
     }
 }
 
 impl Sender {
-    pub fn input_sender_account_id() -> String {
+    pub fn input_sender_account_id() -> color_eyre::eyre::Result<String> {
+        crate::common::ensure_interactive("`<ACCOUNT_ID>` of `signer`")?;
         println!();
-        Input::new()
+        Ok(Input::new()
             .with_prompt("Specify the account that signed the transaction")
             .interact_text()
-            .unwrap()
+            .unwrap())
     }
 
-    fn rpc_client(&self, selected_server_url: &str) -> near_jsonrpc_client::JsonRpcClient {
-        near_jsonrpc_client::new_client(&selected_server_url)
+    pub fn to_cli_args(&self) -> Vec<String> {
+        vec![self.account_id.clone()]
     }
 
     pub async fn process(
         self,
         network_connection_config: crate::common::ConnectionConfig,
         transaction_hash: String,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         let account_id = self.account_id.clone();
-        let query_view_transaction_status = self
-            .rpc_client(network_connection_config.archival_rpc_url().as_str())
-            .tx(transaction_hash, account_id)
-            .await
-            .map_err(|err| {
-                color_eyre::Report::msg(format!(
-                    "Failed to fetch query for view transaction: {:?}",
-                    err
-                ))
-            })?;
-        println!("Transactiion status: {:#?}", query_view_transaction_status);
-        Ok(())
+        let query_view_transaction_status = fetch_transaction_status(
+            &network_connection_config.archival_rpc_client(),
+            transaction_hash,
+            account_id,
+        )
+        .await
+        .map_err(|err| {
+            color_eyre::Report::msg(format!(
+                "Failed to fetch query for view transaction: {:?}",
+                err
+            ))
+        })?;
+        display_transaction_status(&query_view_transaction_status, output_format)
     }
 }
+
+/// The archival server is used to view old transactions, `send-async` polls the regular one
+pub async fn fetch_transaction_status(
+    rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    transaction_hash: String,
+    account_id: String,
+) -> Result<
+    near_primitives::views::FinalExecutionOutcomeView,
+    near_jsonrpc_primitives::errors::RpcError,
+> {
+    rpc_client.tx(transaction_hash, account_id).await
+}
+
+pub fn display_transaction_status(
+    transaction_status: &near_primitives::views::FinalExecutionOutcomeView,
+    output_format: crate::common::OutputFormat,
+) -> crate::CliResult {
+    match output_format {
+        crate::common::OutputFormat::Plaintext => {
+            println!("Transactiion status: {:#?}", transaction_status);
+        }
+        crate::common::OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(transaction_status)?);
+        }
+    };
+    Ok(())
+}
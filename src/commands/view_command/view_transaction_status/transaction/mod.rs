@@ -1,4 +1,5 @@
 use dialoguer::Input;
+use std::convert::{TryFrom, TryInto};
 
 #[derive(Debug, clap::Clap)]
 pub enum CliTransaction {
@@ -11,28 +12,43 @@ pub enum Transaction {
     TransactionHash(TransactionType),
 }
 
-impl From<CliTransaction> for Transaction {
-    fn from(item: CliTransaction) -> Self {
-        match item {
+impl TryFrom<CliTransaction> for Transaction {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliTransaction) -> color_eyre::eyre::Result<Self> {
+        Ok(match item {
             CliTransaction::TransactionHash(cli_transaction_type) => {
-                Transaction::TransactionHash(cli_transaction_type.into())
+                Transaction::TransactionHash(cli_transaction_type.try_into()?)
             }
-        }
+        })
     }
 }
 
 impl Transaction {
-    pub fn transaction() -> Self {
-        Self::from(CliTransaction::TransactionHash(Default::default()))
+    pub fn transaction() -> color_eyre::eyre::Result<Self> {
+        Self::try_from(CliTransaction::TransactionHash(Default::default()))
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let (command, mut args) = match self {
+            Self::TransactionHash(transaction_type) => {
+                ("transaction-hash", transaction_type.to_cli_args())
+            }
+        };
+        args.insert(0, command.to_owned());
+        args
     }
 
     pub async fn process(
         self,
         network_connection_config: crate::common::ConnectionConfig,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         match self {
             Transaction::TransactionHash(transaction_type) => {
-                transaction_type.process(network_connection_config).await
+                transaction_type
+                    .process(network_connection_config, output_format)
+                    .await
             }
         }
     }
@@ -52,38 +68,52 @@ pub struct TransactionType {
     send_from: super::signer::SendFrom,
 }
 
-impl From<CliTransactionType> for TransactionType {
-    fn from(item: CliTransactionType) -> Self {
+impl TryFrom<CliTransactionType> for TransactionType {
+    type Error = color_eyre::Report;
+
+    fn try_from(item: CliTransactionType) -> color_eyre::eyre::Result<Self> {
         let transaction_hash = match item.transaction_hash {
             Some(cli_transaction_hash) => cli_transaction_hash,
-            None => TransactionType::input_transaction_hash(),
+            None => TransactionType::input_transaction_hash()?,
         };
         let send_from = match item.send_from {
-            Some(cli_send_from) => super::signer::SendFrom::from(cli_send_from),
-            None => super::signer::SendFrom::send_from(),
+            Some(cli_send_from) => super::signer::SendFrom::try_from(cli_send_from)?,
+            None => super::signer::SendFrom::send_from()?,
         };
-        Self {
+        Ok(Self {
             transaction_hash,
             send_from,
-        }
+        })
     }
 }
 
 impl TransactionType {
-    fn input_transaction_hash() -> String {
+    fn input_transaction_hash() -> color_eyre::eyre::Result<String> {
+        crate::common::ensure_interactive("`<TRANSACTION_HASH>` of `transaction`")?;
         println!();
-        Input::new()
+        Ok(Input::new()
             .with_prompt("Enter the hash of the transaction you need to view")
             .interact_text()
-            .unwrap()
+            .unwrap())
+    }
+
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let mut args = vec![self.transaction_hash.clone()];
+        args.extend(self.send_from.to_cli_args());
+        args
     }
 
     pub async fn process(
         self,
         network_connection_config: crate::common::ConnectionConfig,
+        output_format: crate::common::OutputFormat,
     ) -> crate::CliResult {
         self.send_from
-            .process(network_connection_config, self.transaction_hash)
+            .process(
+                network_connection_config,
+                self.transaction_hash,
+                output_format,
+            )
             .await
     }
 }
@@ -5,6 +5,7 @@ use near_primitives::borsh::BorshDeserialize;
 #[derive(
     Debug,
     Clone,
+    Copy,
     strum_macros::IntoStaticStr,
     strum_macros::EnumString,
     strum_macros::EnumVariantNames,
@@ -17,6 +18,149 @@ pub enum OutputFormat {
     Json,
 }
 
+static NON_INTERACTIVE: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+static ASSUME_YES: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+static PROMPTED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// The flags whose values are never printed back
+const SECRET_FLAGS: [&str; 4] = [
+    "--secret-key",
+    "--signer-secret-key",
+    "--master-seed-phrase",
+    "--passphrase",
+];
+
+/// Disables every prompt (`--non-interactive`, or stdin is not a terminal)
+pub fn set_non_interactive(non_interactive: bool) {
+    NON_INTERACTIVE.store(non_interactive, std::sync::atomic::Ordering::Relaxed);
+}
+
+pub fn is_non_interactive() -> bool {
+    NON_INTERACTIVE.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Skips the confirmation of mainnet and destructive transactions (`--yes`)
+pub fn set_assume_yes(assume_yes: bool) {
+    ASSUME_YES.store(assume_yes, std::sync::atomic::Ordering::Relaxed);
+}
+
+pub fn is_assume_yes() -> bool {
+    ASSUME_YES.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Whether the user was asked for anything (see `ensure_interactive`)
+pub fn was_prompted() -> bool {
+    PROMPTED.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// The error of a value missing in the non-interactive mode; `main` exits with code 2 on it
+#[derive(Debug)]
+pub struct MissingArgument {
+    missing: String,
+}
+
+impl std::fmt::Display for MissingArgument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} is required, but prompts are disabled (--non-interactive or no terminal)\n\n    {}\n",
+            self.missing,
+            mask_secrets(std::env::args()).join(" ")
+        )
+    }
+}
+
+impl std::error::Error for MissingArgument {}
+
+/// In the non-interactive mode, returns an error naming the missing value instead of
+/// prompting for it; `missing` tells how to pass it, e.g. "`--url <URL>` of `custom`"
+pub fn ensure_interactive(missing: &str) -> color_eyre::eyre::Result<()> {
+    if is_non_interactive() {
+        return Err(MissingArgument {
+            missing: missing.to_string(),
+        }
+        .into());
+    }
+    PROMPTED.store(true, std::sync::atomic::Ordering::Relaxed);
+    Ok(())
+}
+
+/// The same for a missing subcommand; `variants` are the discriminants of the `Cli*` enum
+pub fn ensure_interactive_choice<T: std::fmt::Debug>(
+    variants: &[T],
+) -> color_eyre::eyre::Result<()> {
+    if !is_non_interactive() {
+        PROMPTED.store(true, std::sync::atomic::Ordering::Relaxed);
+        return Ok(());
+    }
+    let subcommands = variants
+        .iter()
+        .map(|variant| format!("`{}`", to_kebab_case(&format!("{:?}", variant))))
+        .collect::<Vec<_>>();
+    ensure_interactive(&format!(
+        "one of the subcommands {}",
+        subcommands.join(", ")
+    ))
+}
+
+/// The subcommand name clap derives from a variant name (`StakeNEARTokens` -> `stake-near-tokens`)
+fn to_kebab_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut kebab = String::new();
+    for (index, c) in chars.iter().enumerate() {
+        if c.is_uppercase()
+            && index > 0
+            && (chars[index - 1].is_lowercase()
+                || chars
+                    .get(index + 1)
+                    .map_or(false, |next| next.is_lowercase()))
+        {
+            kebab.push('-');
+        }
+        kebab.extend(c.to_lowercase());
+    }
+    kebab
+}
+
+/// Replaces the values of the secret flags (`--flag value` and `--flag=value`)
+pub fn mask_secrets(args: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut masked = vec![];
+    let mut mask_next = false;
+    for arg in args {
+        if mask_next {
+            masked.push("***".to_string());
+            mask_next = false;
+            continue;
+        }
+        let flag = arg.splitn(2, '=').next().unwrap_or_default();
+        if flag != arg && SECRET_FLAGS.contains(&flag) {
+            masked.push(format!("{}=***", flag));
+        } else {
+            mask_next = SECRET_FLAGS.contains(&arg.as_str());
+            masked.push(arg);
+        }
+    }
+    masked
+}
+
+/// The command line (secrets masked) that repeats what was chosen in the prompts
+pub fn print_command_line(cli_args: Option<Vec<String>>) {
+    let cli_args = match cli_args {
+        Some(cli_args) => cli_args,
+        None => {
+            eprintln!("\nThis command cannot be repeated without prompts: a transaction with several actions can only be constructed interactively\n");
+            return;
+        }
+    };
+    let program = std::env::args()
+        .next()
+        .unwrap_or_else(|| "./near-cli".to_string());
+    eprintln!(
+        "\nThe same command without prompts:\n    {}\n",
+        shell_words::join(mask_secrets(std::iter::once(program).chain(cli_args)))
+    );
+}
+
 #[derive(Debug, Clone)]
 pub struct TransactionAsBase64 {
     pub inner: near_primitives::transaction::Transaction,
@@ -41,6 +185,262 @@ impl std::fmt::Display for TransactionAsBase64 {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct SignedTransactionAsBase64 {
+    pub inner: near_primitives::transaction::SignedTransaction,
+}
+
+impl std::str::FromStr for SignedTransactionAsBase64 {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            inner: near_primitives::transaction::SignedTransaction::try_from_slice(
+                &near_primitives::serialize::from_base64(s)
+                    .map_err(|err| format!("base64 transaction sequence is invalid: {}", err))?,
+            )
+            .map_err(|err| format!("signed transaction could not be parsed: {}", err))?,
+        })
+    }
+}
+
+impl std::fmt::Display for SignedTransactionAsBase64 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SignedTransaction {}", self.inner.get_hash())
+    }
+}
+
+/// The environment variable the signer secret key is taken from when no flag is given
+pub const SIGNER_SECRET_KEY_ENV: &str = "NEAR_SIGNER_SECRET_KEY";
+
+/// Parses a secret key given either as is or as a key file of the keychain
+/// (`private_key` or `secret_key` field)
+pub fn parse_secret_key(data: &str) -> Result<near_crypto::SecretKey, String> {
+    let data = data.trim();
+    let secret_key = if data.starts_with('{') {
+        let key_file: serde_json::Value = serde_json::from_str(data)
+            .map_err(|err| format!("the key file could not be parsed: {}", err))?;
+        key_file
+            .get("private_key")
+            .or_else(|| key_file.get("secret_key"))
+            .and_then(|secret_key| secret_key.as_str())
+            .ok_or_else(|| "the key file has no `private_key`".to_string())?
+            .to_owned()
+    } else {
+        data.to_owned()
+    };
+    secret_key
+        .parse::<near_crypto::SecretKey>()
+        .map_err(|err| format!("the secret key is invalid: {}", err))
+}
+
+/// A secret key read from a file (`-` reads it from stdin), see `parse_secret_key`
+#[derive(Debug, Clone)]
+pub struct SecretKeyFile {
+    pub path: String,
+    pub inner: near_crypto::SecretKey,
+}
+
+impl std::str::FromStr for SecretKeyFile {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let data = if s == "-" {
+            let mut data = String::new();
+            std::io::Read::read_to_string(&mut std::io::stdin(), &mut data)
+                .map_err(|err| format!("Failed to read stdin: {}", err))?;
+            data
+        } else {
+            std::fs::read_to_string(s).map_err(|err| format!("Failed to read {}: {}", s, err))?
+        };
+        Ok(Self {
+            path: s.to_owned(),
+            inner: parse_secret_key(&data)?,
+        })
+    }
+}
+
+impl std::fmt::Display for SecretKeyFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path)
+    }
+}
+
+/// Where the signer secret key came from, so that the command line does not put it back in sight
+#[derive(Debug, Clone)]
+pub enum SecretKeySource {
+    /// `--signer-secret-key` or the prompt
+    Argument,
+    /// `--signer-secret-key-file`
+    File(String),
+    /// `NEAR_SIGNER_SECRET_KEY`
+    Environment,
+}
+
+impl SecretKeySource {
+    pub fn to_cli_args(&self, secret_key: &near_crypto::SecretKey) -> Vec<String> {
+        match self {
+            Self::Argument => vec!["--signer-secret-key".to_owned(), secret_key.to_string()],
+            Self::File(path) => vec!["--signer-secret-key-file".to_owned(), path.clone()],
+            Self::Environment => vec![],
+        }
+    }
+}
+
+/// Takes the signer secret key from `--signer-secret-key`, `--signer-secret-key-file`,
+/// `NEAR_SIGNER_SECRET_KEY` or, at last, a hidden prompt; `missing` is passed to `ensure_interactive`
+pub fn signer_secret_key(
+    secret_key: Option<near_crypto::SecretKey>,
+    secret_key_file: Option<SecretKeyFile>,
+    missing: &str,
+) -> color_eyre::eyre::Result<(near_crypto::SecretKey, SecretKeySource)> {
+    if let Some(secret_key) = secret_key {
+        return Ok((secret_key, SecretKeySource::Argument));
+    }
+    if let Some(secret_key_file) = secret_key_file {
+        return Ok((
+            secret_key_file.inner,
+            SecretKeySource::File(secret_key_file.path),
+        ));
+    }
+    if let Ok(data) = std::env::var(SIGNER_SECRET_KEY_ENV) {
+        let secret_key = parse_secret_key(&data).map_err(|err| {
+            color_eyre::Report::msg(format!("{} of {}", err, SIGNER_SECRET_KEY_ENV))
+        })?;
+        return Ok((secret_key, SecretKeySource::Environment));
+    }
+    ensure_interactive(missing)?;
+    Ok((
+        input_secret_key("Enter the secret key (it is not shown)"),
+        SecretKeySource::Argument,
+    ))
+}
+
+/// Asks for a secret key without echoing it
+pub fn input_secret_key(prompt: &str) -> near_crypto::SecretKey {
+    loop {
+        let data = dialoguer::Password::new()
+            .with_prompt(prompt)
+            .interact()
+            .unwrap();
+        match parse_secret_key(&data) {
+            Ok(secret_key) => return secret_key,
+            Err(err) => eprintln!("Error: {}", err),
+        }
+    }
+}
+
+/// Whether an access key with this permission can sign the transaction: a full access key signs
+/// anything, a function-call key only a single call of an allowed method without a deposit,
+/// and only while its allowance (if limited) covers `max_gas_cost`
+pub fn access_key_permits(
+    permission: &near_primitives::views::AccessKeyPermissionView,
+    transaction: &near_primitives::transaction::Transaction,
+    max_gas_cost: near_primitives::types::Balance,
+) -> bool {
+    match permission {
+        near_primitives::views::AccessKeyPermissionView::FullAccess => true,
+        near_primitives::views::AccessKeyPermissionView::FunctionCall {
+            allowance,
+            receiver_id,
+            method_names,
+        } => match transaction.actions.as_slice() {
+            [near_primitives::transaction::Action::FunctionCall(function_call)] => {
+                &transaction.receiver_id == receiver_id
+                    && function_call.deposit == 0
+                    && (method_names.is_empty()
+                        || method_names.contains(&function_call.method_name))
+                    && allowance.map_or(true, |allowance| allowance >= max_gas_cost)
+            }
+            _ => false,
+        },
+    }
+}
+
+/// One action of a transaction in plain language, with the exact amounts
+pub fn describe_action(action: &near_primitives::transaction::Action) -> String {
+    match action {
+        near_primitives::transaction::Action::CreateAccount(_) => "Create the account".to_string(),
+        near_primitives::transaction::Action::DeployContract(deploy_contract) => {
+            let code_hash = near_primitives::hash::hash(&deploy_contract.code);
+            format!(
+                "Deploy a contract: {} bytes, sha256 {} (code hash {})",
+                deploy_contract.code.len(),
+                hex::encode(code_hash.as_ref()),
+                code_hash
+            )
+        }
+        near_primitives::transaction::Action::FunctionCall(function_call) => format!(
+            "Call `{}` with {} attached and a deposit of {}, args: {}",
+            function_call.method_name,
+            crate::common::NearGas {
+                inner: function_call.gas
+            }
+            .to_cli_arg(),
+            crate::common::NearBalance::from_yoctonear(function_call.deposit).to_cli_arg(),
+            describe_args(&function_call.args)
+        ),
+        near_primitives::transaction::Action::Transfer(transfer) => format!(
+            "Transfer {}",
+            crate::common::NearBalance::from_yoctonear(transfer.deposit).to_cli_arg()
+        ),
+        near_primitives::transaction::Action::Stake(stake) => format!(
+            "Stake {} with the validator key {}",
+            crate::common::NearBalance::from_yoctonear(stake.stake).to_cli_arg(),
+            stake.public_key
+        ),
+        near_primitives::transaction::Action::AddKey(add_key) => {
+            match &add_key.access_key.permission {
+                near_primitives::account::AccessKeyPermission::FullAccess => format!(
+                    "Add the full access key {} (it can do anything with the account)",
+                    add_key.public_key
+                ),
+                near_primitives::account::AccessKeyPermission::FunctionCall(permission) => {
+                    format!(
+                        "Add the function-call key {} for {} of `{}`, allowance {}",
+                        add_key.public_key,
+                        if permission.method_names.is_empty() {
+                            "any method".to_string()
+                        } else {
+                            format!("the methods {}", permission.method_names.join(", "))
+                        },
+                        permission.receiver_id,
+                        match permission.allowance {
+                            Some(allowance) => {
+                                crate::common::NearBalance::from_yoctonear(allowance).to_cli_arg()
+                            }
+                            None => "unlimited".to_string(),
+                        }
+                    )
+                }
+            }
+        }
+        near_primitives::transaction::Action::DeleteKey(delete_key) => {
+            format!("Delete the access key {}", delete_key.public_key)
+        }
+        near_primitives::transaction::Action::DeleteAccount(delete_account) => format!(
+            "Delete the account and send its balance to {}",
+            delete_account.beneficiary_id
+        ),
+    }
+}
+
+/// Function-call args as JSON when they are JSON, otherwise as text or base64
+fn describe_args(args: &[u8]) -> String {
+    if args.is_empty() {
+        return "none".to_string();
+    }
+    match std::str::from_utf8(args) {
+        Ok(text) => match serde_json::from_str::<serde_json::Value>(text) {
+            Ok(json) => json.to_string(),
+            Err(_) => format!("{:?} (not JSON)", text),
+        },
+        Err(_) => format!(
+            "{} bytes of binary data, base64 {}",
+            args.len(),
+            near_primitives::serialize::to_base64(args)
+        ),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BlockHashAsBase58 {
     pub inner: near_primitives::hash::CryptoHash,
@@ -107,6 +507,20 @@ impl NearBalance {
     pub fn to_yoctonear(&self) -> u128 {
         self.yoctonear_amount
     }
+
+    /// The exact amount that `from_str` accepts (`Display` rounds it to 0.001 NEAR)
+    pub fn to_cli_arg(&self) -> String {
+        let fractional_part = self.yoctonear_amount % ONE_NEAR;
+        if fractional_part == 0 {
+            format!("{} NEAR", self.yoctonear_amount / ONE_NEAR)
+        } else {
+            format!(
+                "{}.{} NEAR",
+                self.yoctonear_amount / ONE_NEAR,
+                format!("{:024}", fractional_part).trim_end_matches('0')
+            )
+        }
+    }
 }
 
 impl std::fmt::Display for NearBalance {
@@ -178,6 +592,49 @@ impl std::str::FromStr for NearBalance {
     }
 }
 
+/// The allowance of a function-call access key (`--allowance`): an amount of NEAR or
+/// `unlimited`, so that no limit can be given on the command line as well
+#[derive(Debug, Clone, PartialEq)]
+pub enum Allowance {
+    Unlimited,
+    Limited(NearBalance),
+}
+
+impl std::str::FromStr for Allowance {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().eq_ignore_ascii_case("unlimited") {
+            return Ok(Self::Unlimited);
+        }
+        Ok(Self::Limited(NearBalance::from_str(s)?))
+    }
+}
+
+impl Allowance {
+    pub fn from_yoctonear(allowance: Option<u128>) -> Self {
+        match allowance {
+            Some(yoctonear_amount) => Self::Limited(NearBalance::from_yoctonear(yoctonear_amount)),
+            None => Self::Unlimited,
+        }
+    }
+
+    pub fn to_yoctonear(&self) -> Option<u128> {
+        match self {
+            Self::Unlimited => None,
+            Self::Limited(near_balance) => Some(near_balance.to_yoctonear()),
+        }
+    }
+
+    pub fn to_cli_arg(&self) -> String {
+        match self {
+            Self::Unlimited => "unlimited".to_owned(),
+            Self::Limited(near_balance) => near_balance.to_cli_arg(),
+        }
+    }
+}
+
+const ONE_TERA_GAS: u64 = 10u64.pow(12);
+
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct NearGas {
     pub inner: u64,
@@ -204,6 +661,20 @@ impl std::str::FromStr for NearGas {
 }
 
 impl NearGas {
+    /// The exact amount that `from_str` accepts (`Display` rounds it to TeraGas)
+    pub fn to_cli_arg(&self) -> String {
+        let fractional_part = self.inner % ONE_TERA_GAS;
+        if fractional_part == 0 {
+            format!("{} TeraGas", self.inner / ONE_TERA_GAS)
+        } else {
+            format!(
+                "{}.{} TeraGas",
+                self.inner / ONE_TERA_GAS,
+                format!("{:012}", fractional_part).trim_end_matches('0')
+            )
+        }
+    }
+
     fn into_tera_gas(num: &str) -> Result<u64, String> {
         let res_split: Vec<&str> = num.split('.').collect();
         match res_split.len() {
@@ -242,7 +713,23 @@ pub enum ConnectionConfig {
     Testnet,
     Mainnet,
     Betanet,
-    Custom { url: url::Url },
+    /// A local `neard` node (sandbox or `neard init` network)
+    Localnet,
+    Custom {
+        url: url::Url,
+    },
+    /// A network defined in the configuration file
+    Named(crate::config::NetworkConfig),
+}
+
+/// Resolves a network name through the configuration file (used by `--network <name>`)
+impl std::str::FromStr for ConnectionConfig {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        crate::config::Config::load()
+            .and_then(|config| config.connection_config(s))
+            .map_err(|err| err.to_string())
+    }
 }
 
 impl ConnectionConfig {
@@ -251,7 +738,9 @@ impl ConnectionConfig {
             Self::Testnet => crate::consts::TESTNET_API_SERVER_URL.parse().unwrap(),
             Self::Mainnet => crate::consts::MAINNET_API_SERVER_URL.parse().unwrap(),
             Self::Betanet => crate::consts::BETANET_API_SERVER_URL.parse().unwrap(),
+            Self::Localnet => crate::consts::LOCALNET_API_SERVER_URL.parse().unwrap(),
             Self::Custom { url } => url.clone(),
+            Self::Named(network_config) => network_config.rpc_url.clone(),
         }
     }
 
@@ -266,16 +755,24 @@ impl ConnectionConfig {
             Self::Betanet => crate::consts::BETANET_ARCHIVAL_API_SERVER_URL
                 .parse()
                 .unwrap(),
+            Self::Localnet => crate::consts::LOCALNET_API_SERVER_URL.parse().unwrap(),
             Self::Custom { url } => url.clone(),
+            Self::Named(network_config) => network_config
+                .archival_rpc_url
+                .clone()
+                .unwrap_or_else(|| network_config.rpc_url.clone()),
         }
     }
 
-    pub fn wallet_url(&self) -> url::Url {
+    /// `None` for the networks without a wallet (localnet, named networks without `wallet_url`)
+    pub fn wallet_url(&self) -> Option<url::Url> {
         match self {
-            Self::Testnet => crate::consts::TESTNET_WALLET_URL.parse().unwrap(),
-            Self::Mainnet => crate::consts::MAINNET_WALLET_URL.parse().unwrap(),
-            Self::Betanet => crate::consts::BETANET_WALLET_URL.parse().unwrap(),
-            Self::Custom { url } => url.clone(),
+            Self::Testnet => Some(crate::consts::TESTNET_WALLET_URL.parse().unwrap()),
+            Self::Mainnet => Some(crate::consts::MAINNET_WALLET_URL.parse().unwrap()),
+            Self::Betanet => Some(crate::consts::BETANET_WALLET_URL.parse().unwrap()),
+            Self::Localnet => None,
+            Self::Custom { url } => Some(url.clone()),
+            Self::Named(network_config) => network_config.wallet_url.clone(),
         }
     }
 
@@ -284,11 +781,168 @@ impl ConnectionConfig {
             Self::Testnet => crate::consts::DIR_NAME_TESTNET,
             Self::Mainnet => crate::consts::DIR_NAME_MAINNET,
             Self::Betanet => crate::consts::DIR_NAME_BETANET,
+            Self::Localnet => crate::consts::DIR_NAME_LOCALNET,
             Self::Custom { url: _ } => crate::consts::DIR_NAME_CUSTOM,
+            Self::Named(network_config) => network_config
+                .credentials_dir
+                .as_deref()
+                .unwrap_or(crate::consts::DIR_NAME_CUSTOM),
+        }
+    }
+
+    /// The network name recorded in transaction files (the URL for a custom server)
+    pub fn name(&self) -> String {
+        match self {
+            Self::Testnet => "testnet".to_owned(),
+            Self::Mainnet => "mainnet".to_owned(),
+            Self::Betanet => "betanet".to_owned(),
+            Self::Localnet => "localnet".to_owned(),
+            Self::Custom { url } => url.to_string(),
+            Self::Named(network_config) => network_config.name.clone(),
+        }
+    }
+
+    /// The flags of the `custom` or `named` server that select this network again
+    pub fn to_cli_args(&self) -> Vec<String> {
+        match self {
+            Self::Custom { url } => vec!["--url".to_owned(), url.to_string()],
+            Self::Named(network_config) => {
+                vec!["--network".to_owned(), network_config.name.clone()]
+            }
+            Self::Testnet => vec!["--network".to_owned(), "testnet".to_owned()],
+            Self::Mainnet => vec!["--network".to_owned(), "mainnet".to_owned()],
+            Self::Betanet => vec!["--network".to_owned(), "betanet".to_owned()],
+            Self::Localnet => vec!["--network".to_owned(), "localnet".to_owned()],
+        }
+    }
+
+    pub fn rpc_client(&self) -> near_jsonrpc_client::JsonRpcClient {
+        self.new_rpc_client(self.rpc_url())
+    }
+
+    pub fn archival_rpc_client(&self) -> near_jsonrpc_client::JsonRpcClient {
+        self.new_rpc_client(self.archival_rpc_url())
+    }
+
+    /// Calls a JSON-RPC method that `near-jsonrpc-client` does not wrap and returns its `result`
+    pub async fn call_json_rpc(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> color_eyre::eyre::Result<serde_json::Value> {
+        let client = match self {
+            Self::Named(crate::config::NetworkConfig {
+                api_key: Some(api_key),
+                ..
+            }) => actix_web::client::Client::builder()
+                .header(api_key.header.as_str(), api_key.value.as_str())
+                .finish(),
+            _ => actix_web::client::Client::default(),
+        };
+        let mut response = client
+            .post(self.rpc_url().as_str())
+            .send_json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": "near-cli",
+                "method": method,
+                "params": params,
+            }))
+            .await
+            .map_err(|err| {
+                color_eyre::Report::msg(format!("Failed to call {}: {}", method, err))
+            })?;
+        let mut body: serde_json::Value = response.json().await.map_err(|err| {
+            color_eyre::Report::msg(format!(
+                "Failed to parse the response to {}: {}",
+                method, err
+            ))
+        })?;
+        match body.get("error") {
+            Some(error) => Err(color_eyre::Report::msg(format!(
+                "{} failed: {}",
+                method, error
+            ))),
+            None => Ok(body["result"].take()),
+        }
+    }
+
+    /// Adds the API-key header of a configured network to every request
+    fn new_rpc_client(&self, url: url::Url) -> near_jsonrpc_client::JsonRpcClient {
+        match self {
+            Self::Named(crate::config::NetworkConfig {
+                api_key: Some(api_key),
+                ..
+            }) => near_jsonrpc_client::JsonRpcClient::new(
+                url.as_str(),
+                actix_web::client::Client::builder()
+                    .header(api_key.header.as_str(), api_key.value.as_str())
+                    .finish(),
+            ),
+            _ => near_jsonrpc_client::new_client(url.as_str()),
         }
     }
 }
 
+/// How long to wait for the outcome of a submitted transaction
+#[derive(Debug, Clone, clap::Clap)]
+pub struct RetryPolicy {
+    /// How many times to ask the RPC server for the outcome (the broadcast included)
+    #[clap(long, default_value = "10")]
+    pub max_attempts: u32,
+    /// The delay before the first status check in milliseconds; it doubles after every attempt
+    #[clap(long, default_value = "1000")]
+    pub backoff_ms: u64,
+    /// Stop waiting for the outcome after this many seconds
+    #[clap(long, default_value = "120")]
+    pub deadline_secs: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 10,
+            backoff_ms: 1000,
+            deadline_secs: 120,
+        }
+    }
+}
+
+const MAX_BACKOFF: std::time::Duration = std::time::Duration::from_secs(30);
+
+impl RetryPolicy {
+    /// The delay after the given (1-based) attempt
+    pub fn backoff(&self, attempt: u32) -> std::time::Duration {
+        let factor = 2u64.saturating_pow(attempt.saturating_sub(1));
+        std::cmp::min(
+            std::time::Duration::from_millis(self.backoff_ms.saturating_mul(factor)),
+            MAX_BACKOFF,
+        )
+    }
+
+    pub fn deadline(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.deadline_secs)
+    }
+
+    /// Only the flags that differ from the defaults
+    pub fn to_cli_args(&self) -> Vec<String> {
+        let default = Self::default();
+        let mut args = vec![];
+        if self.max_attempts != default.max_attempts {
+            args.push("--max-attempts".to_owned());
+            args.push(self.max_attempts.to_string());
+        }
+        if self.backoff_ms != default.backoff_ms {
+            args.push("--backoff-ms".to_owned());
+            args.push(self.backoff_ms.to_string());
+        }
+        if self.deadline_secs != default.deadline_secs {
+            args.push("--deadline-secs".to_owned());
+            args.push(self.deadline_secs.to_string());
+        }
+        args
+    }
+}
+
 #[derive(Debug)]
 pub struct KeyPairProperties {
     pub seed_phrase_hd_path: slip10::BIP32Path,
@@ -348,11 +1002,288 @@ pub async fn generate_keypair(
     Ok(key_pair_properties)
 }
 
+/// scrypt parameters used to derive the encryption key from a passphrase
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ScryptParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl Default for ScryptParams {
+    fn default() -> Self {
+        Self {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+/// Data encrypted with a passphrase: the key is derived with scrypt and the data is
+/// sealed with XChaCha20-Poly1305 (binary fields are base64-encoded)
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EncryptedData {
+    pub kdf: String,
+    pub kdf_params: ScryptParams,
+    pub salt: String,
+    pub cipher: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+const ENCRYPTION_KDF: &str = "scrypt";
+const ENCRYPTION_CIPHER: &str = "xchacha20poly1305";
+
+fn derive_key_from_passphrase(
+    passphrase: &str,
+    salt: &[u8],
+    kdf_params: ScryptParams,
+) -> color_eyre::eyre::Result<chacha20poly1305::Key> {
+    let params = scrypt::ScryptParams::new(kdf_params.log_n, kdf_params.r, kdf_params.p)
+        .map_err(|err| color_eyre::Report::msg(format!("Invalid scrypt parameters: {:?}", err)))?;
+    let mut key = chacha20poly1305::Key::default();
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut key).map_err(|err| {
+        color_eyre::Report::msg(format!("Failed to derive the encryption key: {:?}", err))
+    })?;
+    Ok(key)
+}
+
+pub fn encrypt_with_passphrase(
+    plaintext: &[u8],
+    passphrase: &str,
+) -> color_eyre::eyre::Result<EncryptedData> {
+    encrypt_with_kdf_params(plaintext, passphrase, ScryptParams::default())
+}
+
+fn encrypt_with_kdf_params(
+    plaintext: &[u8],
+    passphrase: &str,
+    kdf_params: ScryptParams,
+) -> color_eyre::eyre::Result<EncryptedData> {
+    use chacha20poly1305::aead::{Aead, NewAead};
+    use rand::RngCore;
+
+    let mut salt = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut salt);
+    let mut nonce = chacha20poly1305::XNonce::default();
+    rand::rngs::OsRng.fill_bytes(&mut nonce);
+    let key = derive_key_from_passphrase(passphrase, &salt, kdf_params)?;
+    let ciphertext = chacha20poly1305::XChaCha20Poly1305::new(&key)
+        .encrypt(&nonce, plaintext)
+        .map_err(|err| color_eyre::Report::msg(format!("Failed to encrypt: {:?}", err)))?;
+    Ok(EncryptedData {
+        kdf: ENCRYPTION_KDF.to_string(),
+        kdf_params,
+        salt: base64::encode(&salt),
+        cipher: ENCRYPTION_CIPHER.to_string(),
+        nonce: base64::encode(&nonce),
+        ciphertext: base64::encode(&ciphertext),
+    })
+}
+
+pub fn decrypt_with_passphrase(
+    encrypted_data: &EncryptedData,
+    passphrase: &str,
+) -> color_eyre::eyre::Result<Vec<u8>> {
+    use chacha20poly1305::aead::{Aead, NewAead};
+
+    if encrypted_data.kdf != ENCRYPTION_KDF || encrypted_data.cipher != ENCRYPTION_CIPHER {
+        return Err(color_eyre::Report::msg(format!(
+            "Unsupported encryption: kdf <{}>, cipher <{}>",
+            encrypted_data.kdf, encrypted_data.cipher
+        )));
+    }
+    let salt = base64::decode(&encrypted_data.salt)?;
+    let nonce = base64::decode(&encrypted_data.nonce)?;
+    if nonce.len() != 24 {
+        return Err(color_eyre::Report::msg(format!(
+            "Invalid nonce length: {}",
+            nonce.len()
+        )));
+    }
+    let ciphertext = base64::decode(&encrypted_data.ciphertext)?;
+    let key = derive_key_from_passphrase(passphrase, &salt, encrypted_data.kdf_params)?;
+    chacha20poly1305::XChaCha20Poly1305::new(&key)
+        .decrypt(
+            chacha20poly1305::XNonce::from_slice(&nonce),
+            ciphertext.as_ref(),
+        )
+        .map_err(|_| {
+            color_eyre::Report::msg(
+                "Failed to decrypt: the passphrase is wrong or the data is corrupted",
+            )
+        })
+}
+
+/// The largest piece of a payload shown in one QR code (it fits a version 13 code at the low
+/// error correction level); longer payloads are split into parts that are shown in turn
+const QR_CODE_PART_SIZE: usize = 400;
+const QR_CODE_PART_PREFIX: &str = "near-cli:";
+const QR_CODE_FRAME_DELAY: std::time::Duration = std::time::Duration::from_millis(700);
+
+/// Splits a payload into the texts of its QR codes; every part of a multi-part payload is
+/// prefixed with `near-cli:<index>/<total>:` so that the parts can be scanned in any order
+pub fn split_qr_payload(payload: &str, part_size: usize) -> Vec<String> {
+    let chars: Vec<char> = payload.chars().collect();
+    if chars.len() <= part_size {
+        return vec![payload.to_owned()];
+    }
+    let chunks: Vec<String> = chars
+        .chunks(part_size)
+        .map(|chunk| chunk.iter().collect())
+        .collect();
+    let total = chunks.len();
+    chunks
+        .into_iter()
+        .enumerate()
+        .map(|(index, chunk)| format!("{}{}/{}:{}", QR_CODE_PART_PREFIX, index + 1, total, chunk))
+        .collect()
+}
+
+/// Restores a payload from the scanned texts of its QR codes, one per line
+pub fn join_qr_payload(scanned: &str) -> Result<String, String> {
+    let lines: Vec<&str> = scanned
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    if !lines
+        .iter()
+        .any(|line| line.starts_with(QR_CODE_PART_PREFIX))
+    {
+        return match lines.as_slice() {
+            [payload] => Ok(payload.to_string()),
+            [] => Err("The QR payload is empty".to_string()),
+            _ => {
+                Err("The QR payload has several lines but they are not near-cli parts".to_string())
+            }
+        };
+    }
+    let mut parts: Vec<Option<&str>> = vec![];
+    for line in lines {
+        let part = line
+            .strip_prefix(QR_CODE_PART_PREFIX)
+            .ok_or_else(|| format!("Unexpected line in a multi-part QR payload: {}", line))?;
+        let mut fields = part.splitn(2, ':');
+        let (position, chunk) = match (fields.next(), fields.next()) {
+            (Some(position), Some(chunk)) => (position, chunk),
+            _ => return Err(format!("Invalid QR code part: {}", line)),
+        };
+        let (index, total) = match position.split('/').collect::<Vec<_>>().as_slice() {
+            [index, total] => (
+                index.parse::<usize>().map_err(|err| err.to_string())?,
+                total.parse::<usize>().map_err(|err| err.to_string())?,
+            ),
+            _ => return Err(format!("Invalid QR code part: {}", line)),
+        };
+        if parts.is_empty() {
+            parts = vec![None; total];
+        }
+        if total != parts.len() || index == 0 || index > total {
+            return Err(format!("Invalid QR code part: {}", line));
+        }
+        parts[index - 1] = Some(chunk);
+    }
+    let missing: Vec<String> = parts
+        .iter()
+        .enumerate()
+        .filter(|(_, part)| part.is_none())
+        .map(|(index, _)| (index + 1).to_string())
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!(
+            "The parts {} of {} of the QR payload are missing",
+            missing.join(", "),
+            parts.len()
+        ));
+    }
+    Ok(parts.into_iter().flatten().collect())
+}
+
+fn render_qr_code(text: &str) -> color_eyre::eyre::Result<String> {
+    let code = qrcode::QrCode::with_error_correction_level(text.as_bytes(), qrcode::EcLevel::L)
+        .map_err(|err| color_eyre::Report::msg(format!("Failed to build a QR code: {}", err)))?;
+    Ok(code
+        .render::<qrcode::render::unicode::Dense1x2>()
+        .dark_color(qrcode::render::unicode::Dense1x2::Light)
+        .light_color(qrcode::render::unicode::Dense1x2::Dark)
+        .build())
+}
+
+/// Prints a payload as QR codes to stderr. A multi-part payload is animated until Enter is
+/// pressed, or printed part after part in the non-interactive mode.
+pub fn print_qr_code(payload: &str) -> crate::CliResult {
+    let frames = split_qr_payload(payload, QR_CODE_PART_SIZE)
+        .iter()
+        .map(|part| render_qr_code(part))
+        .collect::<color_eyre::eyre::Result<Vec<String>>>()?;
+    if frames.len() == 1 || is_non_interactive() {
+        for (index, frame) in frames.iter().enumerate() {
+            eprintln!("\nQR code {} of {}:\n{}", index + 1, frames.len(), frame);
+        }
+        return Ok(());
+    }
+    let scanned = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    {
+        let scanned = scanned.clone();
+        std::thread::spawn(move || {
+            let mut line = String::new();
+            let _ = std::io::stdin().read_line(&mut line);
+            scanned.store(true, std::sync::atomic::Ordering::Relaxed);
+        });
+    }
+    for (index, frame) in frames.iter().enumerate().cycle() {
+        if scanned.load(std::sync::atomic::Ordering::Relaxed) {
+            break;
+        }
+        eprint!(
+            "\x1B[2J\x1B[H{}\nQR code {} of {}, press Enter when all of them are scanned",
+            frame,
+            index + 1,
+            frames.len()
+        );
+        std::thread::sleep(QR_CODE_FRAME_DELAY);
+    }
+    eprintln!();
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::str::FromStr;
 
+    #[test]
+    fn retry_policy_backoff_doubles_up_to_the_cap() {
+        let retry_policy = RetryPolicy::default();
+        assert_eq!(retry_policy.backoff(1), std::time::Duration::from_secs(1));
+        assert_eq!(retry_policy.backoff(2), std::time::Duration::from_secs(2));
+        assert_eq!(retry_policy.backoff(5), std::time::Duration::from_secs(16));
+        assert_eq!(retry_policy.backoff(6), MAX_BACKOFF);
+        assert_eq!(retry_policy.backoff(u32::MAX), MAX_BACKOFF);
+        let retry_policy = RetryPolicy {
+            backoff_ms: u64::MAX,
+            ..RetryPolicy::default()
+        };
+        assert_eq!(retry_policy.backoff(1), MAX_BACKOFF);
+        assert_eq!(
+            RetryPolicy::default().deadline(),
+            std::time::Duration::from_secs(120)
+        );
+    }
+    #[test]
+    fn allowance_from_str_and_cli_arg() {
+        assert_eq!(
+            Allowance::from_str(" Unlimited ").unwrap(),
+            Allowance::Unlimited
+        );
+        let allowance = Allowance::from_str("0.25 NEAR").unwrap();
+        assert_eq!(allowance.to_yoctonear(), Some(25 * 10u128.pow(22)));
+        assert_eq!(allowance.to_cli_arg(), "0.25 NEAR");
+        assert_eq!(Allowance::from_yoctonear(None).to_cli_arg(), "unlimited");
+        assert!(Allowance::from_str("ten").is_err());
+    }
     #[test]
     fn near_balance_from_str_currency_near() {
         assert_eq!(
@@ -614,4 +1545,427 @@ mod tests {
             Err("Near Gas: invalid digit found in string".to_string())
         );
     }
+    #[test]
+    fn near_balance_to_cli_arg_is_exact() {
+        for near_balance in &[
+            "2 NEAR",
+            "0.000000000000000000000001 NEAR",
+            "1.5 NEAR",
+            "0 NEAR",
+        ] {
+            let near_balance = NearBalance::from_str(near_balance).unwrap();
+            assert_eq!(
+                NearBalance::from_str(&near_balance.to_cli_arg()),
+                Ok(near_balance)
+            );
+        }
+        assert_eq!(
+            NearBalance::from_str("1.5 NEAR").unwrap().to_cli_arg(),
+            "1.5 NEAR"
+        );
+    }
+    #[test]
+    fn near_gas_to_cli_arg_is_exact() {
+        for near_gas in &["100 tgas", "0.5 tgas", "1 ggas"] {
+            let near_gas = NearGas::from_str(near_gas).unwrap();
+            assert_eq!(NearGas::from_str(&near_gas.to_cli_arg()), Ok(near_gas));
+        }
+        assert_eq!(
+            NearGas::from_str("30 tgas").unwrap().to_cli_arg(),
+            "30 TeraGas"
+        );
+    }
+    #[test]
+    fn encrypt_with_passphrase_round_trip() {
+        let kdf_params = ScryptParams {
+            log_n: 4,
+            r: 8,
+            p: 1,
+        };
+        let encrypted_data =
+            encrypt_with_kdf_params(b"ed25519:secret", "passphrase", kdf_params).unwrap();
+        assert_ne!(encrypted_data.ciphertext, base64::encode(b"ed25519:secret"));
+        assert_eq!(
+            decrypt_with_passphrase(&encrypted_data, "passphrase").unwrap(),
+            b"ed25519:secret".to_vec()
+        );
+    }
+    #[test]
+    fn decrypt_with_wrong_passphrase() {
+        let kdf_params = ScryptParams {
+            log_n: 4,
+            r: 8,
+            p: 1,
+        };
+        let encrypted_data =
+            encrypt_with_kdf_params(b"ed25519:secret", "passphrase", kdf_params).unwrap();
+        assert!(decrypt_with_passphrase(&encrypted_data, "wrong passphrase").is_err());
+    }
+    #[test]
+    fn retry_policy_backoff_doubles() {
+        let retry_policy = RetryPolicy {
+            max_attempts: 10,
+            backoff_ms: 500,
+            deadline_secs: 120,
+        };
+        assert_eq!(
+            retry_policy.backoff(1),
+            std::time::Duration::from_millis(500)
+        );
+        assert_eq!(
+            retry_policy.backoff(2),
+            std::time::Duration::from_millis(1000)
+        );
+        assert_eq!(
+            retry_policy.backoff(4),
+            std::time::Duration::from_millis(4000)
+        );
+    }
+    #[test]
+    fn retry_policy_backoff_is_capped() {
+        let retry_policy = RetryPolicy::default();
+        assert_eq!(retry_policy.backoff(10), MAX_BACKOFF);
+        assert_eq!(retry_policy.backoff(100), MAX_BACKOFF);
+    }
+    #[test]
+    fn to_kebab_case_like_clap() {
+        assert_eq!(to_kebab_case("Testnet"), "testnet");
+        assert_eq!(to_kebab_case("AtBlockHeight"), "at-block-height");
+        assert_eq!(to_kebab_case("StakeNEARTokens"), "stake-near-tokens");
+        assert_eq!(to_kebab_case("NEAR"), "near");
+    }
+    #[test]
+    fn mask_secrets_hides_secret_flag_values() {
+        let args = vec![
+            "near-cli",
+            "sign-private-key",
+            "--signer-public-key",
+            "ed25519:public",
+            "--signer-secret-key",
+            "ed25519:secret",
+            "--master-seed-phrase=word word",
+        ]
+        .into_iter()
+        .map(String::from);
+        assert_eq!(
+            mask_secrets(args),
+            vec![
+                "near-cli",
+                "sign-private-key",
+                "--signer-public-key",
+                "ed25519:public",
+                "--signer-secret-key",
+                "***",
+                "--master-seed-phrase=***",
+            ]
+        );
+    }
+
+    #[test]
+    fn qr_payload_round_trip() {
+        let payload = "A".repeat(950);
+        let parts = split_qr_payload(&payload, 400);
+        assert_eq!(parts.len(), 3);
+        assert!(parts[0].starts_with("near-cli:1/3:"));
+        let scanned = format!("{}\n{}\n{}\n", parts[2], parts[0], parts[1]);
+        assert_eq!(join_qr_payload(&scanned).unwrap(), payload);
+    }
+    #[test]
+    fn qr_payload_single_part() {
+        assert_eq!(split_qr_payload("abc", 400), vec!["abc".to_string()]);
+        assert_eq!(join_qr_payload("  abc\n").unwrap(), "abc");
+    }
+    #[test]
+    fn qr_payload_missing_part() {
+        let parts = split_qr_payload(&"A".repeat(950), 400);
+        let scanned = format!("{}\n{}", parts[0], parts[2]);
+        assert!(join_qr_payload(&scanned).is_err());
+    }
+    #[test]
+    fn parse_secret_key_plain_and_key_file() {
+        let secret_key =
+            near_crypto::SecretKey::from_seed(near_crypto::KeyType::ED25519, "parse_secret_key");
+        assert_eq!(
+            parse_secret_key(&format!("  {}\n", secret_key)).unwrap(),
+            secret_key
+        );
+        let key_file = serde_json::json!({
+            "account_id": "volodymyr.testnet",
+            "public_key": secret_key.public_key(),
+            "private_key": secret_key,
+        });
+        assert_eq!(parse_secret_key(&key_file.to_string()).unwrap(), secret_key);
+    }
+    #[test]
+    fn parse_secret_key_invalid() {
+        assert!(parse_secret_key("ed25519:not-a-key").is_err());
+        assert!(parse_secret_key(r#"{"account_id": "volodymyr.testnet"}"#).is_err());
+    }
+    #[test]
+    fn signer_secret_key_invalid_environment_variable() {
+        std::env::set_var(SIGNER_SECRET_KEY_ENV, "ed25519:not-a-key");
+        let result = signer_secret_key(None, None, "`--signer-secret-key`");
+        std::env::remove_var(SIGNER_SECRET_KEY_ENV);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .ends_with(&format!(" of {}", SIGNER_SECRET_KEY_ENV)));
+    }
+    #[test]
+    fn access_key_permits_function_call() {
+        const MAX_GAS_COST: near_primitives::types::Balance = 3_000_000_000_000_000_000_000;
+        let call = |method_name: &str, deposit: u128| near_primitives::transaction::Transaction {
+            signer_id: "volodymyr.testnet".to_string(),
+            public_key: near_crypto::PublicKey::empty(near_crypto::KeyType::ED25519),
+            nonce: 1,
+            receiver_id: "counter.testnet".to_string(),
+            block_hash: Default::default(),
+            actions: vec![near_primitives::transaction::Action::FunctionCall(
+                near_primitives::transaction::FunctionCallAction {
+                    method_name: method_name.to_string(),
+                    args: vec![],
+                    gas: 30_000_000_000_000,
+                    deposit,
+                },
+            )],
+        };
+        let function_call_key = near_primitives::views::AccessKeyPermissionView::FunctionCall {
+            allowance: None,
+            receiver_id: "counter.testnet".to_string(),
+            method_names: vec!["increment".to_string()],
+        };
+        assert!(access_key_permits(
+            &function_call_key,
+            &call("increment", 0),
+            MAX_GAS_COST
+        ));
+        assert!(!access_key_permits(
+            &function_call_key,
+            &call("decrement", 0),
+            MAX_GAS_COST
+        ));
+        assert!(!access_key_permits(
+            &function_call_key,
+            &call("increment", 1),
+            MAX_GAS_COST
+        ));
+        let limited_key =
+            |allowance| near_primitives::views::AccessKeyPermissionView::FunctionCall {
+                allowance: Some(allowance),
+                receiver_id: "counter.testnet".to_string(),
+                method_names: vec![],
+            };
+        assert!(access_key_permits(
+            &limited_key(MAX_GAS_COST),
+            &call("increment", 0),
+            MAX_GAS_COST
+        ));
+        assert!(!access_key_permits(
+            &limited_key(MAX_GAS_COST - 1),
+            &call("increment", 0),
+            MAX_GAS_COST
+        ));
+        let mut transfer = call("increment", 0);
+        transfer.actions = vec![near_primitives::transaction::Action::Transfer(
+            near_primitives::transaction::TransferAction { deposit: 1 },
+        )];
+        assert!(!access_key_permits(
+            &function_call_key,
+            &transfer,
+            MAX_GAS_COST
+        ));
+        assert!(access_key_permits(
+            &near_primitives::views::AccessKeyPermissionView::FullAccess,
+            &transfer,
+            MAX_GAS_COST
+        ));
+    }
+
+    /// Records the API-key header of every request and answers with an empty result
+    async fn record_api_key(
+        request: actix_web::HttpRequest,
+        api_keys: actix_web::web::Data<std::sync::Mutex<Vec<Option<String>>>>,
+    ) -> actix_web::HttpResponse {
+        api_keys.lock().unwrap().push(
+            request
+                .headers()
+                .get("x-api-key")
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned),
+        );
+        actix_web::HttpResponse::Ok().json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "near-cli",
+            "result": null,
+        }))
+    }
+
+    #[test]
+    fn named_network_rpc_requests_send_the_api_key() {
+        actix::System::new().block_on(async {
+            let api_keys = actix_web::web::Data::new(std::sync::Mutex::new(vec![]));
+            let app_api_keys = api_keys.clone();
+            let http_server = actix_web::HttpServer::new(move || {
+                actix_web::App::new()
+                    .app_data(app_api_keys.clone())
+                    .default_service(actix_web::web::route().to(record_api_key))
+            })
+            .workers(1)
+            .bind("127.0.0.1:0")
+            .unwrap();
+            let rpc_url = url::Url::parse(&format!("http://{}/", http_server.addrs()[0])).unwrap();
+            let server = http_server.run();
+            let connection_config = ConnectionConfig::Named(crate::config::NetworkConfig {
+                name: "staging".to_string(),
+                rpc_url,
+                archival_rpc_url: None,
+                wallet_url: None,
+                credentials_dir: None,
+                api_key: Some(crate::config::ApiKey {
+                    header: "x-api-key".to_string(),
+                    value: "secret".to_string(),
+                }),
+                chain_id: None,
+            });
+            // Only the requests matter here, not whether the empty results parse
+            let _ = connection_config.rpc_client().status().await;
+            let _ = connection_config
+                .call_json_rpc("status", serde_json::json!([]))
+                .await;
+            server.stop(true).await;
+            assert_eq!(
+                *api_keys.lock().unwrap(),
+                vec![Some("secret".to_string()); 2]
+            );
+        });
+    }
+
+    #[test]
+    fn describe_action_of_each_kind() {
+        let public_key =
+            near_crypto::SecretKey::from_seed(near_crypto::KeyType::ED25519, "describe_action")
+                .public_key();
+        let describe = |action| describe_action(&action);
+        assert_eq!(
+            describe(near_primitives::transaction::Action::CreateAccount(
+                near_primitives::transaction::CreateAccountAction {}
+            )),
+            "Create the account"
+        );
+        let code = vec![0, 1, 2];
+        let code_hash = near_primitives::hash::hash(&code);
+        assert_eq!(
+            describe(near_primitives::transaction::Action::DeployContract(
+                near_primitives::transaction::DeployContractAction { code }
+            )),
+            format!(
+                "Deploy a contract: 3 bytes, sha256 {} (code hash {})",
+                hex::encode(code_hash.as_ref()),
+                code_hash
+            )
+        );
+        let function_call = |args: &[u8]| {
+            near_primitives::transaction::Action::FunctionCall(
+                near_primitives::transaction::FunctionCallAction {
+                    method_name: "increment".to_string(),
+                    args: args.to_vec(),
+                    gas: 30_000_000_000_000,
+                    deposit: ONE_NEAR / 2,
+                },
+            )
+        };
+        assert_eq!(
+            describe(function_call(br#"{"by": 1}"#)),
+            r#"Call `increment` with 30 TeraGas attached and a deposit of 0.5 NEAR, args: {"by":1}"#
+        );
+        assert_eq!(
+            describe(function_call(b"")),
+            "Call `increment` with 30 TeraGas attached and a deposit of 0.5 NEAR, args: none"
+        );
+        assert_eq!(
+            describe(function_call(b"by 1")),
+            r#"Call `increment` with 30 TeraGas attached and a deposit of 0.5 NEAR, args: "by 1" (not JSON)"#
+        );
+        assert_eq!(
+            describe(function_call(&[0xff, 0])),
+            "Call `increment` with 30 TeraGas attached and a deposit of 0.5 NEAR, args: 2 bytes of binary data, base64 /wA="
+        );
+        assert_eq!(
+            describe(near_primitives::transaction::Action::Transfer(
+                near_primitives::transaction::TransferAction {
+                    deposit: 3 * ONE_NEAR
+                }
+            )),
+            "Transfer 3 NEAR"
+        );
+        assert_eq!(
+            describe(near_primitives::transaction::Action::Stake(
+                near_primitives::transaction::StakeAction {
+                    stake: 100 * ONE_NEAR,
+                    public_key: public_key.clone(),
+                }
+            )),
+            format!("Stake 100 NEAR with the validator key {}", public_key)
+        );
+        let add_key = |permission| {
+            describe(near_primitives::transaction::Action::AddKey(
+                near_primitives::transaction::AddKeyAction {
+                    public_key: public_key.clone(),
+                    access_key: near_primitives::account::AccessKey {
+                        nonce: 0,
+                        permission,
+                    },
+                },
+            ))
+        };
+        assert_eq!(
+            add_key(near_primitives::account::AccessKeyPermission::FullAccess),
+            format!(
+                "Add the full access key {} (it can do anything with the account)",
+                public_key
+            )
+        );
+        assert_eq!(
+            add_key(near_primitives::account::AccessKeyPermission::FunctionCall(
+                near_primitives::account::FunctionCallPermission {
+                    allowance: Some(ONE_NEAR / 4),
+                    receiver_id: "counter.testnet".to_string(),
+                    method_names: vec!["increment".to_string(), "reset".to_string()],
+                }
+            )),
+            format!(
+                "Add the function-call key {} for the methods increment, reset of `counter.testnet`, allowance 0.25 NEAR",
+                public_key
+            )
+        );
+        assert_eq!(
+            add_key(near_primitives::account::AccessKeyPermission::FunctionCall(
+                near_primitives::account::FunctionCallPermission {
+                    allowance: None,
+                    receiver_id: "counter.testnet".to_string(),
+                    method_names: vec![],
+                }
+            )),
+            format!(
+                "Add the function-call key {} for any method of `counter.testnet`, allowance unlimited",
+                public_key
+            )
+        );
+        assert_eq!(
+            describe(near_primitives::transaction::Action::DeleteKey(
+                near_primitives::transaction::DeleteKeyAction {
+                    public_key: public_key.clone()
+                }
+            )),
+            format!("Delete the access key {}", public_key)
+        );
+        assert_eq!(
+            describe(near_primitives::transaction::Action::DeleteAccount(
+                near_primitives::transaction::DeleteAccountAction {
+                    beneficiary_id: "alice.testnet".to_string()
+                }
+            )),
+            "Delete the account and send its balance to alice.testnet"
+        );
+    }
 }
@@ -0,0 +1,195 @@
+/// The user configuration kept in `~/.config/near-cli/config.toml`, e.g.:
+///
+/// ```toml
+/// default_network = "staging"
+///
+/// [networks.staging]
+/// rpc_url = "https://rpc.staging.example.com"
+/// archival_rpc_url = "https://archival-rpc.staging.example.com"
+/// wallet_url = "https://wallet.staging.example.com"
+/// credentials_dir = ".near-credentials/staging/"
+/// api_key = { header = "x-api-key", value = "secret" }
+/// chain_id = "testnet"
+///
+/// [external_signer]
+/// program = "/usr/local/bin/hsm-sign"
+/// args = ["--slot", "1"]
+/// ```
+#[derive(Debug, Default, Clone, serde::Deserialize)]
+pub struct Config {
+    /// Used instead of asking for the network in the interactive mode
+    pub default_network: Option<String>,
+    #[serde(default)]
+    pub networks: std::collections::BTreeMap<String, NetworkConfig>,
+    /// The program `sign-with-external-signer` runs unless `--program` is given
+    pub external_signer: Option<ExternalSignerConfig>,
+}
+
+/// A named network; only `rpc_url` is required
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct NetworkConfig {
+    #[serde(skip)]
+    pub name: String,
+    pub rpc_url: url::Url,
+    pub archival_rpc_url: Option<url::Url>,
+    pub wallet_url: Option<url::Url>,
+    /// Relative to the home directory unless absolute (`.near-credentials/<name>/` by default)
+    pub credentials_dir: Option<String>,
+    pub api_key: Option<ApiKey>,
+    /// Whether the network is mainnet, for the confirmation before sending, without asking
+    /// the RPC server for it
+    pub chain_id: Option<String>,
+}
+
+/// A header sent with every RPC request to the network
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct ApiKey {
+    #[serde(default = "default_api_key_header")]
+    pub header: String,
+    pub value: String,
+}
+
+/// A program that signs transactions with a key near-cli never sees
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct ExternalSignerConfig {
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// The networks that are known without a configuration file
+const BUILT_IN_NETWORKS: [&str; 4] = ["testnet", "mainnet", "betanet", "localnet"];
+
+fn default_api_key_header() -> String {
+    "x-api-key".to_string()
+}
+
+impl Config {
+    pub fn path() -> std::path::PathBuf {
+        let home_dir = dirs::home_dir().expect("Impossible to get your home dir!");
+        home_dir.join(crate::consts::CONFIG_FILE_PATH)
+    }
+
+    /// Reads the configuration file; a missing file is the same as an empty one
+    pub fn load() -> color_eyre::eyre::Result<Self> {
+        let path = Self::path();
+        let data = match std::fs::read_to_string(&path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(color_eyre::Report::msg(format!(
+                    "Failed to read {}: {:?}",
+                    path.display(),
+                    err
+                )))
+            }
+        };
+        let mut config: Self = toml::from_str(&data).map_err(|err| {
+            color_eyre::Report::msg(format!("Failed to parse {}: {}", path.display(), err))
+        })?;
+        for (name, network_config) in config.networks.iter_mut() {
+            network_config.name = name.clone();
+            if network_config.credentials_dir.is_none() {
+                network_config.credentials_dir = Some(format!(".near-credentials/{}/", name));
+            }
+        }
+        Ok(config)
+    }
+
+    /// Networks defined in the configuration file take precedence over the built-in ones
+    pub fn connection_config(
+        &self,
+        network_name: &str,
+    ) -> color_eyre::eyre::Result<crate::common::ConnectionConfig> {
+        if let Some(network_config) = self.networks.get(network_name) {
+            return Ok(crate::common::ConnectionConfig::Named(
+                network_config.clone(),
+            ));
+        }
+        match network_name {
+            "testnet" => Ok(crate::common::ConnectionConfig::Testnet),
+            "mainnet" => Ok(crate::common::ConnectionConfig::Mainnet),
+            "betanet" => Ok(crate::common::ConnectionConfig::Betanet),
+            "localnet" => Ok(crate::common::ConnectionConfig::Localnet),
+            _ => Err(color_eyre::Report::msg(format!(
+                "Unknown network <{}>; the known networks are: {}",
+                network_name,
+                BUILT_IN_NETWORKS
+                    .iter()
+                    .map(|name| name.to_string())
+                    .chain(self.networks.keys().cloned())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+
+    pub fn default_connection_config(
+        &self,
+    ) -> color_eyre::eyre::Result<Option<crate::common::ConnectionConfig>> {
+        match &self.default_network {
+            Some(network_name) => Ok(Some(self.connection_config(network_name)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// The `default_network` of the configuration file, used instead of asking for the network;
+/// an unreadable configuration file is reported and ignored
+pub fn default_network() -> Option<crate::common::ConnectionConfig> {
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{}", err);
+            return None;
+        }
+    };
+    match config.default_connection_config() {
+        Ok(Some(connection_config)) => {
+            eprintln!(
+                "Using the default network <{}> ({}) of {}",
+                config.default_network.unwrap_or_default(),
+                connection_config.rpc_url(),
+                Config::path().display()
+            );
+            Some(connection_config)
+        }
+        Ok(None) => None,
+        Err(err) => {
+            eprintln!("{}", err);
+            None
+        }
+    }
+}
+
+/// Asks for one of the networks `--network` accepts: the built-in ones and the ones of the
+/// configuration file (an unreadable configuration file leaves only the built-in ones)
+pub fn choose_network() -> color_eyre::eyre::Result<crate::common::ConnectionConfig> {
+    crate::common::ensure_interactive("`--network <NETWORK>` of `named`")?;
+    let config = Config::load().unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        Config::default()
+    });
+    let networks = BUILT_IN_NETWORKS
+        .iter()
+        .map(|name| name.to_string())
+        .chain(
+            config
+                .networks
+                .keys()
+                .filter(|name| !BUILT_IN_NETWORKS.contains(&name.as_str()))
+                .cloned(),
+        )
+        .collect::<Vec<_>>();
+    println!();
+    let selected_network =
+        dialoguer::Select::with_theme(&dialoguer::theme::ColorfulTheme::default())
+            .with_prompt("Select the network")
+            .items(&networks)
+            .default(0)
+            .interact()
+            .unwrap();
+    Ok(config
+        .connection_config(&networks[selected_network])
+        .expect("Every listed network is known"))
+}
@@ -5,6 +5,8 @@ pub const MAINNET_ARCHIVAL_API_SERVER_URL: &str = "https://archival-rpc.mainnet.
 pub const BETANET_API_SERVER_URL: &str = "https://rpc.betanet.near.org";
 // NOTE: There is no dedicated archival RPC server for betanet by design
 pub const BETANET_ARCHIVAL_API_SERVER_URL: &str = "https://rpc.betanet.near.org";
+// NOTE: A local `neard` node serves both the RPC and its own archive
+pub const LOCALNET_API_SERVER_URL: &str = "http://127.0.0.1:3030";
 
 pub const TESTNET_WALLET_URL: &str = "https://wallet.testnet.near.org";
 pub const MAINNET_WALLET_URL: &str = "https://wallet.mainnet.near.org";
@@ -14,4 +16,10 @@ pub const DIR_NAME_KEY_CHAIN: &str = ".near-credentials/default/";
 pub const DIR_NAME_TESTNET: &str = ".near-credentials/testnet/";
 pub const DIR_NAME_MAINNET: &str = ".near-credentials/mainnet/";
 pub const DIR_NAME_BETANET: &str = ".near-credentials/betanet/";
+pub const DIR_NAME_LOCALNET: &str = ".near-credentials/localnet/";
 pub const DIR_NAME_CUSTOM: &str = ".near-credentials/default/";
+
+/// The key of the genesis validator created by `neard init` (`test.near` by default)
+pub const LOCALNET_VALIDATOR_KEY_FILE_PATH: &str = ".near/validator_key.json";
+
+pub const CONFIG_FILE_PATH: &str = ".config/near-cli/config.toml";
@@ -0,0 +1,276 @@
+use std::io::Write;
+
+/// Key pairs kept as `<account_id>.json` files in `~/.near-credentials/<network>/`;
+/// the other keys of an account are kept as `<account_id>-<n>.json`
+pub struct FileKeyStore;
+
+impl FileKeyStore {
+    /// Reads every key file of the network directory; files that are not key files are skipped.
+    fn entries(network: &super::KeyChainNetwork) -> Vec<(std::path::PathBuf, super::StoredKey)> {
+        let mut paths = match std::fs::read_dir(network.key_chain_dir()) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+                .collect::<Vec<_>>(),
+            Err(_) => return vec![],
+        };
+        paths.sort();
+        paths
+            .into_iter()
+            .filter_map(|path| {
+                let data = std::fs::read_to_string(&path).ok()?;
+                let stored_key: super::StoredKey = serde_json::from_str(&data).ok()?;
+                if stored_key.private_key.is_none() && !stored_key.is_encrypted() {
+                    return None;
+                }
+                Some((path, stored_key))
+            })
+            .collect()
+    }
+
+    fn path_of(
+        network: &super::KeyChainNetwork,
+        stored_key: &super::StoredKey,
+    ) -> color_eyre::eyre::Result<std::path::PathBuf> {
+        Self::entries(network)
+            .into_iter()
+            .find(|(_, entry)| {
+                entry.account_id == stored_key.account_id
+                    && entry.public_key == stored_key.public_key
+            })
+            .map(|(path, _)| path)
+            .ok_or_else(|| {
+                color_eyre::Report::msg(format!(
+                    "There is no file with the key {} of <{}> in {}",
+                    stored_key.public_key,
+                    stored_key.account_id,
+                    network.key_chain_dir().display()
+                ))
+            })
+    }
+
+    /// Writes the key file readable by the owner only; an existing file is replaced atomically
+    fn write(
+        path: &std::path::Path,
+        stored_key: &super::StoredKey,
+    ) -> color_eyre::eyre::Result<()> {
+        let tmp_path = path.with_extension("json.tmp");
+        let mut open_options = std::fs::OpenOptions::new();
+        open_options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut open_options, 0o600);
+        open_options
+            .open(&tmp_path)
+            .map_err(|err| color_eyre::Report::msg(format!("Failed to create file: {:?}", err)))?
+            .write_all(serde_json::to_string(stored_key)?.as_bytes())
+            .map_err(|err| {
+                color_eyre::Report::msg(format!("Failed to write to file: {:?}", err))
+            })?;
+        std::fs::rename(&tmp_path, path).map_err(|err| {
+            color_eyre::Report::msg(format!("Failed to write to file: {:?}", err))
+        })?;
+        Ok(())
+    }
+}
+
+impl super::KeyStore for FileKeyStore {
+    fn location(&self, network: &super::KeyChainNetwork) -> String {
+        network.key_chain_dir().display().to_string()
+    }
+
+    fn list(
+        &self,
+        network: &super::KeyChainNetwork,
+    ) -> color_eyre::eyre::Result<Vec<(String, super::StoredKey)>> {
+        Ok(Self::entries(network)
+            .into_iter()
+            .map(|(path, stored_key)| (path.display().to_string(), stored_key))
+            .collect())
+    }
+
+    /// `<account_id>.json`, the file written by `save`, goes first, then the other files of the
+    /// account (e.g. `<account_id>-2.json`)
+    fn find(
+        &self,
+        network: &super::KeyChainNetwork,
+        account_id: &str,
+    ) -> color_eyre::eyre::Result<Vec<(String, super::StoredKey)>> {
+        let exact_file_name = format!("{}.json", account_id);
+        let mut entries = Self::entries(network)
+            .into_iter()
+            .filter(|(_, stored_key)| stored_key.account_id == account_id)
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|(path, _)| {
+            (
+                path.file_name().and_then(|file_name| file_name.to_str())
+                    != Some(exact_file_name.as_str()),
+                path.clone(),
+            )
+        });
+        Ok(entries
+            .into_iter()
+            .map(|(path, stored_key)| (path.display().to_string(), stored_key))
+            .collect())
+    }
+
+    fn save(
+        &self,
+        network: &super::KeyChainNetwork,
+        stored_key: &super::StoredKey,
+    ) -> color_eyre::eyre::Result<String> {
+        let dir = network.key_chain_dir();
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("{}.json", &stored_key.account_id));
+        let copies = Self::entries(network)
+            .into_iter()
+            .filter(|(other_path, entry)| {
+                other_path != &path
+                    && entry.account_id == stored_key.account_id
+                    && entry.public_key == stored_key.public_key
+            })
+            .map(|(other_path, _)| other_path)
+            .collect::<Vec<_>>();
+        // Another key in `<account_id>.json` is moved aside to the first free `<account_id>-<n>.json`
+        let keeps_same_key = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|data| serde_json::from_str::<super::StoredKey>(&data).ok())
+            .map(|entry| entry.public_key == stored_key.public_key);
+        if path.exists() && keeps_same_key != Some(true) {
+            let free_path = (2..)
+                .map(|n| dir.join(format!("{}-{}.json", &stored_key.account_id, n)))
+                .find(|free_path| !free_path.exists())
+                .unwrap();
+            std::fs::rename(&path, &free_path).map_err(|err| {
+                color_eyre::Report::msg(format!(
+                    "Failed to move {} to {}: {:?}",
+                    path.display(),
+                    free_path.display(),
+                    err
+                ))
+            })?;
+        }
+        Self::write(&path, stored_key)?;
+        // The key is kept once, in `<account_id>.json`
+        for copy in copies {
+            std::fs::remove_file(&copy).map_err(|err| {
+                color_eyre::Report::msg(format!(
+                    "Failed to remove file {}: {:?}",
+                    copy.display(),
+                    err
+                ))
+            })?;
+        }
+        Ok(path.display().to_string())
+    }
+
+    fn update(
+        &self,
+        network: &super::KeyChainNetwork,
+        stored_key: &super::StoredKey,
+    ) -> color_eyre::eyre::Result<String> {
+        let path = Self::path_of(network, stored_key)?;
+        Self::write(&path, stored_key)?;
+        Ok(path.display().to_string())
+    }
+
+    fn remove(
+        &self,
+        network: &super::KeyChainNetwork,
+        stored_key: &super::StoredKey,
+    ) -> color_eyre::eyre::Result<String> {
+        let path = Self::path_of(network, stored_key)?;
+        std::fs::remove_file(&path).map_err(|err| {
+            color_eyre::Report::msg(format!(
+                "Failed to remove file {}: {:?}",
+                path.display(),
+                err
+            ))
+        })?;
+        Ok(path.display().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{KeyChainNetwork, KeyStore, StoredKey};
+    use super::FileKeyStore;
+
+    fn stored_key(account_id: &str) -> StoredKey {
+        let secret_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519);
+        StoredKey {
+            account_id: account_id.to_string(),
+            public_key: secret_key.public_key(),
+            private_key: Some(secret_key),
+            master_seed_phrase: None,
+            seed_phrase_hd_path: None,
+            encrypted_secrets: None,
+        }
+    }
+
+    #[test]
+    fn file_key_store_round_trip() {
+        // An absolute directory name replaces the home directory in `key_chain_dir`
+        let dir = std::env::temp_dir().join(format!("near-cli-key-store-{}", std::process::id()));
+        let network = KeyChainNetwork::new("test", dir.to_str().unwrap());
+        let key_store = FileKeyStore;
+
+        let older_key = stored_key("alice.testnet");
+        std::fs::create_dir_all(&dir).unwrap();
+        FileKeyStore::write(&dir.join("alice.testnet-2.json"), &older_key).unwrap();
+        let saved_key = stored_key("alice.testnet");
+        key_store.save(&network, &saved_key).unwrap();
+        key_store
+            .save(&network, &stored_key("bob.testnet"))
+            .unwrap();
+
+        let found = key_store.find(&network, "alice.testnet").unwrap();
+        assert_eq!(
+            found
+                .iter()
+                .map(|(_, stored_key)| &stored_key.public_key)
+                .collect::<Vec<_>>(),
+            vec![&saved_key.public_key, &older_key.public_key]
+        );
+        assert_eq!(
+            found[0].1.private_key, saved_key.private_key,
+            "the secret key survives the round trip"
+        );
+        assert_eq!(key_store.list(&network).unwrap().len(), 3);
+
+        // Saving another key moves the preferred one aside instead of overwriting it
+        let newest_key = stored_key("alice.testnet");
+        key_store.save(&network, &newest_key).unwrap();
+        assert!(dir.join("alice.testnet-3.json").exists());
+        assert_eq!(
+            key_store
+                .find(&network, "alice.testnet")
+                .unwrap()
+                .iter()
+                .map(|(_, stored_key)| &stored_key.public_key)
+                .collect::<Vec<_>>(),
+            vec![
+                &newest_key.public_key,
+                &older_key.public_key,
+                &saved_key.public_key
+            ]
+        );
+        // Saving a key again makes it the preferred one without keeping a copy
+        key_store.save(&network, &older_key).unwrap();
+        let found = key_store.find(&network, "alice.testnet").unwrap();
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0].1.public_key, older_key.public_key);
+        assert_eq!(key_store.list(&network).unwrap().len(), 4);
+
+        key_store.remove(&network, &newest_key).unwrap();
+        key_store.remove(&network, &saved_key).unwrap();
+        key_store.remove(&network, &older_key).unwrap();
+        assert!(key_store
+            .find(&network, "alice.testnet")
+            .unwrap()
+            .is_empty());
+        assert!(key_store.remove(&network, &older_key).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
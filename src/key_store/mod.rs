@@ -0,0 +1,400 @@
+use dialoguer::{theme::ColorfulTheme, Password, Select};
+
+mod file_key_store;
+#[cfg(target_os = "linux")]
+mod secret_service_key_store;
+
+/// The keychain of a network: the built-in networks, `default` (used by custom networks) and
+/// the networks of the configuration file, which keep their keys in their `credentials_dir`
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyChainNetwork {
+    name: String,
+    dir_name: String,
+}
+
+impl KeyChainNetwork {
+    const BUILT_IN: [(&'static str, &'static str); 5] = [
+        ("testnet", crate::consts::DIR_NAME_TESTNET),
+        ("mainnet", crate::consts::DIR_NAME_MAINNET),
+        ("betanet", crate::consts::DIR_NAME_BETANET),
+        ("localnet", crate::consts::DIR_NAME_LOCALNET),
+        ("default", crate::consts::DIR_NAME_KEY_CHAIN),
+    ];
+
+    pub(crate) fn new(name: &str, dir_name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            dir_name: dir_name.to_string(),
+        }
+    }
+
+    /// The keychain shared by the custom networks
+    pub fn default_key_chain() -> Self {
+        Self::new("default", crate::consts::DIR_NAME_KEY_CHAIN)
+    }
+
+    fn from_network_config(network_config: &crate::config::NetworkConfig) -> Self {
+        Self::new(
+            &network_config.name,
+            network_config
+                .credentials_dir
+                .as_deref()
+                .unwrap_or(crate::consts::DIR_NAME_CUSTOM),
+        )
+    }
+
+    /// The built-in keychains followed by the ones of the configuration file, each directory
+    /// once (an unreadable configuration file leaves only the built-in ones)
+    pub fn all() -> Vec<Self> {
+        let config = crate::config::Config::load().unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            crate::config::Config::default()
+        });
+        Self::unique_dirs(
+            Self::BUILT_IN
+                .iter()
+                .map(|(name, dir_name)| Self::new(name, dir_name))
+                .chain(
+                    config
+                        .networks
+                        .values()
+                        .filter(|network_config| {
+                            !Self::BUILT_IN
+                                .iter()
+                                .any(|(name, _)| *name == network_config.name)
+                        })
+                        .map(Self::from_network_config),
+                ),
+        )
+    }
+
+    /// Keeps the first keychain of each directory, e.g. `default` rather than a named network
+    /// without `credentials_dir`
+    fn unique_dirs(key_chains: impl Iterator<Item = Self>) -> Vec<Self> {
+        let mut unique: Vec<Self> = vec![];
+        for key_chain in key_chains {
+            if !unique
+                .iter()
+                .any(|other| other.key_chain_dir() == key_chain.key_chain_dir())
+            {
+                unique.push(key_chain);
+            }
+        }
+        unique
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn dir_name(&self) -> &str {
+        &self.dir_name
+    }
+
+    pub fn key_chain_dir(&self) -> std::path::PathBuf {
+        let home_dir = dirs::home_dir().expect("Impossible to get your home dir!");
+        home_dir.join(self.dir_name())
+    }
+
+    pub fn choose_network() -> color_eyre::eyre::Result<Self> {
+        crate::common::ensure_interactive("`--network <NETWORK>`")?;
+        println!();
+        let variants = Self::all();
+        let networks = variants
+            .iter()
+            .map(|network| network.name())
+            .collect::<Vec<_>>();
+        let selected_network = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Select the keychain network")
+            .items(&networks)
+            .default(0)
+            .interact()
+            .unwrap();
+        Ok(variants[selected_network].clone())
+    }
+
+    /// Custom networks share the `default` keychain
+    pub fn from_connection_config(connection_config: &crate::common::ConnectionConfig) -> Self {
+        match connection_config {
+            crate::common::ConnectionConfig::Testnet => {
+                Self::new("testnet", crate::consts::DIR_NAME_TESTNET)
+            }
+            crate::common::ConnectionConfig::Mainnet => {
+                Self::new("mainnet", crate::consts::DIR_NAME_MAINNET)
+            }
+            crate::common::ConnectionConfig::Betanet => {
+                Self::new("betanet", crate::consts::DIR_NAME_BETANET)
+            }
+            crate::common::ConnectionConfig::Localnet => {
+                Self::new("localnet", crate::consts::DIR_NAME_LOCALNET)
+            }
+            crate::common::ConnectionConfig::Custom { url: _ } => Self::default_key_chain(),
+            crate::common::ConnectionConfig::Named(network_config) => {
+                Self::from_network_config(network_config)
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for KeyChainNetwork {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let networks = Self::all();
+        networks
+            .iter()
+            .find(|network| network.name() == s)
+            .cloned()
+            .ok_or_else(|| {
+                format!(
+                    "Unknown keychain <{}>; the known keychains are: {}",
+                    s,
+                    networks
+                        .iter()
+                        .map(|network| network.name())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
+}
+
+/// The location and the key of the genesis validator of a local node
+/// (`~/.near/validator_key.json`), if the node was initialized in this home directory
+pub fn localnet_validator_key() -> color_eyre::eyre::Result<(String, Option<StoredKey>)> {
+    let home_dir = dirs::home_dir().expect("Impossible to get your home dir!");
+    let path = home_dir.join(crate::consts::LOCALNET_VALIDATOR_KEY_FILE_PATH);
+    let location = path.display().to_string();
+    let data = match std::fs::read_to_string(&path) {
+        Ok(data) => data,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok((location, None)),
+        Err(err) => {
+            return Err(color_eyre::Report::msg(format!(
+                "Failed to read {}: {:?}",
+                location, err
+            )))
+        }
+    };
+    let validator_key = serde_json::from_str(&data)
+        .map_err(|err| color_eyre::Report::msg(format!("Failed to parse {}: {}", location, err)))?;
+    Ok((location, Some(validator_key)))
+}
+
+/// The key file format written by `login` and `add implicit-account`.
+/// The secrets are either stored in plaintext or passphrase-encrypted in `encrypted_secrets`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredKey {
+    pub account_id: String,
+    pub public_key: near_crypto::PublicKey,
+    /// `secret_key` in the key files of `neard` (`validator_key.json`, `node_key.json`)
+    #[serde(default, alias = "secret_key", skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<near_crypto::SecretKey>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub master_seed_phrase: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed_phrase_hd_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encrypted_secrets: Option<crate::common::EncryptedData>,
+}
+
+/// The part of a key file that gets encrypted
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct KeySecrets {
+    private_key: near_crypto::SecretKey,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    master_seed_phrase: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    seed_phrase_hd_path: Option<String>,
+}
+
+impl StoredKey {
+    pub fn is_encrypted(&self) -> bool {
+        self.encrypted_secrets.is_some()
+    }
+
+    pub fn encrypt(self, passphrase: &str) -> color_eyre::eyre::Result<Self> {
+        if self.is_encrypted() {
+            return Err(color_eyre::Report::msg(format!(
+                "The key of <{}> is already encrypted",
+                self.account_id
+            )));
+        }
+        let private_key = self.secret_key()?;
+        let secrets = KeySecrets {
+            private_key,
+            master_seed_phrase: self.master_seed_phrase,
+            seed_phrase_hd_path: self.seed_phrase_hd_path,
+        };
+        let encrypted_secrets =
+            crate::common::encrypt_with_passphrase(&serde_json::to_vec(&secrets)?, passphrase)?;
+        Ok(Self {
+            account_id: self.account_id,
+            public_key: self.public_key,
+            private_key: None,
+            master_seed_phrase: None,
+            seed_phrase_hd_path: None,
+            encrypted_secrets: Some(encrypted_secrets),
+        })
+    }
+
+    pub fn decrypt(self, passphrase: &str) -> color_eyre::eyre::Result<Self> {
+        let encrypted_secrets = match &self.encrypted_secrets {
+            Some(encrypted_secrets) => encrypted_secrets,
+            None => return Ok(self),
+        };
+        let secrets: KeySecrets = serde_json::from_slice(&crate::common::decrypt_with_passphrase(
+            encrypted_secrets,
+            passphrase,
+        )?)?;
+        if secrets.private_key.public_key() != self.public_key {
+            return Err(color_eyre::Report::msg(format!(
+                "The encrypted secret key of <{}> does not match its public key {}",
+                self.account_id, self.public_key
+            )));
+        }
+        Ok(Self {
+            account_id: self.account_id,
+            public_key: self.public_key,
+            private_key: Some(secrets.private_key),
+            master_seed_phrase: secrets.master_seed_phrase,
+            seed_phrase_hd_path: secrets.seed_phrase_hd_path,
+            encrypted_secrets: None,
+        })
+    }
+
+    /// Decrypts the key, asking for the passphrase, if it is stored encrypted
+    pub fn unlock(self) -> color_eyre::eyre::Result<Self> {
+        if !self.is_encrypted() {
+            return Ok(self);
+        }
+        crate::common::ensure_interactive(&format!(
+            "the passphrase of the encrypted key {} of <{}>",
+            self.public_key, self.account_id
+        ))?;
+        let passphrase = Password::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Enter the passphrase for the key {} of <{}>",
+                self.public_key, self.account_id
+            ))
+            .interact()?;
+        self.decrypt(&passphrase)
+    }
+
+    /// The secret key of a plaintext (or unlocked) key file
+    pub fn secret_key(&self) -> color_eyre::eyre::Result<near_crypto::SecretKey> {
+        self.private_key.clone().ok_or_else(|| {
+            color_eyre::Report::msg(format!(
+                "The key file of <{}> does not contain a plaintext secret key",
+                self.account_id
+            ))
+        })
+    }
+}
+
+/// Where the keys are kept
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    strum_macros::IntoStaticStr,
+    strum_macros::EnumString,
+    strum_macros::EnumVariantNames,
+    smart_default::SmartDefault,
+)]
+#[strum(serialize_all = "kebab-case")]
+pub enum KeyStoreKind {
+    /// JSON files in ~/.near-credentials
+    #[default]
+    File,
+    /// The Secret Service of the desktop session (GNOME Keyring, KWallet), Linux only
+    SecretService,
+}
+
+impl KeyStoreKind {
+    pub fn open(&self) -> color_eyre::eyre::Result<Box<dyn KeyStore>> {
+        match self {
+            Self::File => Ok(Box::new(self::file_key_store::FileKeyStore)),
+            #[cfg(target_os = "linux")]
+            Self::SecretService => Ok(Box::new(
+                self::secret_service_key_store::SecretServiceKeyStore,
+            )),
+            #[cfg(not(target_os = "linux"))]
+            Self::SecretService => Err(color_eyre::Report::msg(
+                "The Secret Service key store is only available on Linux",
+            )),
+        }
+    }
+}
+
+/// A storage of key pairs, separated by network.
+/// Entries are identified by the account ID and the public key; `location` strings are only
+/// meant to be shown to the user (a file path, a Secret Service item label).
+pub trait KeyStore {
+    /// Where the keys of the network are kept
+    fn location(&self, network: &KeyChainNetwork) -> String;
+
+    fn list(&self, network: &KeyChainNetwork)
+        -> color_eyre::eyre::Result<Vec<(String, StoredKey)>>;
+
+    /// The keys of the account, the preferred one first: the key last saved with `save`
+    /// (`update` leaves the order as it is)
+    fn find(
+        &self,
+        network: &KeyChainNetwork,
+        account_id: &str,
+    ) -> color_eyre::eyre::Result<Vec<(String, StoredKey)>> {
+        Ok(self
+            .list(network)?
+            .into_iter()
+            .filter(|(_, stored_key)| stored_key.account_id == account_id)
+            .collect())
+    }
+
+    /// Saves the key as the preferred key of its account and returns its location;
+    /// the other keys of the account are kept
+    fn save(
+        &self,
+        network: &KeyChainNetwork,
+        stored_key: &StoredKey,
+    ) -> color_eyre::eyre::Result<String>;
+
+    /// Replaces an existing entry with the same account ID and public key
+    fn update(
+        &self,
+        network: &KeyChainNetwork,
+        stored_key: &StoredKey,
+    ) -> color_eyre::eyre::Result<String>;
+
+    fn remove(
+        &self,
+        network: &KeyChainNetwork,
+        stored_key: &StoredKey,
+    ) -> color_eyre::eyre::Result<String>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_chains_sharing_a_directory_are_listed_once() {
+        let key_chains = KeyChainNetwork::unique_dirs(
+            vec![
+                KeyChainNetwork::new("testnet", crate::consts::DIR_NAME_TESTNET),
+                KeyChainNetwork::default_key_chain(),
+                KeyChainNetwork::new("staging", crate::consts::DIR_NAME_CUSTOM),
+                KeyChainNetwork::new("shardnet", ".near-credentials/shardnet/"),
+                KeyChainNetwork::new("shardnet-2", ".near-credentials/shardnet"),
+            ]
+            .into_iter(),
+        );
+        assert_eq!(
+            key_chains
+                .iter()
+                .map(KeyChainNetwork::name)
+                .collect::<Vec<_>>(),
+            vec!["testnet", "default", "shardnet"]
+        );
+    }
+}
@@ -0,0 +1,295 @@
+use secret_service::{EncryptionType, SecretService};
+use std::collections::HashMap;
+
+const APPLICATION: &str = "near-cli";
+
+/// Key pairs kept in the default collection of the Secret Service (D-Bus `org.freedesktop.secrets`).
+/// Every item holds the key file JSON as its secret and is looked up by its attributes.
+pub struct SecretServiceKeyStore;
+
+impl SecretServiceKeyStore {
+    fn attributes<'a>(
+        network: &'a super::KeyChainNetwork,
+        account_id: Option<&'a str>,
+        public_key: Option<&'a str>,
+    ) -> HashMap<&'a str, &'a str> {
+        let mut attributes = HashMap::new();
+        attributes.insert("application", APPLICATION);
+        attributes.insert("network", network.name());
+        if let Some(account_id) = account_id {
+            attributes.insert("account_id", account_id);
+        }
+        if let Some(public_key) = public_key {
+            attributes.insert("public_key", public_key);
+        }
+        attributes
+    }
+
+    fn label(network: &super::KeyChainNetwork, stored_key: &super::StoredKey) -> String {
+        format!(
+            "{}: {} ({}, {})",
+            APPLICATION,
+            stored_key.account_id,
+            network.name(),
+            stored_key.public_key
+        )
+    }
+
+    fn search(
+        network: &super::KeyChainNetwork,
+        account_id: Option<&str>,
+    ) -> color_eyre::eyre::Result<Vec<(String, super::StoredKey)>> {
+        let service = SecretService::new(EncryptionType::Dh).map_err(connection_error)?;
+        let collection = service.get_default_collection().map_err(report)?;
+        let items = collection
+            .search_items(Self::attributes(network, account_id, None))
+            .map_err(report)?;
+        let mut secrets = vec![];
+        for item in items {
+            if item.is_locked().map_err(report)? {
+                item.unlock().map_err(report)?;
+            }
+            secrets.push((
+                item.get_created().map_err(report)?,
+                item.get_label().map_err(report)?,
+                item.get_secret().map_err(report)?,
+            ));
+        }
+        Ok(Self::stored_keys(secrets))
+    }
+
+    /// The keys of the `(creation time, label, secret)` of the items, newest first; the items
+    /// whose secret is not a key file are skipped
+    fn stored_keys(secrets: Vec<(u64, String, Vec<u8>)>) -> Vec<(String, super::StoredKey)> {
+        let mut stored_keys = secrets
+            .into_iter()
+            .filter_map(|(created, label, secret)| {
+                serde_json::from_slice::<super::StoredKey>(&secret)
+                    .ok()
+                    .map(|stored_key| (created, label, stored_key))
+            })
+            .collect::<Vec<_>>();
+        // The item created last is the key saved last (`update` replaces the secret of an item)
+        stored_keys.sort_by(|(created_a, label_a, _), (created_b, label_b, _)| {
+            created_b.cmp(created_a).then_with(|| label_a.cmp(label_b))
+        });
+        stored_keys
+            .into_iter()
+            .map(|(_, label, stored_key)| (label, stored_key))
+            .collect()
+    }
+
+    fn store(
+        network: &super::KeyChainNetwork,
+        stored_key: &super::StoredKey,
+    ) -> color_eyre::eyre::Result<String> {
+        let service = SecretService::new(EncryptionType::Dh).map_err(connection_error)?;
+        let collection = service.get_default_collection().map_err(report)?;
+        if collection.is_locked().map_err(report)? {
+            collection.unlock().map_err(report)?;
+        }
+        let label = Self::label(network, stored_key);
+        let public_key = stored_key.public_key.to_string();
+        collection
+            .create_item(
+                &label,
+                Self::attributes(network, Some(&stored_key.account_id), Some(&public_key)),
+                serde_json::to_string(stored_key)?.as_bytes(),
+                true,
+                "application/json",
+            )
+            .map_err(report)?;
+        Ok(label)
+    }
+}
+
+fn connection_error(err: secret_service::Error) -> color_eyre::Report {
+    color_eyre::Report::msg(format!(
+        "Failed to connect to the Secret Service (is a keyring daemon running in this D-Bus session?): {:?}",
+        err
+    ))
+}
+
+fn report(err: secret_service::Error) -> color_eyre::Report {
+    color_eyre::Report::msg(format!("Secret Service error: {:?}", err))
+}
+
+impl super::KeyStore for SecretServiceKeyStore {
+    fn location(&self, network: &super::KeyChainNetwork) -> String {
+        format!(
+            "Secret Service (application={}, network={})",
+            APPLICATION,
+            network.name()
+        )
+    }
+
+    fn list(
+        &self,
+        network: &super::KeyChainNetwork,
+    ) -> color_eyre::eyre::Result<Vec<(String, super::StoredKey)>> {
+        Self::search(network, None)
+    }
+
+    fn find(
+        &self,
+        network: &super::KeyChainNetwork,
+        account_id: &str,
+    ) -> color_eyre::eyre::Result<Vec<(String, super::StoredKey)>> {
+        Self::search(network, Some(account_id))
+    }
+
+    fn save(
+        &self,
+        network: &super::KeyChainNetwork,
+        stored_key: &super::StoredKey,
+    ) -> color_eyre::eyre::Result<String> {
+        Self::store(network, stored_key)
+    }
+
+    fn update(
+        &self,
+        network: &super::KeyChainNetwork,
+        stored_key: &super::StoredKey,
+    ) -> color_eyre::eyre::Result<String> {
+        Self::store(network, stored_key)
+    }
+
+    fn remove(
+        &self,
+        network: &super::KeyChainNetwork,
+        stored_key: &super::StoredKey,
+    ) -> color_eyre::eyre::Result<String> {
+        let service = SecretService::new(EncryptionType::Dh).map_err(connection_error)?;
+        let collection = service.get_default_collection().map_err(report)?;
+        let public_key = stored_key.public_key.to_string();
+        let items = collection
+            .search_items(Self::attributes(
+                network,
+                Some(&stored_key.account_id),
+                Some(&public_key),
+            ))
+            .map_err(report)?;
+        if items.is_empty() {
+            return Err(color_eyre::Report::msg(format!(
+                "There is no Secret Service item with the key {} of <{}>",
+                stored_key.public_key, stored_key.account_id
+            )));
+        }
+        if collection.is_locked().map_err(report)? {
+            collection.unlock().map_err(report)?;
+        }
+        for item in items {
+            item.delete().map_err(report)?;
+        }
+        Ok(Self::label(network, stored_key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{KeyChainNetwork, KeyStore, StoredKey};
+    use super::SecretServiceKeyStore;
+
+    #[test]
+    fn items_are_labelled_and_read_back_newest_first() {
+        let network = KeyChainNetwork::new("testnet", crate::consts::DIR_NAME_TESTNET);
+        let stored_keys = ["first", "second"]
+            .iter()
+            .map(|seed| {
+                let secret_key =
+                    near_crypto::SecretKey::from_seed(near_crypto::KeyType::ED25519, seed);
+                StoredKey {
+                    account_id: "alice.testnet".to_string(),
+                    public_key: secret_key.public_key(),
+                    private_key: Some(secret_key),
+                    master_seed_phrase: None,
+                    seed_phrase_hd_path: None,
+                    encrypted_secrets: None,
+                }
+            })
+            .collect::<Vec<_>>();
+        let label = SecretServiceKeyStore::label(&network, &stored_keys[0]);
+        assert_eq!(
+            label,
+            format!(
+                "near-cli: alice.testnet (testnet, {})",
+                stored_keys[0].public_key
+            )
+        );
+        let public_key = stored_keys[0].public_key.to_string();
+        let attributes =
+            SecretServiceKeyStore::attributes(&network, Some("alice.testnet"), Some(&public_key));
+        assert_eq!(attributes.len(), 4);
+        assert_eq!(attributes["application"], "near-cli");
+        assert_eq!(attributes["network"], "testnet");
+        assert_eq!(attributes["account_id"], "alice.testnet");
+        assert_eq!(attributes["public_key"], public_key.as_str());
+        assert_eq!(
+            SecretServiceKeyStore::attributes(&network, None, None).len(),
+            2
+        );
+
+        let secret = |stored_key| serde_json::to_vec(stored_key).unwrap();
+        let found = SecretServiceKeyStore::stored_keys(vec![
+            (1, "first".to_string(), secret(&stored_keys[0])),
+            (3, "not a key".to_string(), b"password".to_vec()),
+            (2, "second".to_string(), secret(&stored_keys[1])),
+        ]);
+        assert_eq!(
+            found
+                .iter()
+                .map(|(label, stored_key)| (label.as_str(), &stored_key.public_key))
+                .collect::<Vec<_>>(),
+            vec![
+                ("second", &stored_keys[1].public_key),
+                ("first", &stored_keys[0].public_key)
+            ]
+        );
+        assert_eq!(found[1].1.private_key, stored_keys[0].private_key);
+    }
+
+    /// Needs a D-Bus session with an unlocked keyring: `cargo test -- --ignored`
+    #[test]
+    #[ignore]
+    fn secret_service_key_store_round_trip() {
+        let network =
+            KeyChainNetwork::new(&format!("near-cli-test-{}", std::process::id()), "unused");
+        let key_store = SecretServiceKeyStore;
+        let stored_keys = (0..2)
+            .map(|_| {
+                let secret_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519);
+                StoredKey {
+                    account_id: "alice.testnet".to_string(),
+                    public_key: secret_key.public_key(),
+                    private_key: Some(secret_key),
+                    master_seed_phrase: None,
+                    seed_phrase_hd_path: None,
+                    encrypted_secrets: None,
+                }
+            })
+            .collect::<Vec<_>>();
+        for stored_key in &stored_keys {
+            key_store.save(&network, stored_key).unwrap();
+            // The creation time of an item has a resolution of a second
+            std::thread::sleep(std::time::Duration::from_secs(1));
+        }
+
+        let found = key_store.find(&network, "alice.testnet").unwrap();
+        assert_eq!(
+            found
+                .iter()
+                .map(|(_, stored_key)| &stored_key.public_key)
+                .collect::<Vec<_>>(),
+            vec![&stored_keys[1].public_key, &stored_keys[0].public_key]
+        );
+        assert_eq!(found[0].1.private_key, stored_keys[1].private_key);
+
+        for stored_key in &stored_keys {
+            key_store.remove(&network, stored_key).unwrap();
+        }
+        assert!(key_store
+            .find(&network, "alice.testnet")
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -1,8 +1,13 @@
 use clap::Clap;
+use std::convert::TryFrom;
 
 mod commands;
 mod common;
+mod config;
 mod consts;
+mod key_store;
+mod transaction_file;
+mod wallet_callback;
 
 type CliResult = color_eyre::eyre::Result<()>;
 
@@ -18,28 +23,55 @@ type CliResult = color_eyre::eyre::Result<()>;
     setting(clap::AppSettings::NextLineHelp)
 )]
 struct CliArgs {
+    /// Output format: plaintext or json
+    #[clap(long, global = true, default_value = "plaintext")]
+    format: self::common::OutputFormat,
+    /// Fail with an error instead of asking for the missing arguments
+    /// (implied when stdin is not a terminal)
+    #[clap(long, global = true)]
+    non_interactive: bool,
+    /// Send mainnet and destructive transactions without asking for confirmation
+    #[clap(long, global = true)]
+    yes: bool,
     #[clap(subcommand)]
     top_level_command: Option<self::commands::CliTopLevelCommand>,
 }
 
 #[derive(Debug)]
 struct Args {
+    output_format: self::common::OutputFormat,
     top_level_command: self::commands::TopLevelCommand,
 }
 
-impl From<CliArgs> for Args {
-    fn from(cli_args: CliArgs) -> Self {
+impl TryFrom<CliArgs> for Args {
+    type Error = color_eyre::Report;
+
+    fn try_from(cli_args: CliArgs) -> color_eyre::eyre::Result<Self> {
         let top_level_command = match cli_args.top_level_command {
-            Some(cli_subcommand) => self::commands::TopLevelCommand::from(cli_subcommand),
-            None => self::commands::TopLevelCommand::choose_command(),
+            Some(cli_subcommand) => self::commands::TopLevelCommand::try_from(cli_subcommand)?,
+            None => self::commands::TopLevelCommand::choose_command()?,
         };
-        Self { top_level_command }
+        Ok(Self {
+            output_format: cli_args.format,
+            top_level_command,
+        })
     }
 }
 
 impl Args {
+    /// The arguments that repeat this command without prompts
+    fn to_cli_args(&self) -> Option<Vec<String>> {
+        let mut args = vec![];
+        if let self::common::OutputFormat::Json = self.output_format {
+            args.push("--format".to_owned());
+            args.push("json".to_owned());
+        }
+        args.extend(self.top_level_command.to_cli_args()?);
+        Some(args)
+    }
+
     async fn process(self) -> CliResult {
-        self.top_level_command.process().await
+        self.top_level_command.process(self.output_format).await
     }
 }
 
@@ -53,9 +85,26 @@ fn main() -> CliResult {
         return Ok(());
     }
 
-    let args = Args::from(cli);
+    self::common::set_non_interactive(cli.non_interactive || !atty::is(atty::Stream::Stdin));
+    self::common::set_assume_yes(cli.yes);
 
     color_eyre::install()?;
 
-    actix::System::new().block_on(args.process())
+    let result = Args::try_from(cli).and_then(|args| {
+        let cli_args = args.to_cli_args();
+        let result = actix::System::new().block_on(args.process());
+        if result.is_ok() && self::common::was_prompted() {
+            self::common::print_command_line(cli_args);
+        }
+        result
+    });
+    if let Some(missing) = result
+        .as_ref()
+        .err()
+        .and_then(|err| err.downcast_ref::<self::common::MissingArgument>())
+    {
+        eprintln!("error: {}", missing);
+        std::process::exit(2);
+    }
+    result
 }
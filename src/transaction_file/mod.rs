@@ -0,0 +1,344 @@
+use near_primitives::borsh::{BorshDeserialize, BorshSerialize};
+
+/// A transaction kept in a file to move it between machines (e.g. to sign it on an offline one).
+/// A `.json` file holds a `TransactionEnvelope`, any other file the raw borsh-serialized transaction.
+#[derive(Debug, Clone)]
+pub struct TransactionFile {
+    pub path: std::path::PathBuf,
+    /// `None` for a raw borsh file, which does not tell whether it is signed
+    pub kind: Option<TransactionKind>,
+    pub network: Option<String>,
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionKind {
+    UnsignedTransaction,
+    SignedTransaction,
+}
+
+/// The JSON form of a transaction file; `summary` is written for the person who checks the
+/// transaction before signing or sending it and is not read back
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct TransactionEnvelope {
+    pub kind: TransactionKind,
+    /// The network the transaction was prepared for, if it was known
+    #[serde(default)]
+    pub network: Option<String>,
+    pub transaction_hash: String,
+    #[serde(default)]
+    pub summary: serde_json::Value,
+    pub base64: String,
+}
+
+fn is_json(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.eq_ignore_ascii_case("json"))
+        .unwrap_or(false)
+}
+
+impl std::str::FromStr for TransactionFile {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let path = std::path::PathBuf::from(s);
+        let data = std::fs::read(&path)
+            .map_err(|err| format!("Failed to read {}: {}", path.display(), err))?;
+        if !is_json(&path) {
+            return Ok(Self {
+                path,
+                kind: None,
+                network: None,
+                bytes: data,
+            });
+        }
+        let envelope: TransactionEnvelope = serde_json::from_slice(&data)
+            .map_err(|err| format!("Failed to parse {}: {}", path.display(), err))?;
+        let bytes = near_primitives::serialize::from_base64(&envelope.base64).map_err(|err| {
+            format!(
+                "base64 transaction sequence of {} is invalid: {}",
+                path.display(),
+                err
+            )
+        })?;
+        Ok(Self {
+            path,
+            kind: Some(envelope.kind),
+            network: envelope.network,
+            bytes,
+        })
+    }
+}
+
+impl std::fmt::Display for TransactionFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path.display())
+    }
+}
+
+/// An unsigned transaction read from a `TransactionFile`
+#[derive(Debug, Clone)]
+pub struct UnsignedTransactionFile {
+    pub path: std::path::PathBuf,
+    pub network: Option<String>,
+    pub inner: near_primitives::transaction::Transaction,
+}
+
+impl std::str::FromStr for UnsignedTransactionFile {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let transaction_file = TransactionFile::from_str(s)?;
+        if transaction_file.kind == Some(TransactionKind::SignedTransaction) {
+            return Err(format!(
+                "{} contains a signed transaction",
+                transaction_file.path.display()
+            ));
+        }
+        let inner =
+            near_primitives::transaction::Transaction::try_from_slice(&transaction_file.bytes)
+                .map_err(|err| {
+                    format!(
+                        "unsigned transaction of {} could not be parsed: {}",
+                        transaction_file.path.display(),
+                        err
+                    )
+                })?;
+        Ok(Self {
+            path: transaction_file.path,
+            network: transaction_file.network,
+            inner,
+        })
+    }
+}
+
+impl std::fmt::Display for UnsignedTransactionFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path.display())
+    }
+}
+
+/// Why a transaction file cannot be used on the network: it was prepared for another one
+/// (files that do not record their network are taken as they are)
+fn network_mismatch(
+    path: &std::path::Path,
+    network: Option<&str>,
+    network_connection_config: &crate::common::ConnectionConfig,
+) -> Option<String> {
+    match network {
+        Some(network) if network != network_connection_config.name() => Some(format!(
+            "{} was prepared for {}, not for {}",
+            path.display(),
+            network,
+            network_connection_config.name()
+        )),
+        _ => None,
+    }
+}
+
+/// Rejects a transaction file prepared for another network than the one it is used on,
+/// unless `--yes` is given
+pub fn check_network(
+    path: &std::path::Path,
+    network: Option<&str>,
+    network_connection_config: &crate::common::ConnectionConfig,
+) -> crate::CliResult {
+    match network_mismatch(path, network, network_connection_config) {
+        None => Ok(()),
+        Some(mismatch) if crate::common::is_assume_yes() => {
+            eprintln!("WARNING: {}", mismatch);
+            Ok(())
+        }
+        Some(mismatch) => Err(color_eyre::Report::msg(format!(
+            "{}; pass --yes to use it anyway",
+            mismatch
+        ))),
+    }
+}
+
+fn save(
+    path: &std::path::Path,
+    kind: TransactionKind,
+    network: Option<String>,
+    bytes: Vec<u8>,
+    transaction_hash: near_primitives::hash::CryptoHash,
+    summary: serde_json::Value,
+) -> crate::CliResult {
+    let data = if is_json(path) {
+        serde_json::to_vec_pretty(&TransactionEnvelope {
+            kind,
+            network,
+            transaction_hash: transaction_hash.to_string(),
+            summary,
+            base64: near_primitives::serialize::to_base64(bytes),
+        })?
+    } else {
+        bytes
+    };
+    std::fs::write(path, data).map_err(|err| {
+        color_eyre::Report::msg(format!("Failed to write {}: {:?}", path.display(), err))
+    })?;
+    eprintln!("The transaction is saved in {}", path.display());
+    Ok(())
+}
+
+pub fn save_unsigned_transaction(
+    path: &std::path::Path,
+    network: Option<String>,
+    transaction: &near_primitives::transaction::Transaction,
+) -> crate::CliResult {
+    save(
+        path,
+        TransactionKind::UnsignedTransaction,
+        network,
+        transaction
+            .try_to_vec()
+            .expect("Transaction is not expected to fail on serialization"),
+        transaction.get_hash_and_size().0,
+        serde_json::json!({
+            "signer_id": transaction.signer_id,
+            "public_key": transaction.public_key,
+            "nonce": transaction.nonce,
+            "receiver_id": transaction.receiver_id,
+            "block_hash": transaction.block_hash,
+            "actions": transaction
+                .actions
+                .iter()
+                .map(crate::common::describe_action)
+                .collect::<Vec<_>>(),
+        }),
+    )
+}
+
+pub fn save_signed_transaction(
+    path: &std::path::Path,
+    network: Option<String>,
+    signed_transaction: &near_primitives::transaction::SignedTransaction,
+) -> crate::CliResult {
+    save(
+        path,
+        TransactionKind::SignedTransaction,
+        network,
+        signed_transaction
+            .try_to_vec()
+            .expect("Transaction is not expected to fail on serialization"),
+        signed_transaction.get_hash(),
+        serde_json::to_value(near_primitives::views::SignedTransactionView::from(
+            signed_transaction.clone(),
+        ))?,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn transaction() -> near_primitives::transaction::Transaction {
+        near_primitives::transaction::Transaction {
+            signer_id: "volodymyr.testnet".to_string(),
+            public_key: near_crypto::SecretKey::from_seed(
+                near_crypto::KeyType::ED25519,
+                "transaction_file",
+            )
+            .public_key(),
+            nonce: 7,
+            receiver_id: "21.testnet".to_string(),
+            block_hash: Default::default(),
+            actions: vec![near_primitives::transaction::Action::Transfer(
+                near_primitives::transaction::TransferAction {
+                    deposit: 10u128.pow(24),
+                },
+            )],
+        }
+    }
+
+    fn temp_path(extension: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!(
+            "near-cli-transaction-{}.{}",
+            std::process::id(),
+            extension
+        ))
+    }
+
+    #[test]
+    fn unsigned_transaction_json_round_trip() {
+        let path = temp_path("json");
+        let transaction = transaction();
+        save_unsigned_transaction(&path, Some("testnet".to_string()), &transaction).unwrap();
+
+        let envelope: TransactionEnvelope =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(envelope.kind, TransactionKind::UnsignedTransaction);
+        assert_eq!(
+            envelope.transaction_hash,
+            transaction.get_hash_and_size().0.to_string()
+        );
+        assert_eq!(
+            envelope.summary["actions"],
+            serde_json::json!([crate::common::describe_action(&transaction.actions[0])])
+        );
+
+        let unsigned_transaction_file =
+            UnsignedTransactionFile::from_str(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            unsigned_transaction_file.network,
+            Some("testnet".to_string())
+        );
+        assert_eq!(unsigned_transaction_file.inner, transaction);
+    }
+
+    #[test]
+    fn unsigned_transaction_raw_round_trip() {
+        let path = temp_path("bin");
+        let transaction = transaction();
+        save_unsigned_transaction(&path, Some("testnet".to_string()), &transaction).unwrap();
+        assert_eq!(
+            std::fs::read(&path).unwrap(),
+            transaction.try_to_vec().unwrap()
+        );
+
+        let unsigned_transaction_file =
+            UnsignedTransactionFile::from_str(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(unsigned_transaction_file.network, None);
+        assert_eq!(unsigned_transaction_file.inner, transaction);
+    }
+
+    #[test]
+    fn signed_transaction_is_not_unsigned() {
+        let path = temp_path("signed.json");
+        let secret_key =
+            near_crypto::SecretKey::from_seed(near_crypto::KeyType::ED25519, "transaction_file");
+        let transaction = transaction();
+        let signature = secret_key.sign(transaction.get_hash_and_size().0.as_ref());
+        let signed_transaction =
+            near_primitives::transaction::SignedTransaction::new(signature, transaction);
+        save_signed_transaction(&path, None, &signed_transaction).unwrap();
+
+        let transaction_file = TransactionFile::from_str(path.to_str().unwrap()).unwrap();
+        assert_eq!(
+            transaction_file.kind,
+            Some(TransactionKind::SignedTransaction)
+        );
+        assert_eq!(
+            transaction_file.bytes,
+            signed_transaction.try_to_vec().unwrap()
+        );
+        assert!(UnsignedTransactionFile::from_str(path.to_str().unwrap()).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn network_of_the_file_has_to_match() {
+        let path = std::path::Path::new("transaction.json");
+        let testnet = crate::common::ConnectionConfig::Testnet;
+        assert_eq!(network_mismatch(path, Some("testnet"), &testnet), None);
+        assert_eq!(network_mismatch(path, None, &testnet), None);
+        assert_eq!(
+            network_mismatch(path, Some("mainnet"), &testnet).as_deref(),
+            Some("transaction.json was prepared for mainnet, not for testnet")
+        );
+    }
+}
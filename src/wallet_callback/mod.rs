@@ -0,0 +1,176 @@
+use futures::StreamExt;
+
+/// How long to wait for the wallet to redirect the browser back by default
+pub const DEFAULT_CALLBACK_TIMEOUT_SECS: u64 = 300;
+
+/// Where the wallet sent the browser once the user was done with it
+#[derive(Debug)]
+pub enum WalletRedirect {
+    /// The query parameters the wallet passed to `success_url` (`callbackUrl`)
+    Approved(std::collections::HashMap<String, String>),
+    /// The wallet went to `failure_url` or passed an `errorCode`
+    Rejected(String),
+}
+
+type WalletRedirectSender = futures::channel::mpsc::UnboundedSender<WalletRedirect>;
+
+/// A short-lived HTTP server on a random port of `127.0.0.1` that catches the wallet redirects
+pub struct CallbackListener {
+    server: actix_web::dev::Server,
+    url: url::Url,
+    redirects: futures::channel::mpsc::UnboundedReceiver<WalletRedirect>,
+}
+
+impl CallbackListener {
+    pub fn start() -> std::io::Result<Self> {
+        let (sender, redirects) = futures::channel::mpsc::unbounded::<WalletRedirect>();
+        let sender = actix_web::web::Data::new(sender);
+        let http_server = actix_web::HttpServer::new(move || {
+            actix_web::App::new()
+                .app_data(sender.clone())
+                .route("/success", actix_web::web::get().to(approved))
+                .route("/failure", actix_web::web::get().to(rejected))
+        })
+        .workers(1)
+        .bind("127.0.0.1:0")?;
+        let url = url::Url::parse(&format!("http://{}/", http_server.addrs()[0]))
+            .expect("A socket address is a valid URL host");
+        Ok(Self {
+            server: http_server.run(),
+            url,
+            redirects,
+        })
+    }
+
+    pub fn success_url(&self) -> url::Url {
+        self.url
+            .join("success")
+            .expect("A relative path is a valid URL")
+    }
+
+    pub fn failure_url(&self) -> url::Url {
+        self.url
+            .join("failure")
+            .expect("A relative path is a valid URL")
+    }
+
+    /// Waits for the first redirect and stops the server; `None` if none came in time
+    pub async fn wait(mut self, timeout: std::time::Duration) -> Option<WalletRedirect> {
+        eprintln!(
+            "Waiting up to {} seconds for the wallet to redirect back...",
+            timeout.as_secs()
+        );
+        let redirect = tokio::time::timeout(timeout, self.redirects.next()).await;
+        self.server.stop(true).await;
+        match redirect {
+            Ok(Some(redirect)) => Some(redirect),
+            Ok(None) | Err(_) => {
+                eprintln!("The wallet did not redirect back in time");
+                None
+            }
+        }
+    }
+}
+
+fn rejection_reason(query: &std::collections::HashMap<String, String>) -> Option<String> {
+    let error_code = query.get("errorCode").or_else(|| query.get("error"))?;
+    Some(match query.get("errorMessage") {
+        Some(error_message) => format!("{} ({})", error_code, error_message),
+        None => error_code.clone(),
+    })
+}
+
+async fn approved(
+    query: actix_web::web::Query<std::collections::HashMap<String, String>>,
+    sender: actix_web::web::Data<WalletRedirectSender>,
+) -> actix_web::HttpResponse {
+    let query = query.into_inner();
+    let redirect = match rejection_reason(&query) {
+        Some(reason) => WalletRedirect::Rejected(reason),
+        None => WalletRedirect::Approved(query),
+    };
+    let _ = sender.unbounded_send(redirect);
+    actix_web::HttpResponse::Ok()
+        .content_type("text/plain; charset=utf-8")
+        .body("NEAR CLI got the answer of the wallet, return to the terminal to finish.")
+}
+
+async fn rejected(
+    query: actix_web::web::Query<std::collections::HashMap<String, String>>,
+    sender: actix_web::web::Data<WalletRedirectSender>,
+) -> actix_web::HttpResponse {
+    let reason = rejection_reason(&query).unwrap_or_else(|| "rejected by the user".to_string());
+    let _ = sender.unbounded_send(WalletRedirect::Rejected(reason));
+    actix_web::HttpResponse::Ok()
+        .content_type("text/plain; charset=utf-8")
+        .body("The request was not completed, return to the terminal.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Starts a listener, sends the browser request to `path?query` and waits for the redirect
+    fn redirect(path: &str, query: &str) -> Option<WalletRedirect> {
+        actix::System::new().block_on(async {
+            let callback_listener = CallbackListener::start().unwrap();
+            let mut url = match path {
+                "success" => callback_listener.success_url(),
+                _ => callback_listener.failure_url(),
+            };
+            url.set_query(Some(query));
+            let response = actix_web::client::Client::default()
+                .get(url.as_str())
+                .send()
+                .await
+                .unwrap();
+            assert!(response.status().is_success());
+            callback_listener
+                .wait(std::time::Duration::from_secs(10))
+                .await
+        })
+    }
+
+    #[test]
+    fn success_redirect_passes_the_query() {
+        match redirect(
+            "success",
+            "account_id=volodymyr.testnet&public_key=ed25519%3AAbc&all_keys=ed25519%3AAbc%2Ced25519%3ADef",
+        ) {
+            Some(WalletRedirect::Approved(query)) => {
+                assert_eq!(query["account_id"], "volodymyr.testnet");
+                assert_eq!(query["public_key"], "ed25519:Abc");
+                assert_eq!(query["all_keys"], "ed25519:Abc,ed25519:Def");
+            }
+            redirect => panic!("Unexpected redirect: {:?}", redirect),
+        }
+    }
+
+    #[test]
+    fn error_code_rejects_the_request() {
+        match redirect(
+            "success",
+            "errorCode=userRejected&errorMessage=User%20rejected",
+        ) {
+            Some(WalletRedirect::Rejected(reason)) => {
+                assert_eq!(reason, "userRejected (User rejected)")
+            }
+            redirect => panic!("Unexpected redirect: {:?}", redirect),
+        }
+        match redirect("failure", "") {
+            Some(WalletRedirect::Rejected(reason)) => assert_eq!(reason, "rejected by the user"),
+            redirect => panic!("Unexpected redirect: {:?}", redirect),
+        }
+    }
+
+    #[test]
+    fn no_redirect_in_time_is_none() {
+        let redirect = actix::System::new().block_on(async {
+            CallbackListener::start()
+                .unwrap()
+                .wait(std::time::Duration::from_millis(100))
+                .await
+        });
+        assert!(redirect.is_none());
+    }
+}